@@ -3,10 +3,11 @@ use criterion::{BenchmarkId, Criterion, Throughput};
 use nucleo::SortStrategy;
 use std::hint::black_box;
 use television::channels::entry_processor::{
-    AnsiProcessor, DisplayProcessor, PlainProcessor,
+    AnsiProcessor, ChannelItem, DisplayProcessor, EntryInjector,
+    PlainProcessor,
 };
 use television::channels::prototypes::SourceSpec;
-use television::matcher::{Matcher, matcher_threads};
+use television::matcher::{Matcher, config::Config, matcher_threads};
 use tokio::runtime::Runtime;
 
 pub fn load_candidates_by_size(c: &mut Criterion) {
@@ -37,11 +38,13 @@ pub fn load_candidates_by_size(c: &mut Criterion) {
                     .unwrap();
 
                     // Plain mode uses Matcher<()> for memory efficiency
-                    let mut matcher = Matcher::<()>::new(
+                    let mut matcher = Matcher::<ChannelItem<()>>::new(
+                        &Config::default(),
                         SortStrategy::Score,
                         matcher_threads(),
                     );
-                    let injector = matcher.injector();
+                    let injector =
+                        EntryInjector::new(matcher.injector(), None);
 
                     television::channels::channel::load_candidates(
                         black_box(source_spec.command),
@@ -81,9 +84,12 @@ pub fn load_candidates_with_ansi(c: &mut Criterion) {
             .unwrap();
 
             // Plain mode uses Matcher<()>
-            let mut matcher =
-                Matcher::<()>::new(SortStrategy::Score, matcher_threads());
-            let injector = matcher.injector();
+            let mut matcher = Matcher::<ChannelItem<()>>::new(
+                &Config::default(),
+                SortStrategy::Score,
+                matcher_threads(),
+            );
+            let injector = EntryInjector::new(matcher.injector(), None);
 
             television::channels::channel::load_candidates(
                 black_box(source_spec.command),
@@ -111,9 +117,12 @@ pub fn load_candidates_with_ansi(c: &mut Criterion) {
             .unwrap();
 
             // ANSI mode uses Matcher<String> to store original
-            let mut matcher =
-                Matcher::<String>::new(SortStrategy::Score, matcher_threads());
-            let injector = matcher.injector();
+            let mut matcher = Matcher::<ChannelItem<String>>::new(
+                &Config::default(),
+                SortStrategy::Score,
+                matcher_threads(),
+            );
+            let injector = EntryInjector::new(matcher.injector(), None);
 
             television::channels::channel::load_candidates(
                 black_box(source_spec.command),
@@ -149,9 +158,12 @@ pub fn load_candidates_with_display_template(c: &mut Criterion) {
             .unwrap();
 
             // Plain mode uses Matcher<()>
-            let mut matcher =
-                Matcher::<()>::new(SortStrategy::Score, matcher_threads());
-            let injector = matcher.injector();
+            let mut matcher = Matcher::<ChannelItem<()>>::new(
+                &Config::default(),
+                SortStrategy::Score,
+                matcher_threads(),
+            );
+            let injector = EntryInjector::new(matcher.injector(), None);
 
             television::channels::channel::load_candidates(
                 black_box(source_spec.command),
@@ -178,9 +190,12 @@ pub fn load_candidates_with_display_template(c: &mut Criterion) {
             .unwrap();
 
             // Display mode uses Matcher<String> to store original
-            let mut matcher =
-                Matcher::<String>::new(SortStrategy::Score, matcher_threads());
-            let injector = matcher.injector();
+            let mut matcher = Matcher::<ChannelItem<String>>::new(
+                &Config::default(),
+                SortStrategy::Score,
+                matcher_threads(),
+            );
+            let injector = EntryInjector::new(matcher.injector(), None);
 
             television::channels::channel::load_candidates(
                 black_box(source_spec.command),
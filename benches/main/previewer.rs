@@ -6,6 +6,7 @@ use television::{
         prototypes::{CommandSpec, Template},
     },
     previewer::try_preview,
+    utils::hashmaps::FxIndexSet,
 };
 use tokio::sync::mpsc;
 
@@ -27,9 +28,21 @@ fn bench_preview(
             let command = black_box(make_command(command));
             let (tx, mut rx) = mpsc::unbounded_channel();
 
-            try_preview(command, 0, None, None, None, entry, tx, None)
-                .await
-                .unwrap();
+            try_preview(
+                command,
+                None,
+                0,
+                None,
+                None,
+                None,
+                entry,
+                FxIndexSet::default(),
+                String::from(" "),
+                tx,
+                None,
+            )
+            .await
+            .unwrap();
 
             let _ = rx.recv().await;
         });
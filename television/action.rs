@@ -26,6 +26,24 @@ pub enum Action {
     GoToInputStart,
     /// Move the cursor to the end of the input buffer.
     GoToInputEnd,
+    /// Move the cursor to the start of the previous word.
+    GoToPrevWord,
+    /// Move the cursor to the start of the next word.
+    GoToNextWord,
+    /// Delete the next word from the input buffer.
+    DeleteNextWord,
+    /// Delete everything from the cursor to the end of the input buffer.
+    DeleteToInputEnd,
+    /// Swap the character before the cursor with the one under it.
+    TransposeChars,
+    /// Insert the most recently deleted text at the cursor.
+    Yank,
+    /// Replace the text just yanked with the previously deleted text.
+    YankPop,
+    /// Undo the last edit of the input buffer.
+    Undo,
+    /// Redo the last undone edit of the input buffer.
+    Redo,
     // rendering actions
     /// Render the terminal user interface screen.
     #[serde(skip)]
@@ -84,12 +102,18 @@ pub enum Action {
     /// Toggle a UI feature.
     ToggleRemoteControl,
     ToggleActionPicker,
+    /// Search the query history and run the chosen query.
+    ToggleHistoryPicker,
     ToggleHelp,
     ToggleStatusBar,
     TogglePreview,
     /// Switch between the portrait and landscape modes.
     #[serde(rename = "toggle_layout")]
     ToggleOrientation,
+    /// Switch to normal mode, where keys only trigger actions.
+    EnterNormalMode,
+    /// Switch to insert mode, where keys type text.
+    EnterInsertMode,
     /// Signal an error with the given message.
     #[serde(skip)]
     Error(String),
@@ -105,6 +129,12 @@ pub enum Action {
     /// Switch to the specified channel directly via shortcut.
     #[serde(skip)]
     SwitchToChannel(String),
+    /// Replace the current query with the given one.
+    #[serde(skip)]
+    SetQuery(String),
+    /// Replace the current preview command with the given one.
+    #[serde(skip)]
+    ChangePreview(String),
     /// Timer action for watch mode to trigger periodic reloads.
     #[serde(skip)]
     WatchTimer,
@@ -112,6 +142,8 @@ pub enum Action {
     SelectPrevHistory,
     /// Navigate to the next entry in the history.
     SelectNextHistory,
+    /// Forget the frecency of the currently selected entry.
+    ForgetEntry,
     // Mouse and position-aware actions
     /// Select an entry at a specific position (e.g., from mouse click)
     #[serde(skip)]
@@ -303,10 +335,13 @@ impl Action {
                 | Action::ScrollPreviewHalfPageUp
                 | Action::ScrollPreviewHalfPageDown
                 | Action::CyclePreviews
+                | Action::ChangePreview(_)
                 | Action::ToggleHelp
                 | Action::TogglePreview
                 | Action::ToggleStatusBar
                 | Action::ToggleOrientation
+                | Action::EnterNormalMode
+                | Action::EnterInsertMode
                 | Action::CopyEntryToClipboard
                 | Action::OpenEntry
                 | Action::Render
@@ -350,6 +385,15 @@ impl Action {
             Action::GoToNextChar => "Move cursor right",
             Action::GoToInputStart => "Move to start",
             Action::GoToInputEnd => "Move to end",
+            Action::GoToPrevWord => "Move to previous word",
+            Action::GoToNextWord => "Move to next word",
+            Action::DeleteNextWord => "Delete next word",
+            Action::DeleteToInputEnd => "Delete to end",
+            Action::TransposeChars => "Transpose chars",
+            Action::Yank => "Yank deleted text",
+            Action::YankPop => "Cycle yanked text",
+            Action::Undo => "Undo",
+            Action::Redo => "Redo",
 
             // Rendering actions (typically not shown in help)
             Action::Render => "Render",
@@ -388,10 +432,13 @@ impl Action {
             // Toggle actions
             Action::ToggleRemoteControl => "Toggle remote control",
             Action::ToggleActionPicker => "Toggle action picker",
+            Action::ToggleHistoryPicker => "Search history",
             Action::ToggleHelp => "Toggle help",
             Action::ToggleStatusBar => "Toggle status bar",
             Action::TogglePreview => "Toggle preview",
             Action::ToggleOrientation => "Toggle layout",
+            Action::EnterNormalMode => "Normal mode",
+            Action::EnterInsertMode => "Insert mode",
 
             // Error and no-op
             Action::Error(_) => "Error",
@@ -402,11 +449,14 @@ impl Action {
             Action::CyclePreviews => "Cycle previews",
             Action::ReloadSource => "Reload source",
             Action::SwitchToChannel(_) => "Switch to channel",
+            Action::SetQuery(_) => "Set query",
+            Action::ChangePreview(_) => "Change preview",
             Action::WatchTimer => "Watch timer",
 
             // History actions
             Action::SelectPrevHistory => "Previous history",
             Action::SelectNextHistory => "Next history",
+            Action::ForgetEntry => "Forget entry",
 
             // Mouse actions
             Action::SelectEntryAtPosition(_, _) => "Select at position",
@@ -11,16 +11,18 @@ use crate::{
     event::{ControlEvent, Event, EventLoop, Key},
     frecency::{Frecency, FrecencyHandle},
     history::History,
+    keymap::{InputMode, KeyResolution},
+    listen::{ListenState, listen},
     mouse::get_action_for_mouse_event,
     render::{RenderingTask, UiState, render},
-    television::{Mode, Television},
+    session::Sessions,
+    television::{Mode, ParamPrompt, Television},
     tui::{IoStream, Tui, TuiMode},
-    utils::command::execute_action,
+    utils::{command::execute_action, hashmaps::FxIndexSet},
 };
 use anyhow::Result;
-use rustc_hash::FxHashSet;
 use std::sync::Arc;
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, watch};
 use tracing::{debug, error, trace};
 
 /// The main application struct that holds the state of the application.
@@ -59,58 +61,79 @@ pub struct App {
     history: History,
     /// Frecency manager for ranking previously-selected entries
     frecency: FrecencyHandle,
+    /// Session state of the channels that resume where they were left
+    sessions: Sessions,
+    /// Control server task handle, see `--listen`
+    listen_task: Option<tokio::task::JoinHandle<()>>,
+    /// Publishes state snapshots to the control server clients
+    listen_state_tx: Option<watch::Sender<ListenState>>,
 }
 
 /// The outcome of an action.
 #[derive(Debug, PartialEq)]
 pub enum ActionOutcome {
-    Entries(FxHashSet<Entry>),
-    EntriesWithExpect(FxHashSet<Entry>, Key),
+    Entries(FxIndexSet<Entry>),
+    EntriesWithExpect(FxIndexSet<Entry>, Key),
     Input(String),
     None,
-    ExternalAction(ActionSpec, FxHashSet<Entry>),
+    ExternalAction(ActionSpec, FxIndexSet<Entry>),
 }
 
 /// The result of the application.
 #[derive(Debug)]
 pub struct AppOutput {
-    pub selected_entries: Option<FxHashSet<Entry>>,
+    pub selected_entries: Option<FxIndexSet<Entry>>,
     pub expect_key: Option<Key>,
-    pub external_action: Option<(ActionSpec, FxHashSet<Entry>)>,
+    pub external_action: Option<(ActionSpec, FxIndexSet<Entry>)>,
+    /// The query text when the application exited.
+    pub query: String,
+    /// The name of the channel that was active when the application exited.
+    pub channel: String,
+    /// The index of the source command that was active when the application
+    /// exited.
+    pub source_index: usize,
 }
 
 impl AppOutput {
     pub fn new(action_outcome: ActionOutcome) -> Self {
-        match action_outcome {
-            ActionOutcome::Entries(entries) => Self {
-                selected_entries: Some(entries),
-                expect_key: None,
-                external_action: None,
-            },
-            ActionOutcome::EntriesWithExpect(entries, expect_key) => Self {
-                selected_entries: Some(entries),
-                expect_key: Some(expect_key),
-                external_action: None,
-            },
-            ActionOutcome::Input(input) => Self {
-                selected_entries: Some(FxHashSet::from_iter([Entry::new(
-                    input,
-                )])),
-                expect_key: None,
-                external_action: None,
-            },
-            ActionOutcome::None => Self {
-                selected_entries: None,
-                expect_key: None,
-                external_action: None,
-            },
-            ActionOutcome::ExternalAction(action_spec, entries) => Self {
-                selected_entries: None,
-                expect_key: None,
-                external_action: Some((action_spec, entries)),
-            },
+        let (selected_entries, expect_key, external_action) =
+            match action_outcome {
+                ActionOutcome::Entries(entries) => (Some(entries), None, None),
+                ActionOutcome::EntriesWithExpect(entries, expect_key) => {
+                    (Some(entries), Some(expect_key), None)
+                }
+                ActionOutcome::Input(input) => (
+                    Some(FxIndexSet::from_iter([Entry::new(input)])),
+                    None,
+                    None,
+                ),
+                ActionOutcome::None => (None, None, None),
+                ActionOutcome::ExternalAction(action_spec, entries) => {
+                    (None, None, Some((action_spec, entries)))
+                }
+            };
+        Self {
+            selected_entries,
+            expect_key,
+            external_action,
+            query: String::new(),
+            channel: String::new(),
+            source_index: 0,
         }
     }
+
+    /// Records the state of the application at exit time.
+    pub fn with_state(
+        mut self,
+        query: String,
+        channel: String,
+        source_index: usize,
+    ) -> Self {
+        self.query = query;
+        self.channel = channel;
+        self.source_index = source_index;
+        self
+    }
 }
 
 const EVENT_BUF_SIZE: usize = 4;
@@ -127,10 +150,13 @@ impl App {
 
         // Create frecency handle before Television so we can pass it
         let merged_for_frecency = layered_config.merge();
-        let frecency = Arc::new(Frecency::new(
-            merged_for_frecency.frecency_max_entries,
-            &merged_for_frecency.data_dir,
-        ));
+        let frecency = Arc::new(
+            Frecency::new(
+                merged_for_frecency.frecency_max_entries,
+                &merged_for_frecency.data_dir,
+            )
+            .with_config(merged_for_frecency.frecency_config.clone()),
+        );
         if let Err(e) = frecency.init() {
             error!("Failed to initialize frecency: {}", e);
         }
@@ -152,6 +178,12 @@ impl App {
             error!("Failed to initialize history: {}", e);
         }
 
+        let mut sessions =
+            Sessions::new(&television.merged_config.data_dir.clone());
+        if let Err(e) = sessions.init() {
+            error!("Failed to initialize sessions: {}", e);
+        }
+
         let mut app = Self {
             television,
             should_quit: false,
@@ -167,11 +199,15 @@ impl App {
             watch_timer_task: None,
             history,
             frecency,
+            sessions,
+            listen_task: None,
+            listen_state_tx: None,
         };
 
         // populate input_map by going through all cable channels and adding their shortcuts if remote
         // control is present
         app.update_input_map();
+        app.restore_session();
 
         app
     }
@@ -224,6 +260,71 @@ impl App {
         self.start_watch_timer();
     }
 
+    /// Start the control server if a socket was given with `--listen`.
+    fn start_listening(&mut self) -> Result<()> {
+        if let Some(path) = &self.television.merged_config.listen {
+            let (state_tx, _) = watch::channel(self.listen_state());
+            self.listen_task =
+                Some(listen(path, self.action_tx.clone(), state_tx.clone())?);
+            self.listen_state_tx = Some(state_tx);
+        }
+        Ok(())
+    }
+
+    /// Stop the control server and remove its socket.
+    fn stop_listening(&mut self) {
+        if let Some(task) = self.listen_task.take() {
+            task.abort();
+            if let Some(path) = &self.television.merged_config.listen
+                && let Err(e) = std::fs::remove_file(path)
+            {
+                error!("Failed to remove listen socket: {}", e);
+            }
+        }
+        self.listen_state_tx = None;
+    }
+
+    /// A snapshot of the state exposed to the control server clients.
+    fn listen_state(&self) -> ListenState {
+        let channel = &self.television.channel;
+        ListenState {
+            channel: self.television.current_channel(),
+            query: self.television.current_pattern.clone(),
+            current_entry: self
+                .television
+                .currently_selected
+                .as_ref()
+                .map(|entry| entry.raw.clone()),
+            selected_entries: channel
+                .selected_entries()
+                .iter()
+                .map(|entry| entry.raw.clone())
+                .collect(),
+            matched_count: channel.result_count(),
+            total_count: channel.total_count(),
+            running: channel.running(),
+        }
+    }
+
+    /// Publish the current state to the control server clients if it changed.
+    ///
+    /// Nothing is computed while no client is connected.
+    fn update_listen_state(&self) {
+        if let Some(state_tx) = &self.listen_state_tx
+            && state_tx.receiver_count() > 0
+        {
+            let state = self.listen_state();
+            state_tx.send_if_modified(|current| {
+                if *current == state {
+                    false
+                } else {
+                    *current = state;
+                    true
+                }
+            });
+        }
+    }
+
     /// Update the `input_map` from the television's current config.
     ///
     /// This should be called whenever the channel changes to ensure the `input_map` includes the
@@ -253,6 +354,33 @@ impl App {
         );
     }
 
+    /// Restores the saved session of the current channel, if it resumes
+    /// where it was left.
+    fn restore_session(&mut self) {
+        if !self.television.merged_config.channel_resume {
+            return;
+        }
+        if let Some(state) = self
+            .sessions
+            .get(&self.television.merged_config.channel_name)
+        {
+            self.television.restore_session(state);
+        }
+    }
+
+    /// Records the session state of the current channel, done when the
+    /// channel is left and on exit rather than after every action.
+    fn update_session(&mut self) {
+        if self.television.merged_config.channel_resume
+            && self.television.param_prompt.is_none()
+        {
+            self.sessions.update(
+                &self.television.merged_config.channel_name,
+                self.television.session_state(),
+            );
+        }
+    }
+
     /// Run the application main loop.
     ///
     /// This function will start the event loop and the rendering loop and handle
@@ -272,6 +400,10 @@ impl App {
         is_output_tty: bool,
         headless: bool,
     ) -> Result<AppOutput> {
+        // Control server, started first so that errors are reported before
+        // entering the TUI
+        self.start_listening()?;
+
         // Rendering loop
         if !headless {
             debug!("Starting rendering loop");
@@ -339,7 +471,10 @@ impl App {
         // Start watch timer if configured
         self.start_watch_timer();
 
-        self.television.channel.load();
+        // channels waiting for parameters are loaded once they get them
+        if self.television.param_prompt.is_none() {
+            self.television.channel.load();
+        }
 
         // Main loop
         debug!("Starting event handling loop");
@@ -371,8 +506,11 @@ impl App {
             trace!("Event buffer processed, handling actions...");
             // It's important that this shouldn't block if no actions are available
             action_outcome = self.handle_actions(&mut action_buf).await?;
+            self.update_listen_state();
 
-            if self.television.merged_config.select_1
+            if self.television.param_prompt.is_some() {
+                // the channel hasn't started yet
+            } else if self.television.merged_config.select_1
                 && !self.television.channel.running()
                 && self.television.channel.result_count() == 1
             {
@@ -418,12 +556,23 @@ impl App {
                     error!("Failed to persist frecency: {}", e);
                 }
 
+                self.update_session();
+                if let Err(e) = self.sessions.save_to_file() {
+                    error!("Failed to persist sessions: {}", e);
+                }
+
+                self.stop_listening();
+
                 // wait for the rendering task to finish
                 if let Some(rendering_task) = self.render_task.take() {
                     rendering_task.await?.expect("Rendering task failed");
                 }
 
-                return Ok(AppOutput::new(action_outcome));
+                return Ok(AppOutput::new(action_outcome).with_state(
+                    self.television.current_pattern.clone(),
+                    self.television.current_channel(),
+                    self.television.channel.source_index(),
+                ));
             }
         }
     }
@@ -448,27 +597,11 @@ impl App {
     /// # Returns
     /// A vector of actions that correspond to the given event. Multiple actions
     /// will be returned for keys/events bound to action sequences.
-    fn convert_event_to_actions(&self, event: Event<Key>) -> Vec<Action> {
+    fn convert_event_to_actions(&mut self, event: Event<Key>) -> Vec<Action> {
+        let prompting = self.television.param_prompt.is_some()
+            && matches!(event, Event::Input(_) | Event::Mouse(_));
         let actions = match event {
-            Event::Input(keycode) => {
-                // First try to get actions based on keybindings
-                if let Some(actions) = self
-                    .television
-                    .merged_config
-                    .input_map
-                    .get_actions_for_key(&keycode, &self.television.mode)
-                {
-                    let actions_vec = actions.as_slice().to_vec();
-                    debug!("Keybinding found: {actions_vec:?}");
-                    actions_vec
-                } else {
-                    // fallback to text input events
-                    match keycode {
-                        Key::Char(c) => vec![Action::AddInputChar(c)],
-                        _ => vec![Action::NoOp],
-                    }
-                }
-            }
+            Event::Input(keycode) => self.convert_key_to_actions(keycode),
             Event::Mouse(me) => {
                 vec![get_action_for_mouse_event(
                     me,
@@ -491,13 +624,68 @@ impl App {
             trace!("Converted {event:?} to actions: {non_tick_actions:?}");
         }
 
-        // Filter out NoOp actions
+        // Filter out NoOp actions, and the ones the parameter prompt
+        // doesn't handle while it is shown
         actions
             .into_iter()
             .filter(|action| *action != Action::NoOp)
+            .filter(|action| !prompting || ParamPrompt::accepts(action))
             .collect()
     }
 
+    /// Resolve a key press to actions, taking the keys pressed before it
+    /// into account when they started a key sequence.
+    fn convert_key_to_actions(&mut self, key: Key) -> Vec<Action> {
+        // the parameter prompt always expects text
+        let input_mode = if self.television.param_prompt.is_some() {
+            InputMode::Insert
+        } else {
+            self.television.input_mode
+        };
+        let had_pending_keys = !self.television.pending_keys.is_empty();
+        let mut keys = std::mem::take(&mut self.television.pending_keys);
+        keys.push(key);
+
+        let input_map = &self.television.merged_config.input_map;
+        let mut resolution =
+            input_map.resolve(&keys, &self.television.mode, input_mode);
+        if resolution == KeyResolution::Unbound && keys.len() > 1 {
+            // the sequence is abandoned: `esc` only cancels it while other
+            // keys are handled on their own
+            if key == Key::Esc {
+                return vec![Action::Render];
+            }
+            keys = vec![key];
+            resolution =
+                input_map.resolve(&keys, &self.television.mode, input_mode);
+        }
+
+        let mut actions = match resolution {
+            KeyResolution::Actions(actions) => {
+                let actions_vec = actions.into_vec();
+                debug!("Keybinding found: {actions_vec:?}");
+                actions_vec
+            }
+            KeyResolution::Pending => {
+                debug!("Waiting for the next key of a sequence: {keys:?}");
+                self.television.pending_keys = keys;
+                vec![Action::Render]
+            }
+            // fallback to text input events, which normal mode ignores
+            KeyResolution::Unbound => match key {
+                Key::Char(c) if input_mode == InputMode::Insert => {
+                    vec![Action::AddInputChar(c)]
+                }
+                _ => vec![Action::NoOp],
+            },
+        };
+        // clear the pending keys indicator
+        if had_pending_keys && self.television.pending_keys.is_empty() {
+            actions.push(Action::Render);
+        }
+        actions
+    }
+
     /// Handle actions.
     ///
     /// This function will handle all actions that are sent to the application.
@@ -524,11 +712,21 @@ impl App {
                 }
                 match action {
                     Action::Quit => {
-                        if self.television.mode == Mode::RemoteControl {
+                        if self
+                            .television
+                            .param_prompt
+                            .as_ref()
+                            .is_some_and(ParamPrompt::is_cancellable)
+                        {
+                            // the television handler dismisses the prompt
+                        } else if self.television.mode == Mode::RemoteControl {
                             self.action_tx
                                 .send(Action::ToggleRemoteControl)?;
                         } else if self.television.mode == Mode::ActionPicker {
                             self.action_tx.send(Action::ToggleActionPicker)?;
+                        } else if self.television.mode == Mode::HistoryPicker {
+                            self.action_tx
+                                .send(Action::ToggleHistoryPicker)?;
                         } else {
                             self.stop_watch_timer();
                             self.should_quit = true;
@@ -608,6 +806,17 @@ impl App {
                             self.television.set_pattern("");
                         }
                     }
+                    Action::ToggleHistoryPicker => {
+                        // the picker is rebuilt each time it is opened to
+                        // show the latest entries
+                        if self.television.mode != Mode::HistoryPicker
+                            && self.television.merged_config.history_size > 0
+                        {
+                            self.television.init_history_picker(
+                                &self.history.get_scoped_entries(),
+                            );
+                        }
+                    }
                     Action::ExternalAction(ref action_name) => {
                         debug!("External action triggered: {}", action_name);
 
@@ -652,23 +861,36 @@ impl App {
                     }
                     _ => {}
                 }
+                if matches!(action, Action::SwitchToChannel(_)) {
+                    self.update_session();
+                }
                 // Check if we're switching from remote control to channel mode
                 let was_remote_control =
                     self.television.mode == Mode::RemoteControl;
+                let was_channel = self.television.mode == Mode::Channel;
+                let was_prompting = self.television.param_prompt.is_some();
 
                 // forward action to the television handler
                 if let Some(action) = self.television.update(&action)? {
                     self.action_tx.send(action)?;
                 }
+                // pickers may switch to another channel once confirmed
+                if was_channel && self.television.mode != Mode::Channel {
+                    self.update_session();
+                }
 
                 // Update watch timer and history
                 if was_remote_control
                     && matches!(action, Action::ConfirmSelection)
                     && self.television.mode == Mode::Channel
                     || matches!(action, Action::SwitchToChannel(_))
+                    || was_prompting
+                        && matches!(action, Action::ConfirmSelection)
+                        && self.television.param_prompt.is_none()
                 {
                     self.update_history();
                     self.restart_watch_timer();
+                    self.restore_session();
                 }
             }
         }
@@ -678,7 +900,7 @@ impl App {
     fn run_external_command_fork(
         &self,
         action_spec: &ActionSpec,
-        entries: &FxHashSet<Entry>,
+        entries: &FxIndexSet<Entry>,
     ) -> Result<()> {
         // suspend the event loop
         self.event_control_tx
@@ -708,7 +930,7 @@ impl App {
     fn run_external_command_execute(
         &mut self,
         action_spec: &ActionSpec,
-        entries: &FxHashSet<Entry>,
+        entries: &FxIndexSet<Entry>,
     ) -> Result<()> {
         // cleanup
         self.render_tx.send(RenderingTask::Quit)?;
@@ -727,6 +949,11 @@ impl App {
             error!("Failed to persist frecency: {}", e);
         }
 
+        self.update_session();
+        if let Err(e) = self.sessions.save_to_file() {
+            error!("Failed to persist sessions: {}", e);
+        }
+
         execute_action(action_spec, entries).map_err(|e| {
             error!("Failed to execute external action: {}", e);
             anyhow::anyhow!("Failed to execute external action: {}", e)
@@ -747,7 +974,7 @@ impl App {
                 let _ = self.render_tx.send(RenderingTask::Quit);
             }
 
-            return Some(ActionOutcome::Entries(FxHashSet::from_iter([
+            return Some(ActionOutcome::Entries(FxIndexSet::from_iter([
                 unique_entry.clone(),
             ])));
         }
@@ -767,7 +994,9 @@ impl App {
                 let _ = self.render_tx.send(RenderingTask::Quit);
             }
 
-            ActionOutcome::Entries(FxHashSet::from_iter([first_entry.clone()]))
+            ActionOutcome::Entries(FxIndexSet::from_iter(
+                [first_entry.clone()],
+            ))
         } else {
             debug!("No entries available, exiting with None");
             self.should_quit = true;
@@ -780,10 +1009,15 @@ impl App {
         }
     }
 
-    fn record_selection(&mut self, entries: &FxHashSet<Entry>) -> Result<()> {
+    fn record_selection(&mut self, entries: &FxIndexSet<Entry>) -> Result<()> {
         let channel_name = self.television.current_channel();
         for entry in entries {
-            self.frecency.record_access(&channel_name, &entry.raw);
+            let key = self.television.channel.frecency_key(&entry.raw);
+            self.frecency.record_access(&channel_name, &key);
+            if let Some(scope) = self.television.channel.frecency_scope() {
+                self.frecency
+                    .record_scoped_access(scope, &channel_name, &key);
+            }
         }
 
         let query = self.television.current_pattern.clone();
@@ -2,11 +2,14 @@ use crate::{
     action::Action, channels::prototypes::ChannelPrototype,
     config::Keybindings, errors::unknown_channel_exit, event::Key,
 };
+use anyhow::{Result, anyhow, bail};
 use rustc_hash::FxHashMap;
+use serde::Deserialize;
 use std::{
     ops::Deref,
     path::{Path, PathBuf},
 };
+use toml::{Table, Value};
 use tracing::{debug, error};
 use walkdir::WalkDir;
 
@@ -99,7 +102,7 @@ pub const CHANNEL_FILE_FORMAT: &str = "toml";
 /// ```
 pub const CABLE_DIR_NAME: &str = "cable";
 
-fn get_cable_files<P>(cable_dir: P) -> Vec<PathBuf>
+pub(crate) fn get_cable_files<P>(cable_dir: P) -> Vec<PathBuf>
 where
     P: AsRef<Path>,
 {
@@ -120,13 +123,128 @@ where
         .collect::<Vec<_>>()
 }
 
+/// The raw tables of channel files indexed by channel name, used to resolve
+/// `metadata.extends`.
+pub(crate) type ChannelTables = FxHashMap<String, Table>;
+
+/// Parses the channel files into tables indexed by channel name, ignoring
+/// files that aren't valid TOML or lack a name.
+pub(crate) fn channel_tables<'a>(
+    contents: impl IntoIterator<Item = &'a String>,
+) -> ChannelTables {
+    contents
+        .into_iter()
+        .filter_map(|content| {
+            let table = toml::from_str::<Table>(content).ok()?;
+            let name = metadata_str(&table, "name")?.to_string();
+            Some((name, table))
+        })
+        .collect()
+}
+
+fn metadata_str<'a>(table: &'a Table, key: &str) -> Option<&'a str> {
+    table.get("metadata")?.get(key)?.as_str()
+}
+
+/// The name of the channel a channel table extends, if any.
+pub(crate) fn extends(table: &Table) -> Option<&str> {
+    metadata_str(table, "extends")
+}
+
+/// Resolves `metadata.extends` by deep-merging `table` over the chain of
+/// channels it extends.
+///
+/// Tables are merged key by key while any other value, arrays included, is
+/// replaced. The name, description and shortcut of a parent are never
+/// inherited.
+pub(crate) fn resolve_extends(
+    table: &Table,
+    channels: &ChannelTables,
+) -> Result<Table> {
+    let name = metadata_str(table, "name").unwrap_or_default();
+    let mut chain = vec![name];
+    let mut ancestors = Vec::new();
+    let mut current = table;
+    while let Some(parent) = extends(current) {
+        if chain.contains(&parent) {
+            chain.push(parent);
+            bail!("inheritance cycle: {}", chain.join(" -> "));
+        }
+        current = channels.get(parent).ok_or_else(|| {
+            anyhow!(
+                "channel `{}` extends unknown channel `{}`",
+                chain.last().unwrap(),
+                parent
+            )
+        })?;
+        chain.push(parent);
+        ancestors.push(current);
+    }
+
+    let mut merged = Table::new();
+    for ancestor in ancestors.into_iter().rev() {
+        merge_tables(&mut merged, inheritable(ancestor));
+    }
+    merge_tables(&mut merged, table.clone());
+    Ok(merged)
+}
+
+/// The part of a parent channel its children inherit.
+fn inheritable(table: &Table) -> Table {
+    let mut table = table.clone();
+    if let Some(Value::Table(metadata)) = table.get_mut("metadata") {
+        metadata.remove("name");
+        metadata.remove("description");
+    }
+    if let Some(Value::Table(keybindings)) = table.get_mut("keybindings") {
+        keybindings.remove("shortcut");
+    }
+    table
+}
+
+fn merge_tables(base: &mut Table, overlay: Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(&key), value) {
+            (Some(Value::Table(base)), Value::Table(overlay)) => {
+                merge_tables(base, overlay);
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+/// Parses a channel file, resolving the channel it extends if any.
+pub(crate) fn parse_prototype(
+    content: &str,
+    channels: &ChannelTables,
+) -> Result<ChannelPrototype> {
+    let table = toml::from_str::<Table>(content)?;
+    if extends(&table).is_none() {
+        // parse from the source text to get errors with line numbers
+        return Ok(toml::from_str::<ChannelPrototype>(content)?);
+    }
+    let merged = resolve_extends(&table, channels)?;
+    Ok(ChannelPrototype::deserialize(Value::Table(merged))?)
+}
+
 fn load_prototypes(
-    toml_prototypes: FxHashMap<PathBuf, String>,
+    toml_prototypes: &FxHashMap<PathBuf, String>,
 ) -> Vec<ChannelPrototype> {
+    // channels may extend any other channel of the cable
+    let channels = channel_tables(toml_prototypes.values());
     toml_prototypes
-        .into_iter()
+        .iter()
         .filter_map(|(path, content)| {
-            match toml::from_str::<ChannelPrototype>(&content) {
+            let prototype = parse_prototype(content, &channels).and_then(
+                |mut prototype| {
+                    prototype.validate()?;
+                    prototype.resolve_builtin_fallbacks();
+                    Ok(prototype)
+                },
+            );
+            match prototype {
                 Ok(prototype) => {
                     debug!(
                         "Loaded cable channel prototype from {}: {}",
@@ -170,6 +288,18 @@ where
 {
     let cable_dir = cable_dir.as_ref();
     debug!("Using cable directory: {}", cable_dir.to_string_lossy());
+    let prototypes = load_prototypes(&read_cable_files(cable_dir));
+
+    debug!("Loaded {} cable channels", prototypes.len());
+
+    Cable::from_prototypes(prototypes)
+}
+
+/// Reads the default channel files and the ones found in `cable_dir`,
+/// indexed by file name.
+pub(crate) fn read_cable_files(
+    cable_dir: &Path,
+) -> FxHashMap<PathBuf, String> {
     let cable_files = get_cable_files(cable_dir);
     debug!("Found cable channel files: {:?}", cable_files);
 
@@ -197,11 +327,7 @@ where
             .collect::<FxHashMap<_, _>>(),
     );
 
-    let prototypes = load_prototypes(cable_map);
-
-    debug!("Loaded {} cable channels", prototypes.len());
-
-    Cable::from_prototypes(prototypes)
+    cable_map
 }
 
 #[cfg(unix)]
@@ -258,3 +384,111 @@ const DEFAULT_CABLE_FILES: &[(&str, &str)] = &[
     ),
     ("text.toml", include_str!("../cable/windows/text.toml")),
 ];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::channels::prototypes::ExecutionMode;
+    use std::str::FromStr;
+
+    fn channels(contents: &[&str]) -> ChannelTables {
+        let contents =
+            contents.iter().map(ToString::to_string).collect::<Vec<_>>();
+        channel_tables(&contents)
+    }
+
+    #[test]
+    fn test_parse_prototype_extends() {
+        let channels = channels(&[
+            r#"
+[metadata]
+name = "base"
+description = "The base channel"
+requirements = ["bat"]
+
+[source]
+command = "fd -t f"
+display = "{split:/:-1}"
+
+[preview]
+command = "bat {}"
+
+[keybindings]
+shortcut = "f1"
+ctrl-e = "actions:edit"
+
+[actions.edit]
+command = "vim {}"
+mode = "execute"
+"#,
+            r#"
+[metadata]
+name = "middle"
+extends = "base"
+
+[preview]
+command = "cat {}"
+"#,
+        ]);
+
+        let prototype = parse_prototype(
+            r#"
+[metadata]
+name = "child"
+extends = "middle"
+
+[source]
+command = "fd -t d"
+
+[actions.edit]
+mode = "fork"
+"#,
+            &channels,
+        )
+        .unwrap();
+
+        assert_eq!(prototype.metadata.name, "child");
+        assert_eq!(prototype.metadata.description, None);
+        assert_eq!(prototype.metadata.requirements[0].bin_name, "bat");
+        assert_eq!(
+            prototype.source.command.inner[0].template().raw(),
+            "fd -t d"
+        );
+        assert_eq!(prototype.source.display.unwrap().raw(), "{split:/:-1}");
+        assert_eq!(
+            prototype.preview.unwrap().command.inner[0].template().raw(),
+            "cat {}"
+        );
+        let keybindings = prototype.keybindings.unwrap();
+        assert_eq!(keybindings.shortcut, None);
+        assert!(
+            keybindings
+                .bindings
+                .contains_key(&Key::from_str("ctrl-e").unwrap())
+        );
+        let edit = &prototype.actions["edit"];
+        assert_eq!(edit.command.inner[0].template().raw(), "vim {}");
+        assert_eq!(edit.mode, ExecutionMode::Fork);
+    }
+
+    #[test]
+    fn test_parse_prototype_extends_errors() {
+        let channels = channels(&[
+            "[metadata]\nname = \"a\"\nextends = \"b\"\n",
+            "[metadata]\nname = \"b\"\nextends = \"a\"\n",
+        ]);
+
+        let error = |content: &str| {
+            parse_prototype(content, &channels).unwrap_err().to_string()
+        };
+
+        assert_eq!(
+            error("[metadata]\nname = \"c\"\nextends = \"a\"\n"),
+            "inheritance cycle: c -> a -> b -> a"
+        );
+        assert_eq!(
+            error("[metadata]\nname = \"c\"\nextends = \"d\"\n"),
+            "channel `c` extends unknown channel `d`"
+        );
+    }
+}
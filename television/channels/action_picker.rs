@@ -1,6 +1,9 @@
 use crate::{
-    action::CUSTOM_ACTION_PREFIX, channels::entry::into_ranges,
-    channels::prototypes::ActionSpec, event::Key, matcher::Matcher,
+    action::CUSTOM_ACTION_PREFIX,
+    channels::entry::into_ranges,
+    channels::prototypes::ActionSpec,
+    event::Key,
+    matcher::{Matcher, config::Config},
     screen::result_item::ResultItem,
 };
 use anyhow::Result;
@@ -79,7 +82,8 @@ impl ActionPicker {
         channel_actions: &FxHashMap<String, ActionSpec>,
         action_keybindings: &FxHashMap<String, Key>,
     ) -> Self {
-        let matcher = Matcher::new(SortStrategy::Score, NUM_THREADS);
+        let matcher =
+            Matcher::new(&Config::default(), SortStrategy::Score, NUM_THREADS);
         let injector = matcher.injector();
 
         // Sort actions alphabetically for consistent display
@@ -2,21 +2,26 @@ use crate::{
     channels::{
         entry::Entry,
         entry_processor::{
-            AnsiProcessor, DisplayProcessor, EntryProcessor, PlainProcessor,
+            AnsiProcessor, ChannelItem, DisplayProcessor, EntryInjector,
+            EntryProcessor, FieldProcessor, PlainProcessor,
         },
+        fields::FieldOptions,
         prototypes::{CommandSpec, Template},
+        walker::{Walker, load_walker_candidates},
     },
-    frecency::FrecencyHandle,
-    matcher::{Matcher, injector::Injector, matcher_threads},
-    utils::command::shell_command,
+    frecency::{
+        ChannelFrecency, Frecency, FrecencyCacheHandle, FrecencyHandle,
+        FrecencyKey, FrecencyKeyer,
+    },
+    matcher::{Matcher, config::Config as MatcherConfig, matcher_threads},
+    utils::{command::shell_command, hashmaps::FxIndexSet},
 };
 use nucleo::SortStrategy;
-use rustc_hash::{FxBuildHasher, FxHashSet};
+use std::borrow::Cow;
 use std::cmp::Ordering;
-use std::collections::HashSet;
 use std::process::Stdio;
 use std::sync::Arc;
-use std::sync::atomic::AtomicBool;
+use std::sync::atomic::{AtomicBool, AtomicU64};
 use std::time::Duration;
 use tokio::process::Command as TokioCommand;
 use tokio::{
@@ -29,78 +34,140 @@ const RELOAD_RENDERING_DELAY: Duration = Duration::from_millis(200);
 
 pub struct Channel<P: EntryProcessor> {
     pub source_command: CommandSpec,
+    /// Native walker used instead of `source_command` for builtin sources.
+    pub source_walker: Option<Walker>,
     pub source_entry_delimiter: Option<char>,
     pub source_output: Option<Template>,
     pub supports_preview: bool,
     processor: P,
-    matcher: Matcher<P::Data>,
-    selected_entries: FxHashSet<Entry>,
+    matcher: Matcher<ChannelItem<P::Data>>,
+    selected_entries: FxIndexSet<Entry>,
     crawl_handle: Option<tokio::task::JoinHandle<()>>,
     current_source_index: usize,
     /// Indicates if the channel is currently reloading to prevent UI flickering
     /// by delaying the rendering of a new frame.
     pub reloading: Arc<AtomicBool>,
+    /// Incremented on every reload so that the delayed reset of
+    /// `reloading` only applies to the reload that scheduled it.
+    reload_generation: Arc<AtomicU64>,
     /// Whether this channel reads from stdin directly instead of spawning a
     /// source command. When true, `load()` reads `tokio::io::stdin()` and
     /// `reload()` is a no-op (stdin can only be consumed once).
     is_stdin: bool,
+    /// When set, the source command receives the query and is run again
+    /// once the query has stopped changing for this long.
+    reload_on_query: Option<Duration>,
+    /// The query the source command was last run with.
+    source_query: String,
+    /// A query change waiting for the debounce delay to elapse.
+    pending_query: Option<(String, Instant)>,
+    /// The pattern last given to the matcher, restored after reloads.
+    pattern: String,
+    /// The frecency scores used to sort results, if sorted by frecency.
+    frecency_cache: Option<FrecencyCacheHandle>,
+    /// Computes the frecency keys of entries as they are loaded, when they
+    /// aren't keyed on the raw entry itself.
+    frecency_keyer: Option<Arc<FrecencyKeyer>>,
 }
 
 impl<P: EntryProcessor> Channel<P> {
     #[allow(clippy::too_many_arguments)]
     pub fn new(
         source_command: CommandSpec,
+        source_walker: Option<Walker>,
         source_entry_delimiter: Option<char>,
         source_output: Option<Template>,
         supports_preview: bool,
         no_sort: bool,
+        matcher_config: &MatcherConfig,
         processor: P,
-        frecency: Option<(FrecencyHandle, String)>,
+        frecency: Option<(FrecencyHandle, ChannelFrecency)>,
         is_stdin: bool,
+        reload_on_query: Option<Duration>,
     ) -> Self {
+        let frecency_cache =
+            frecency.filter(|_| !no_sort).map(|(handle, channel)| {
+                let keyer =
+                    FrecencyKeyer::new(channel.key, source_output.clone());
+                handle.create_cache(channel, keyer)
+            });
+        let frecency_keyer = frecency_cache
+            .as_ref()
+            .filter(|cache| cache.key() != FrecencyKey::Raw)
+            .map(|cache| cache.keyer().clone());
         let sort_strategy = if no_sort {
             SortStrategy::Index
-        } else if let Some((frecency_handle, channel_name)) = frecency {
-            let cache = frecency_handle.create_cache(channel_name);
-            SortStrategy::Custom(Box::new(move |m1, i1, m2, i2| {
+        } else if let Some(cache) = frecency_cache.clone() {
+            SortStrategy::Custom(Box::new(move |_, i1, _, i2| {
                 let scores = cache.snapshot();
-                let key1 = P::frecency_key(&i1);
-                let key2 = P::frecency_key(&i2);
-                let f1 = scores.get(&key1);
-                let f2 = scores.get(&key2);
+                let f1 = scores.get(&frecency_key::<P>(&i1));
+                let f2 = scores.get(&frecency_key::<P>(&i2));
 
+                // equal frecencies are ordered by the matcher's tiebreaks
                 match (f1, f2) {
-                    (Some(s1), Some(s2)) => {
-                        s2.cmp(&s1).then_with(|| m2.score.cmp(&m1.score))
-                    }
+                    (Some(s1), Some(s2)) => s2.cmp(&s1),
                     (Some(_), None) => Ordering::Less,
                     (None, Some(_)) => Ordering::Greater,
-                    (None, None) => m2.score.cmp(&m1.score),
+                    (None, None) => Ordering::Equal,
                 }
             }))
         } else {
             SortStrategy::Score
         };
 
-        let matcher = Matcher::new(sort_strategy, matcher_threads());
+        let matcher =
+            Matcher::new(matcher_config, sort_strategy, matcher_threads());
         let current_source_index = 0;
         Self {
             source_command,
+            source_walker,
             source_entry_delimiter,
             source_output,
             supports_preview,
             processor,
             matcher,
-            selected_entries: HashSet::with_hasher(FxBuildHasher),
+            selected_entries: FxIndexSet::default(),
             crawl_handle: None,
             current_source_index,
             reloading: Arc::new(AtomicBool::new(false)),
+            reload_generation: Arc::new(AtomicU64::new(0)),
             is_stdin,
+            reload_on_query,
+            source_query: String::new(),
+            pending_query: None,
+            pattern: String::new(),
+            frecency_cache,
+            frecency_keyer,
+        }
+    }
+
+    /// Refresh the frecency scores used to sort results, e.g. after an entry
+    /// was forgotten.
+    pub fn refresh_frecency(&mut self, frecency: &Frecency) {
+        if let Some(cache) = &self.frecency_cache {
+            cache.refresh(frecency);
+            self.matcher.rescore();
         }
     }
 
+    /// Get the key a raw entry is recorded under in the frecency records.
+    pub fn frecency_key(&self, raw: &str) -> String {
+        match &self.frecency_cache {
+            Some(cache) => cache.keyer().key(raw).into_owned(),
+            None => raw.to_string(),
+        }
+    }
+
+    /// The project directory entries are ranked in, if ranked per project.
+    pub fn frecency_scope(&self) -> Option<&str> {
+        self.frecency_cache.as_ref().and_then(|cache| cache.scope())
+    }
+
     pub fn load(&mut self) {
-        let injector = self.matcher.injector();
+        let injector = EntryInjector::new(
+            self.matcher.injector(),
+            self.frecency_keyer.clone(),
+        );
         let processor = self.processor.clone();
         let crawl_handle = if self.is_stdin {
             tokio::spawn(load_stdin_candidates(
@@ -108,9 +175,20 @@ impl<P: EntryProcessor> Channel<P> {
                 processor,
                 injector,
             ))
+        } else if let Some(walker) = &self.source_walker {
+            tokio::spawn(load_walker_candidates(
+                walker.clone(),
+                processor,
+                injector,
+            ))
         } else {
+            let command = if self.reload_on_query.is_some() {
+                self.source_command.with_query(&self.source_query)
+            } else {
+                self.source_command.clone()
+            };
             tokio::spawn(load_candidates(
-                self.source_command.clone(),
+                command,
                 self.source_entry_delimiter,
                 self.current_source_index,
                 processor,
@@ -139,16 +217,32 @@ impl<P: EntryProcessor> Channel<P> {
         }
         self.matcher.restart();
         self.load();
+        if !self.pattern.is_empty() {
+            self.matcher.find(&self.pattern);
+        }
         // Spawn a thread that turns off reloading after a short delay
         // to avoid UI flickering (this boolean is used by `Television::should_render`)
+        let generation = self
+            .reload_generation
+            .fetch_add(1, std::sync::atomic::Ordering::Relaxed)
+            + 1;
+        let reload_generation = self.reload_generation.clone();
         let reloading = self.reloading.clone();
         tokio::spawn(async move {
             tokio::time::sleep(RELOAD_RENDERING_DELAY).await;
-            reloading.store(false, std::sync::atomic::Ordering::Relaxed);
+            // a newer reload turns it off itself
+            if reload_generation.load(std::sync::atomic::Ordering::Relaxed)
+                == generation
+            {
+                reloading.store(false, std::sync::atomic::Ordering::Relaxed);
+            }
         });
     }
 
     pub fn current_command(&self) -> &str {
+        if let Some(walker) = &self.source_walker {
+            return walker.kind.as_str();
+        }
         self.source_command
             .get_nth(self.current_source_index)
             .template()
@@ -156,20 +250,67 @@ impl<P: EntryProcessor> Channel<P> {
     }
 
     pub fn current_source_name(&self) -> Option<&str> {
+        if self.source_walker.is_some() {
+            return None;
+        }
         self.source_command
             .get_nth(self.current_source_index)
             .name()
     }
 
     pub fn find(&mut self, pattern: &str) {
+        pattern.clone_into(&mut self.pattern);
         self.matcher.find(pattern);
     }
 
+    /// Match entries against regular expressions instead of the extended
+    /// query syntax.
+    pub fn set_regex(&mut self, regex: bool) {
+        self.matcher.set_regex(regex);
+    }
+
+    pub fn query_error(&self) -> Option<&str> {
+        self.matcher.query_error()
+    }
+
+    /// Updates the query the source command is run with, for sources that
+    /// reload on query changes.
+    ///
+    /// The source is only run again once the query has stopped changing for
+    /// the debounce delay, see `tick`.
+    pub fn set_source_query(&mut self, query: &str) {
+        if self.reload_on_query.is_none() {
+            return;
+        }
+        if self.crawl_handle.is_none() {
+            // not loaded yet, the initial load will pick the query up
+            query.clone_into(&mut self.source_query);
+        } else if query == self.source_query {
+            self.pending_query = None;
+        } else {
+            self.pending_query = Some((query.to_string(), Instant::now()));
+        }
+    }
+
     /// Let the background matcher thread make progress.
     ///
     /// This is cheap and should be called frequently (e.g. every update cycle)
     /// to keep the matcher responsive, even when results aren't being fetched.
     pub fn tick(&mut self) {
+        if let Some(debounce) = self.reload_on_query
+            && self
+                .pending_query
+                .as_ref()
+                .is_some_and(|(_, changed)| changed.elapsed() >= debounce)
+            && let Some((query, _)) = self.pending_query.take()
+        {
+            debug!("Reloading source for query: {:?}", query);
+            self.source_query = query;
+            // a newer query always supersedes a reload in progress
+            self.reloading
+                .store(false, std::sync::atomic::Ordering::Relaxed);
+            self.reload();
+        }
         self.matcher.tick();
     }
 
@@ -183,7 +324,8 @@ impl<P: EntryProcessor> Channel<P> {
 
         for item in results {
             entries.push(
-                self.processor.make_entry(item, self.source_output.as_ref()),
+                self.processor
+                    .make_entry(item.into(), self.source_output.as_ref()),
             );
         }
 
@@ -192,17 +334,18 @@ impl<P: EntryProcessor> Channel<P> {
 
     pub fn get_result(&mut self, index: u32) -> Option<Entry> {
         self.matcher.get_result(index).map(|item| {
-            self.processor.make_entry(item, self.source_output.as_ref())
+            self.processor
+                .make_entry(item.into(), self.source_output.as_ref())
         })
     }
 
-    pub fn selected_entries(&self) -> &FxHashSet<Entry> {
+    pub fn selected_entries(&self) -> &FxIndexSet<Entry> {
         &self.selected_entries
     }
 
     pub fn toggle_selection(&mut self, entry: &Entry) {
         if self.selected_entries.contains(entry) {
-            self.selected_entries.remove(entry);
+            self.selected_entries.shift_remove(entry);
         } else {
             self.selected_entries.insert(entry.clone());
         }
@@ -225,7 +368,8 @@ impl<P: EntryProcessor> Channel<P> {
     pub fn shutdown(&self) {}
 
     pub fn cycle_sources(&mut self) {
-        if self.source_command.inner.len() > 1 {
+        if self.source_walker.is_none() && self.source_command.inner.len() > 1
+        {
             self.current_source_index = (self.current_source_index + 1)
                 % self.source_command.inner.len();
             debug!(
@@ -238,6 +382,21 @@ impl<P: EntryProcessor> Channel<P> {
         }
     }
 
+    /// Switches to the source command at `index`, reloading the channel if
+    /// it was already loaded.
+    pub fn set_source_index(&mut self, index: usize) {
+        if index == self.current_source_index
+            || index >= self.source_command.inner.len()
+            || self.source_walker.is_some()
+        {
+            return;
+        }
+        self.current_source_index = index;
+        if self.crawl_handle.is_some() {
+            self.reload();
+        }
+    }
+
     pub fn supports_preview(&self) -> bool {
         self.supports_preview
     }
@@ -251,6 +410,9 @@ impl<P: EntryProcessor> Channel<P> {
     }
 
     pub fn source_count(&self) -> usize {
+        if self.source_walker.is_some() {
+            return 1;
+        }
         self.source_command.inner.len()
     }
 
@@ -259,6 +421,19 @@ impl<P: EntryProcessor> Channel<P> {
     }
 }
 
+/// Get the key a matched item is recorded under in the frecency records.
+fn frecency_key<'a, P: EntryProcessor>(
+    item: &nucleo::Item<'a, ChannelItem<P::Data>>,
+) -> Cow<'a, str> {
+    match &item.data.frecency_key {
+        Some(key) => Cow::Borrowed(key),
+        None => P::frecency_key(&nucleo::Item {
+            data: &item.data.data,
+            matcher_columns: item.matcher_columns,
+        }),
+    }
+}
+
 const DEFAULT_LINE_BUFFER_SIZE: usize = 256;
 // Batch size for pushing candidates to the injector
 // 10k * 500 bytes (pessimistic avg line size) = ~5 MB
@@ -277,7 +452,7 @@ pub async fn load_candidates<P: EntryProcessor>(
     entry_delimiter: Option<char>,
     command_index: usize,
     processor: P,
-    injector: Injector<P::Data>,
+    injector: EntryInjector<P::Data>,
 ) {
     debug!("Loading candidates from command: {:?}", command);
     let mut std_command = shell_command(
@@ -288,6 +463,8 @@ pub async fn load_candidates<P: EntryProcessor>(
     );
     std_command.stdout(Stdio::piped()).stderr(Stdio::piped());
     let mut child = TokioCommand::from(std_command)
+        // make sure aborting a reload doesn't leave the command running
+        .kill_on_drop(true)
         .spawn()
         .expect("failed to execute process"); // FIXME: handle error
 
@@ -375,7 +552,7 @@ pub async fn load_candidates<P: EntryProcessor>(
 pub async fn load_stdin_candidates<P: EntryProcessor>(
     entry_delimiter: Option<char>,
     processor: P,
-    injector: Injector<P::Data>,
+    injector: EntryInjector<P::Data>,
 ) {
     debug!("Loading candidates from stdin");
     let stdin = tokio::io::stdin();
@@ -431,7 +608,7 @@ pub async fn load_stdin_candidates<P: EntryProcessor>(
 /// This is called from a blocking task spawned in the threadpool.
 fn flush_batch<P: EntryProcessor>(
     batch: Vec<Vec<u8>>,
-    injector: &Injector<P::Data>,
+    injector: &EntryInjector<P::Data>,
     processor: &P,
     delimiter: u8,
 ) {
@@ -454,10 +631,12 @@ fn flush_batch<P: EntryProcessor>(
 /// - Plain: no ANSI processing, no display template (uses Matcher<()> for memory efficiency)
 /// - Ansi: strips ANSI codes for matching (uses Matcher<String>)
 /// - Display: applies custom display template for matching (uses Matcher<String>)
+/// - Fields: matches and displays selected fields of each line (uses Matcher<String>)
 pub enum ChannelKind {
     Plain(Channel<PlainProcessor>),
     Ansi(Channel<AnsiProcessor>),
     Display(Channel<DisplayProcessor>),
+    Fields(Channel<FieldProcessor>),
 }
 
 /// This reduces the boilerplate you'd have to write to have the wrapping enum delegate same
@@ -501,6 +680,7 @@ macro_rules! delegate_to_channel {
                     ChannelKind::Plain(ch) => ch.$method($($arg),*),
                     ChannelKind::Ansi(ch) => ch.$method($($arg),*),
                     ChannelKind::Display(ch) => ch.$method($($arg),*),
+                    ChannelKind::Fields(ch) => ch.$method($($arg),*),
                 }
             }
         )*
@@ -514,6 +694,7 @@ macro_rules! delegate_to_channel {
                     ChannelKind::Plain(ch) => ch.$method($($arg),*),
                     ChannelKind::Ansi(ch) => ch.$method($($arg),*),
                     ChannelKind::Display(ch) => ch.$method($($arg),*),
+                    ChannelKind::Fields(ch) => ch.$method($($arg),*),
                 }
             }
         )*
@@ -529,45 +710,76 @@ impl ChannelKind {
     #[allow(clippy::fn_params_excessive_bools)]
     pub fn new(
         source_command: CommandSpec,
+        source_walker: Option<Walker>,
         source_entry_delimiter: Option<char>,
         source_ansi: bool,
         source_display: Option<Template>,
         source_output: Option<Template>,
+        source_fields: FieldOptions,
         supports_preview: bool,
         no_sort: bool,
-        frecency: Option<(FrecencyHandle, String)>,
+        matcher_config: &MatcherConfig,
+        frecency: Option<(FrecencyHandle, ChannelFrecency)>,
         is_stdin: bool,
+        reload_on_query: Option<Duration>,
     ) -> Self {
         match (source_ansi, source_display) {
+            (ansi, None) if !source_fields.is_empty() => {
+                ChannelKind::Fields(Channel::new(
+                    source_command,
+                    source_walker,
+                    source_entry_delimiter,
+                    source_output,
+                    supports_preview,
+                    no_sort,
+                    matcher_config,
+                    FieldProcessor {
+                        fields: Arc::new(source_fields),
+                        ansi,
+                    },
+                    frecency,
+                    is_stdin,
+                    reload_on_query,
+                ))
+            }
             (false, None) => ChannelKind::Plain(Channel::new(
                 source_command,
+                source_walker,
                 source_entry_delimiter,
                 source_output,
                 supports_preview,
                 no_sort,
+                matcher_config,
                 PlainProcessor,
                 frecency,
                 is_stdin,
+                reload_on_query,
             )),
             (true, None) => ChannelKind::Ansi(Channel::new(
                 source_command,
+                source_walker,
                 source_entry_delimiter,
                 source_output,
                 supports_preview,
                 no_sort,
+                matcher_config,
                 AnsiProcessor,
                 frecency,
                 is_stdin,
+                reload_on_query,
             )),
             (_, Some(template)) => ChannelKind::Display(Channel::new(
                 source_command,
+                source_walker,
                 source_entry_delimiter,
                 source_output,
                 supports_preview,
                 no_sort,
+                matcher_config,
                 DisplayProcessor { template },
                 frecency,
                 is_stdin,
+                reload_on_query,
             )),
         }
     }
@@ -577,27 +789,34 @@ impl ChannelKind {
         load() -> (),
         reload() -> (),
         find(pattern: &str) -> (),
+        set_regex(regex: bool) -> (),
+        set_source_query(query: &str) -> (),
         tick() -> (),
         results(num_entries: u32, offset: u32) -> Vec<Entry>,
         get_result(index: u32) -> Option<Entry>,
         toggle_selection(entry: &Entry) -> (),
         cycle_sources() -> (),
+        set_source_index(index: usize) -> (),
+        refresh_frecency(frecency: &Frecency) -> (),
     );
 
     // Generate all immutable delegation methods
     delegate_to_channel!(ref
         current_command() -> &str,
         current_source_name() -> Option<&str>,
-        selected_entries() -> &FxHashSet<Entry>,
+        selected_entries() -> &FxIndexSet<Entry>,
         result_count() -> u32,
         total_count() -> u32,
         running() -> bool,
+        query_error() -> Option<&str>,
         shutdown() -> (),
         supports_preview() -> bool,
         reloading() -> bool,
         source_index() -> usize,
         source_count() -> usize,
         is_stdin() -> bool,
+        frecency_key(raw: &str) -> String,
+        frecency_scope() -> Option<&str>,
     );
 }
 
@@ -619,9 +838,12 @@ mod tests {
         .unwrap();
 
         // Use PlainProcessor for no ansi, no display
-        let mut matcher =
-            Matcher::<()>::new(SortStrategy::Score, MATCHER_TEST_THREADS);
-        let injector = matcher.injector();
+        let mut matcher = Matcher::<ChannelItem<()>>::new(
+            &MatcherConfig::default(),
+            SortStrategy::Score,
+            MATCHER_TEST_THREADS,
+        );
+        let injector = EntryInjector::new(matcher.injector(), None);
 
         load_candidates(
             source_spec.command,
@@ -650,9 +872,12 @@ mod tests {
         )
         .unwrap();
 
-        let mut matcher =
-            Matcher::<()>::new(SortStrategy::Score, MATCHER_TEST_THREADS);
-        let injector = matcher.injector();
+        let mut matcher = Matcher::<ChannelItem<()>>::new(
+            &MatcherConfig::default(),
+            SortStrategy::Score,
+            MATCHER_TEST_THREADS,
+        );
+        let injector = EntryInjector::new(matcher.injector(), None);
 
         load_candidates(
             source_spec.command,
@@ -681,9 +906,12 @@ mod tests {
         )
         .unwrap();
 
-        let mut matcher =
-            Matcher::<()>::new(SortStrategy::Score, MATCHER_TEST_THREADS);
-        let injector = matcher.injector();
+        let mut matcher = Matcher::<ChannelItem<()>>::new(
+            &MatcherConfig::default(),
+            SortStrategy::Score,
+            MATCHER_TEST_THREADS,
+        );
+        let injector = EntryInjector::new(matcher.injector(), None);
 
         load_candidates(
             source_spec.command,
@@ -713,9 +941,12 @@ mod tests {
         )
         .unwrap();
 
-        let mut matcher =
-            Matcher::<()>::new(SortStrategy::Score, MATCHER_TEST_THREADS);
-        let injector = matcher.injector();
+        let mut matcher = Matcher::<ChannelItem<()>>::new(
+            &MatcherConfig::default(),
+            SortStrategy::Score,
+            MATCHER_TEST_THREADS,
+        );
+        let injector = EntryInjector::new(matcher.injector(), None);
 
         load_candidates(
             source_spec.command,
@@ -745,9 +976,12 @@ mod tests {
         )
         .unwrap();
 
-        let mut matcher =
-            Matcher::<String>::new(SortStrategy::Score, MATCHER_TEST_THREADS);
-        let injector = matcher.injector();
+        let mut matcher = Matcher::<ChannelItem<String>>::new(
+            &MatcherConfig::default(),
+            SortStrategy::Score,
+            MATCHER_TEST_THREADS,
+        );
+        let injector = EntryInjector::new(matcher.injector(), None);
 
         load_candidates(
             source_spec.command,
@@ -767,4 +1001,103 @@ mod tests {
         assert_eq!(results[1].matched_string, "test2");
         assert_eq!(results[2].matched_string, "test3");
     }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 3)]
+    async fn test_load_candidates_extended_query() {
+        let source_spec: SourceSpec = toml::from_str(
+            r#"
+            command = "printf 'foo.rs\nbar.rs\nbaz.toml\nfoo bar.md\n'"
+            "#,
+        )
+        .unwrap();
+
+        let mut matcher = Matcher::<ChannelItem<()>>::new(
+            &MatcherConfig::default(),
+            SortStrategy::Score,
+            MATCHER_TEST_THREADS,
+        );
+        let injector = EntryInjector::new(matcher.injector(), None);
+
+        load_candidates(
+            source_spec.command,
+            source_spec.entry_delimiter,
+            0,
+            PlainProcessor,
+            injector,
+        )
+        .await;
+
+        let matching = |matcher: &mut Matcher<ChannelItem<()>>,
+                        pattern: &str| {
+            matcher.find(pattern);
+            matcher.tick();
+            while matcher.status.running {
+                matcher.tick();
+            }
+            let mut results: Vec<String> = matcher
+                .results(10, 0)
+                .into_iter()
+                .map(|item| item.matched_string)
+                .collect();
+            results.sort();
+            results
+        };
+
+        assert_eq!(
+            matching(&mut matcher, "'.rs$ | toml$ !^foo"),
+            vec!["bar.rs", "baz.toml"]
+        );
+        assert_eq!(matcher.matched_item_count, 2);
+        assert_eq!(matching(&mut matcher, r#""foo b""#), vec!["foo bar.md"]);
+        assert!(matcher.query_error().is_none());
+        assert_eq!(matching(&mut matcher, r#""foo b"#), vec!["foo bar.md"]);
+        assert_eq!(matcher.query_error(), Some("unterminated quote"));
+
+        matcher.set_regex(true);
+        assert_eq!(
+            matching(&mut matcher, r"^ba.\.(rs|toml)$"),
+            vec!["bar.rs", "baz.toml"]
+        );
+        let item = matcher.get_result(0).unwrap();
+        assert_eq!(item.match_indices.len(), item.matched_string.len());
+        matching(&mut matcher, "(");
+        assert_eq!(matcher.query_error(), Some("invalid regex"));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 3)]
+    async fn test_reload_on_query() {
+        let mut channel = Channel::new(
+            CommandSpec::from(Template::parse("echo {query}").unwrap()),
+            None,
+            None,
+            None,
+            false,
+            true,
+            &MatcherConfig::default(),
+            PlainProcessor,
+            None,
+            false,
+            Some(Duration::ZERO),
+        );
+        channel.set_source_query("foo");
+        channel.load();
+
+        let wait_for = async |channel: &mut Channel<PlainProcessor>, raw| {
+            for _ in 0..100 {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                channel.tick();
+                if let Some(entry) = channel.get_result(0)
+                    && entry.raw == raw
+                {
+                    return;
+                }
+            }
+            panic!("source was not reloaded with {raw}");
+        };
+        wait_for(&mut channel, "foo").await;
+
+        channel.set_source_query("bar baz");
+        wait_for(&mut channel, "bar baz").await;
+        assert_eq!(channel.results(10, 0).len(), 1);
+    }
 }
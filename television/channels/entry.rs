@@ -1,10 +1,15 @@
 use crate::{
-    channels::prototypes::Template, event::Key,
+    channels::{fields::FieldOptions, prototypes::Template},
+    event::Key,
     screen::result_item::ResultItem,
 };
 use anyhow::Result;
+use fast_strip_ansi::strip_ansi_string;
 use smallvec::SmallVec;
-use std::hash::{Hash, Hasher};
+use std::{
+    hash::{Hash, Hasher},
+    sync::Arc,
+};
 
 #[derive(Clone, Debug, Eq)]
 pub struct Entry {
@@ -14,6 +19,9 @@ pub struct Entry {
     pub display: Option<String>,
     /// The output string that will be used when the entry is selected.
     pub output: Option<Template>,
+    /// The fields printed when the entry is selected, used when no output
+    /// template is set.
+    pub output_fields: Option<Arc<FieldOptions>>,
     /// The optional ranges for matching characters (based on `self.display`).
     pub match_ranges: Option<SmallVec<[(u32, u32); 8]>>,
     /// Whether the entry contains ANSI escape sequences.
@@ -90,6 +98,7 @@ impl Entry {
             raw,
             display: None,
             output: None,
+            output_fields: None,
             match_ranges: None,
             ansi: false,
         }
@@ -105,6 +114,11 @@ impl Entry {
         self
     }
 
+    pub fn with_output_fields(mut self, fields: Arc<FieldOptions>) -> Self {
+        self.output_fields = Some(fields);
+        self
+    }
+
     pub fn with_match_indices(mut self, indices: &[u32]) -> Self {
         self.match_ranges = Some(into_ranges(indices));
         self
@@ -117,6 +131,8 @@ impl Entry {
     pub fn output(&self) -> Result<String> {
         if let Some(output) = &self.output {
             output.format(&self.raw)
+        } else if let Some(fields) = &self.output_fields {
+            Ok(fields.output(&strip_ansi_string(&self.raw)))
         } else {
             Ok(self.raw.clone())
         }
@@ -189,6 +205,7 @@ mod tests {
             raw: "test name with spaces".to_string(),
             display: None,
             output: None,
+            output_fields: None,
             match_ranges: None,
             ansi: false,
         };
@@ -1,10 +1,82 @@
 use crate::{
-    channels::{entry::Entry, prototypes::Template},
+    channels::{entry::Entry, fields::FieldOptions, prototypes::Template},
+    frecency::FrecencyKeyer,
     matcher::{injector::Injector, matched_item::MatchedItem},
 };
 use fast_strip_ansi::strip_ansi_string;
-use nucleo::Utf32Str;
-use std::borrow::Cow;
+use nucleo::{Utf32Str, Utf32String};
+use std::{borrow::Cow, sync::Arc};
+
+/// The data stored in a channel's matcher for each entry.
+#[derive(Clone, Debug)]
+pub struct ChannelItem<D> {
+    /// The data of the entry processor.
+    pub data: D,
+    /// The key the entry is recorded under in the frecency records, when
+    /// that isn't the raw entry itself.
+    pub frecency_key: Option<Box<str>>,
+}
+
+impl<D> From<MatchedItem<ChannelItem<D>>> for MatchedItem<D>
+where
+    D: Sync + Send + Clone + 'static,
+{
+    fn from(item: MatchedItem<ChannelItem<D>>) -> Self {
+        Self {
+            inner: item.inner.data,
+            matched_string: item.matched_string,
+            match_indices: item.match_indices,
+        }
+    }
+}
+
+/// Pushes the entries of a channel into its matcher.
+///
+/// When entries are recorded in the frecency records under another key than
+/// the raw entry, the key is computed once here rather than each time
+/// entries are sorted.
+#[derive(Clone)]
+pub struct EntryInjector<D>
+where
+    D: Sync + Send + Clone + 'static,
+{
+    inner: Injector<ChannelItem<D>>,
+    frecency_keyer: Option<Arc<FrecencyKeyer>>,
+}
+
+impl<D> EntryInjector<D>
+where
+    D: Sync + Send + Clone + 'static,
+{
+    pub fn new(
+        inner: Injector<ChannelItem<D>>,
+        frecency_keyer: Option<Arc<FrecencyKeyer>>,
+    ) -> Self {
+        Self {
+            inner,
+            frecency_keyer,
+        }
+    }
+
+    /// Get the key `raw` is recorded under in the frecency records, when
+    /// that isn't the raw entry itself.
+    pub fn frecency_key(&self, raw: &str) -> Option<Box<str>> {
+        self.frecency_keyer
+            .as_ref()
+            .map(|keyer| keyer.key(raw).into())
+    }
+
+    /// Push an item into the matcher.
+    ///
+    /// The closure `f` should produce the string to match against based on
+    /// the data of the item.
+    pub fn push<F>(&self, item: ChannelItem<D>, f: F)
+    where
+        F: FnOnce(&D, &mut [Utf32String]),
+    {
+        self.inner.push(item, |item, cols| f(&item.data, cols));
+    }
+}
 
 /// Implementors of this trait define two things:
 /// - how to push lines into the matcher, including any preprocessing steps (e.g. stripping ANSI
@@ -16,7 +88,11 @@ use std::borrow::Cow;
 pub trait EntryProcessor: Send + Sync + Clone + 'static {
     type Data: Send + Sync + Clone + 'static;
 
-    fn push_to_injector(&self, line: String, injector: &Injector<Self::Data>);
+    fn push_to_injector(
+        &self,
+        line: String,
+        injector: &EntryInjector<Self::Data>,
+    );
 
     fn make_entry(
         &self,
@@ -26,7 +102,7 @@ pub trait EntryProcessor: Send + Sync + Clone + 'static {
 
     fn has_ansi(&self) -> bool;
 
-    /// Extract the frecency key from a matched item for lookup.
+    /// Extract the raw entry from a matched item for frecency lookups.
     ///
     /// This should return the same value that becomes `Entry.raw` so that
     /// frecency lookups match correctly.
@@ -46,8 +122,13 @@ pub struct PlainProcessor;
 impl EntryProcessor for PlainProcessor {
     type Data = ();
 
-    fn push_to_injector(&self, line: String, injector: &Injector<()>) {
-        injector.push((), |(), cols| {
+    fn push_to_injector(&self, line: String, injector: &EntryInjector<()>) {
+        let frecency_key = injector.frecency_key(&line);
+        let item = ChannelItem {
+            data: (),
+            frecency_key,
+        };
+        injector.push(item, |(), cols| {
             cols[0] = line.into();
         });
     }
@@ -98,8 +179,16 @@ pub struct AnsiProcessor;
 impl EntryProcessor for AnsiProcessor {
     type Data = String;
 
-    fn push_to_injector(&self, line: String, injector: &Injector<String>) {
-        injector.push(line, |original, cols| {
+    fn push_to_injector(
+        &self,
+        line: String,
+        injector: &EntryInjector<String>,
+    ) {
+        let item = ChannelItem {
+            frecency_key: injector.frecency_key(&line),
+            data: line,
+        };
+        injector.push(item, |original, cols| {
             cols[0] = strip_ansi_string(original).into();
         });
     }
@@ -141,9 +230,17 @@ pub struct DisplayProcessor {
 impl EntryProcessor for DisplayProcessor {
     type Data = String;
 
-    fn push_to_injector(&self, line: String, injector: &Injector<String>) {
+    fn push_to_injector(
+        &self,
+        line: String,
+        injector: &EntryInjector<String>,
+    ) {
         let template = self.template.clone();
-        injector.push(line, move |original, cols| {
+        let item = ChannelItem {
+            frecency_key: injector.frecency_key(&line),
+            data: line,
+        };
+        injector.push(item, move |original, cols| {
             cols[0] = template.format(original)
                 .unwrap_or_else(|_| {
                     panic!(
@@ -180,3 +277,200 @@ impl EntryProcessor for DisplayProcessor {
         Cow::Borrowed(item.data.as_str())
     }
 }
+
+/// A processor that splits each line into fields to choose which of them are matched against
+/// (`nth`), displayed (`with_nth`) and printed on exit (`accept_nth`).
+///
+/// Only the searchable fields are stored in the matcher column, the displayed text is computed
+/// from the original line when building entries. When `ansi` is set, fields are split and matched
+/// on the line stripped from its ANSI codes while the original line is kept for display, preview
+/// and output. Fields selected with `with_nth` are displayed without colors.
+///
+/// Uses `Matcher<String>` to store original lines.
+#[derive(Clone, Debug)]
+pub struct FieldProcessor {
+    pub fields: Arc<FieldOptions>,
+    pub ansi: bool,
+}
+
+impl EntryProcessor for FieldProcessor {
+    type Data = String;
+
+    fn push_to_injector(
+        &self,
+        line: String,
+        injector: &EntryInjector<String>,
+    ) {
+        let fields = self.fields.clone();
+        let ansi = self.ansi;
+        let item = ChannelItem {
+            frecency_key: injector.frecency_key(&line),
+            data: line,
+        };
+        injector.push(item, move |original, cols| {
+            cols[0] = if ansi {
+                fields.search_text(&strip_ansi_string(original))
+            } else {
+                fields.search_text(original)
+            }
+            .into();
+        });
+    }
+
+    fn make_entry(
+        &self,
+        item: MatchedItem<String>,
+        source_output: Option<&Template>,
+    ) -> Entry {
+        let stripped = if self.ansi {
+            strip_ansi_string(&item.inner)
+        } else {
+            Cow::Borrowed(item.inner.as_str())
+        };
+        let (display, match_indices) =
+            self.fields.display(&stripped, &item.match_indices);
+        // without `with_nth`, the original line is displayed with its colors
+        let ansi = self.ansi && display.is_none();
+        let display = display.or(match stripped {
+            Cow::Owned(stripped) => Some(stripped),
+            Cow::Borrowed(_) => None,
+        });
+        let mut entry = Entry::new(item.inner)
+            .with_match_indices(&match_indices)
+            .ansi(ansi);
+        if let Some(display) = display {
+            entry = entry.with_display(display);
+        }
+        if let Some(output) = source_output {
+            entry = entry.with_output(output.clone());
+        } else if self.fields.accept_nth.is_some() {
+            entry = entry.with_output_fields(self.fields.clone());
+        }
+        entry
+    }
+
+    fn has_ansi(&self) -> bool {
+        false
+    }
+
+    fn frecency_key<'a>(item: &nucleo::Item<'a, Self::Data>) -> Cow<'a, str> {
+        // item.data is &String, borrow it directly without allocation
+        Cow::Borrowed(item.data.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::channels::fields::FieldSelector;
+    use crate::frecency::FrecencyKey;
+    use crate::matcher::{Matcher, config::Config};
+
+    #[test]
+    fn test_entry_injector_computes_frecency_keys() {
+        let mut matcher = Matcher::<ChannelItem<String>>::new(
+            &Config::default(),
+            nucleo::SortStrategy::Score,
+            1,
+        );
+        let keyer = FrecencyKeyer::new(
+            FrecencyKey::Output,
+            Some(Template::parse("{split: :1}").unwrap()),
+        );
+        let injector =
+            EntryInjector::new(matcher.injector(), Some(Arc::new(keyer)));
+        AnsiProcessor.push_to_injector("M src/x.rs".to_string(), &injector);
+        let raw = EntryInjector::new(matcher.injector(), None);
+        AnsiProcessor.push_to_injector("M src/y.rs".to_string(), &raw);
+
+        matcher.find("");
+        matcher.tick();
+        while matcher.status.running {
+            matcher.tick();
+        }
+        let mut keys: Vec<_> = matcher
+            .results(10, 0)
+            .into_iter()
+            .map(|item| (item.inner.data, item.inner.frecency_key))
+            .collect();
+        keys.sort();
+        assert_eq!(
+            keys,
+            vec![
+                ("M src/x.rs".to_string(), Some("src/x.rs".into())),
+                ("M src/y.rs".to_string(), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_field_processor_make_entry() {
+        let processor = FieldProcessor {
+            fields: Arc::new(FieldOptions {
+                nth: Some(FieldSelector::parse("-1").unwrap()),
+                with_nth: Some(FieldSelector::parse("-1,1").unwrap()),
+                accept_nth: Some(FieldSelector::parse("1").unwrap()),
+                ..Default::default()
+            }),
+            ansi: false,
+        };
+        let item = MatchedItem {
+            inner: "3f2a1b nginx".to_string(),
+            matched_string: "nginx".to_string(),
+            match_indices: vec![0, 1],
+        };
+
+        let entry = processor.make_entry(item, None);
+
+        assert_eq!(entry.raw, "3f2a1b nginx");
+        assert_eq!(entry.display(), "nginx 3f2a1b");
+        assert_eq!(entry.match_ranges.as_deref(), Some(&[(0, 2)][..]));
+        assert_eq!(entry.output().unwrap(), "3f2a1b");
+    }
+
+    #[test]
+    fn test_field_processor_keeps_ansi() {
+        let line = "\x1b[31m3f2a1b\x1b[0m nginx".to_string();
+        let processor = FieldProcessor {
+            fields: Arc::new(FieldOptions {
+                nth: Some(FieldSelector::parse("1").unwrap()),
+                ..Default::default()
+            }),
+            ansi: true,
+        };
+        let item = MatchedItem {
+            inner: line.clone(),
+            matched_string: "3f2a1b".to_string(),
+            match_indices: vec![0, 1],
+        };
+
+        let entry = processor.make_entry(item, None);
+
+        assert_eq!(entry.raw, line);
+        assert!(entry.ansi);
+        assert_eq!(entry.display(), "3f2a1b nginx");
+        assert_eq!(entry.match_ranges.as_deref(), Some(&[(0, 2)][..]));
+        assert_eq!(entry.output().unwrap(), line);
+
+        let processor = FieldProcessor {
+            fields: Arc::new(FieldOptions {
+                with_nth: Some(FieldSelector::parse("2").unwrap()),
+                accept_nth: Some(FieldSelector::parse("1").unwrap()),
+                ..Default::default()
+            }),
+            ansi: true,
+        };
+        let item = MatchedItem {
+            inner: line.clone(),
+            matched_string: String::new(),
+            match_indices: vec![],
+        };
+
+        let entry = processor.make_entry(item, None);
+
+        assert_eq!(entry.raw, line);
+        assert!(!entry.ansi);
+        assert_eq!(entry.display(), "nginx");
+        assert_eq!(entry.output().unwrap(), "3f2a1b");
+    }
+}
@@ -0,0 +1,389 @@
+//! Splitting entries into fields to choose which parts of a line are
+//! searched (`nth`), displayed (`with_nth`) and printed on exit
+//! (`accept_nth`).
+//!
+//! Field selectors use the same syntax as fzf: a comma separated list of
+//! 1-based indices or ranges where negative indices count from the end, e.g.
+//! `1`, `-1`, `2..`, `..3`, `1..3` or `1,3..-2`.
+use anyhow::{Result, anyhow, bail};
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+
+/// The separator used to join fields that were split on whitespace.
+const WHITESPACE_SEPARATOR: &str = " ";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FieldRange {
+    start: Option<i64>,
+    end: Option<i64>,
+}
+
+impl FieldRange {
+    fn parse(range: &str) -> Result<Self> {
+        let index = |s: &str| -> Result<Option<i64>> {
+            if s.is_empty() {
+                return Ok(None);
+            }
+            match s.parse::<i64>() {
+                Ok(0) => bail!("Field indices start at 1, got '0'"),
+                Ok(i) => Ok(Some(i)),
+                Err(_) => Err(anyhow!("Invalid field index: '{}'", s)),
+            }
+        };
+        let range = range.trim();
+        if let Some((start, end)) = range.split_once("..") {
+            Ok(Self {
+                start: index(start)?,
+                end: index(end)?,
+            })
+        } else if range.is_empty() {
+            bail!("Empty field index")
+        } else {
+            let i = index(range)?;
+            Ok(Self { start: i, end: i })
+        }
+    }
+
+    /// Resolves the range to 0-based inclusive bounds for `count` fields.
+    #[allow(
+        clippy::cast_possible_wrap,
+        clippy::cast_sign_loss,
+        clippy::cast_possible_truncation
+    )]
+    fn resolve(self, count: usize) -> Option<(usize, usize)> {
+        let count = count as i64;
+        let resolve = |i: i64| if i > 0 { i - 1 } else { count + i };
+        let start = self.start.map_or(0, resolve).max(0);
+        let end = self.end.map_or(count - 1, resolve).min(count - 1);
+        (start <= end).then_some((start as usize, end as usize))
+    }
+}
+
+/// A list of fields or field ranges, e.g. `1,3..`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldSelector {
+    ranges: Vec<FieldRange>,
+    raw: String,
+}
+
+impl FieldSelector {
+    pub fn parse(selector: &str) -> Result<Self> {
+        let ranges = selector
+            .split(',')
+            .map(FieldRange::parse)
+            .collect::<Result<Vec<_>>>()
+            .map_err(|e| {
+                anyhow!("Invalid field selector '{}': {}", selector, e)
+            })?;
+        Ok(Self {
+            ranges,
+            raw: selector.to_string(),
+        })
+    }
+
+    pub fn raw(&self) -> &str {
+        &self.raw
+    }
+}
+
+impl Display for FieldSelector {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.raw)
+    }
+}
+
+impl Serialize for FieldSelector {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.raw)
+    }
+}
+
+impl<'de> Deserialize<'de> for FieldSelector {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let raw = String::deserialize(deserializer)?;
+        FieldSelector::parse(&raw).map_err(serde::de::Error::custom)
+    }
+}
+
+/// A field of a line, as byte offsets along with the char offset of its
+/// first character.
+#[derive(Debug, Clone, Copy)]
+struct Span {
+    start: usize,
+    end: usize,
+    char_start: u32,
+}
+
+/// Describes how entries are split into fields and which of them are used
+/// for matching, display and output.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct FieldOptions {
+    /// Splits entries into fields, defaults to runs of whitespace.
+    #[serde(default)]
+    pub field_delimiter: Option<String>,
+    /// The fields matched against the query.
+    #[serde(default)]
+    pub nth: Option<FieldSelector>,
+    /// The fields displayed in the results list.
+    #[serde(default)]
+    pub with_nth: Option<FieldSelector>,
+    /// The fields printed when the entry is selected.
+    #[serde(default)]
+    pub accept_nth: Option<FieldSelector>,
+}
+
+impl FieldOptions {
+    /// Whether any field selector is set.
+    pub fn is_empty(&self) -> bool {
+        self.nth.is_none()
+            && self.with_nth.is_none()
+            && self.accept_nth.is_none()
+    }
+
+    fn separator(&self) -> &str {
+        match self.field_delimiter.as_deref() {
+            Some(delimiter) if !delimiter.is_empty() => delimiter,
+            _ => WHITESPACE_SEPARATOR,
+        }
+    }
+
+    fn split(&self, line: &str) -> Vec<Span> {
+        let mut spans = Vec::new();
+        let mut char_start = 0;
+        match self.field_delimiter.as_deref() {
+            Some(delimiter) if !delimiter.is_empty() => {
+                let mut start = 0;
+                for (i, _) in line.match_indices(delimiter) {
+                    spans.push(Span {
+                        start,
+                        end: i,
+                        char_start,
+                    });
+                    char_start += to_u32(line[start..i].chars().count())
+                        + to_u32(delimiter.chars().count());
+                    start = i + delimiter.len();
+                }
+                spans.push(Span {
+                    start,
+                    end: line.len(),
+                    char_start,
+                });
+            }
+            _ => {
+                let mut current: Option<Span> = None;
+                for (i, c) in line.char_indices() {
+                    if c.is_whitespace() {
+                        spans.extend(
+                            current.take().map(|span| Span { end: i, ..span }),
+                        );
+                    } else if current.is_none() {
+                        current = Some(Span {
+                            start: i,
+                            end: i,
+                            char_start,
+                        });
+                    }
+                    char_start += 1;
+                }
+                spans.extend(current.map(|span| Span {
+                    end: line.len(),
+                    ..span
+                }));
+            }
+        }
+        spans
+    }
+
+    /// Joins the fields of `line` picked by `selector`.
+    ///
+    /// When `mapping` is given, it is filled with the char offset in `line`
+    /// of every char of the result (`None` for separators).
+    fn select_into(
+        &self,
+        line: &str,
+        selector: &FieldSelector,
+        mut mapping: Option<&mut Vec<Option<u32>>>,
+    ) -> String {
+        let spans = self.split(line);
+        let separator = self.separator();
+        let mut selected = String::with_capacity(line.len());
+        let mut first = true;
+        for range in &selector.ranges {
+            let Some((start, end)) = range.resolve(spans.len()) else {
+                continue;
+            };
+            for span in &spans[start..=end] {
+                if !first {
+                    selected.push_str(separator);
+                    if let Some(mapping) = mapping.as_deref_mut() {
+                        mapping.extend(std::iter::repeat_n(
+                            None,
+                            separator.chars().count(),
+                        ));
+                    }
+                }
+                first = false;
+                let field = &line[span.start..span.end];
+                selected.push_str(field);
+                if let Some(mapping) = mapping.as_deref_mut() {
+                    mapping.extend(
+                        (0..to_u32(field.chars().count()))
+                            .map(|i| Some(span.char_start + i)),
+                    );
+                }
+            }
+        }
+        selected
+    }
+
+    /// Joins the fields of `line` picked by `selector`.
+    pub fn select(&self, line: &str, selector: &FieldSelector) -> String {
+        self.select_into(line, selector, None)
+    }
+
+    /// The text matched against the query for `line`.
+    pub fn search_text(&self, line: &str) -> String {
+        match &self.nth {
+            Some(nth) => self.select(line, nth),
+            None => line.to_string(),
+        }
+    }
+
+    /// The output of `line` according to `accept_nth`.
+    pub fn output(&self, line: &str) -> String {
+        match &self.accept_nth {
+            Some(accept_nth) => self.select(line, accept_nth),
+            None => line.to_string(),
+        }
+    }
+
+    /// Computes the displayed text of `line` and translates the indices of
+    /// the chars matched in its search text into indices in that displayed
+    /// text.
+    pub fn display(
+        &self,
+        line: &str,
+        match_indices: &[u32],
+    ) -> (Option<String>, Vec<u32>) {
+        // indices relative to the whole line
+        let mut indices: Vec<u32> = match &self.nth {
+            Some(nth) => {
+                let mut mapping = Vec::new();
+                self.select_into(line, nth, Some(&mut mapping));
+                match_indices
+                    .iter()
+                    .filter_map(|i| {
+                        mapping.get(*i as usize).copied().flatten()
+                    })
+                    .collect()
+            }
+            None => match_indices.to_vec(),
+        };
+        let Some(with_nth) = &self.with_nth else {
+            indices.sort_unstable();
+            indices.dedup();
+            return (None, indices);
+        };
+        let mut mapping = Vec::new();
+        let display = self.select_into(line, with_nth, Some(&mut mapping));
+        let mut display_indices: Vec<u32> = mapping
+            .iter()
+            .enumerate()
+            .filter(|(_, i)| i.is_some_and(|i| indices.contains(&i)))
+            .map(|(d, _)| to_u32(d))
+            .collect();
+        display_indices.dedup();
+        (Some(display), display_indices)
+    }
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn to_u32(n: usize) -> u32 {
+    n as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields(delimiter: Option<&str>) -> FieldOptions {
+        FieldOptions {
+            field_delimiter: delimiter.map(ToString::to_string),
+            ..Default::default()
+        }
+    }
+
+    fn selector(s: &str) -> FieldSelector {
+        FieldSelector::parse(s).unwrap()
+    }
+
+    #[test]
+    fn test_parse_field_selector() {
+        assert!(FieldSelector::parse("1,-1,2..,..3,1..-2,..").is_ok());
+        assert!(FieldSelector::parse("0").is_err());
+        assert!(FieldSelector::parse("a").is_err());
+        assert!(FieldSelector::parse("1,").is_err());
+    }
+
+    #[test]
+    fn test_select_whitespace_fields() {
+        let fields = fields(None);
+        let line = "  root   1234  0.0 /usr/bin/foo --bar";
+
+        assert_eq!(fields.select(line, &selector("1")), "root");
+        assert_eq!(fields.select(line, &selector("-1")), "--bar");
+        assert_eq!(
+            fields.select(line, &selector("4..")),
+            "/usr/bin/foo --bar"
+        );
+        assert_eq!(fields.select(line, &selector("..2")), "root 1234");
+        assert_eq!(
+            fields.select(line, &selector("1,-2")),
+            "root /usr/bin/foo"
+        );
+        assert_eq!(fields.select(line, &selector("9")), "");
+    }
+
+    #[test]
+    fn test_select_delimited_fields() {
+        let fields = fields(Some(":"));
+        let line = "src/main.rs:12::fn main";
+
+        assert_eq!(fields.select(line, &selector("1,2")), "src/main.rs:12");
+        assert_eq!(fields.select(line, &selector("3")), "");
+        assert_eq!(fields.select(line, &selector("-1")), "fn main");
+    }
+
+    #[test]
+    fn test_display_maps_match_indices() {
+        let fields = FieldOptions {
+            nth: Some(selector("2")),
+            with_nth: Some(selector("2,1")),
+            ..Default::default()
+        };
+        let line = "abc def";
+        // "de" matched in the search text "def"
+        let (display, indices) = fields.display(line, &[0, 1]);
+
+        assert_eq!(display.as_deref(), Some("def abc"));
+        assert_eq!(indices, vec![0, 1]);
+    }
+
+    #[test]
+    fn test_display_without_with_nth() {
+        let fields = FieldOptions {
+            nth: Some(selector("-1")),
+            ..Default::default()
+        };
+        let (display, indices) = fields.display("föö bar", &[2]);
+
+        assert_eq!(display, None);
+        assert_eq!(indices, vec![6]);
+    }
+}
@@ -0,0 +1,178 @@
+use crate::{
+    channels::entry::into_ranges,
+    history::HistoryEntry,
+    matcher::{Matcher, config::Config, tiebreak::Tiebreak},
+    screen::result_item::ResultItem,
+};
+use anyhow::Result;
+use nucleo::SortStrategy;
+use smallvec::SmallVec;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, Clone)]
+pub struct HistoryPickerEntry {
+    pub query: String,
+    pub channel: String,
+    /// The channel of the entry and how long ago it was searched, e.g.
+    /// `files · 3m ago`.
+    pub details: String,
+    pub match_ranges: Option<SmallVec<[(u32, u32); 8]>>,
+}
+
+impl HistoryPickerEntry {
+    pub fn new(entry: &HistoryEntry, now: u64) -> Self {
+        HistoryPickerEntry {
+            query: entry.query.clone(),
+            channel: entry.channel.clone(),
+            details: format!(
+                "{} · {}",
+                entry.channel,
+                format_age(now.saturating_sub(entry.timestamp))
+            ),
+            match_ranges: None,
+        }
+    }
+
+    pub fn with_match_indices(mut self, indices: &[u32]) -> Self {
+        self.match_ranges = Some(into_ranges(indices));
+        self
+    }
+}
+
+impl ResultItem for HistoryPickerEntry {
+    fn raw(&self) -> &str {
+        &self.query
+    }
+
+    fn display(&self) -> &str {
+        &self.query
+    }
+
+    fn output(&self) -> Result<String> {
+        Ok(self.query.clone())
+    }
+
+    fn match_ranges(&self) -> Option<&[(u32, u32)]> {
+        self.match_ranges.as_deref()
+    }
+
+    fn details(&self) -> Option<&str> {
+        Some(&self.details)
+    }
+}
+
+/// Formats a duration in seconds the way it is shown next to history
+/// entries, e.g. `just now`, `5m ago` or `2d ago`.
+pub fn format_age(seconds: u64) -> String {
+    const MINUTE: u64 = 60;
+    const HOUR: u64 = 60 * MINUTE;
+    const DAY: u64 = 24 * HOUR;
+    const WEEK: u64 = 7 * DAY;
+    const MONTH: u64 = 30 * DAY;
+    const YEAR: u64 = 365 * DAY;
+
+    match seconds {
+        s if s < MINUTE => "just now".to_string(),
+        s if s < HOUR => format!("{}m ago", s / MINUTE),
+        s if s < DAY => format!("{}h ago", s / HOUR),
+        s if s < WEEK => format!("{}d ago", s / DAY),
+        s if s < MONTH => format!("{}w ago", s / WEEK),
+        s if s < YEAR => format!("{}mo ago", s / MONTH),
+        s => format!("{}y ago", s / YEAR),
+    }
+}
+
+pub struct HistoryPicker {
+    matcher: Matcher<HistoryPickerEntry>,
+}
+
+const NUM_THREADS: usize = 1;
+
+impl HistoryPicker {
+    /// Creates a picker over the given history entries, which are expected
+    /// from the most recent one.
+    pub fn new(entries: &[&HistoryEntry]) -> Self {
+        // matches scoring the same keep the most recent first
+        let config = Config {
+            tiebreak: vec![Tiebreak::Score, Tiebreak::Index],
+            ..Config::default()
+        };
+        let matcher = Matcher::new(&config, SortStrategy::Score, NUM_THREADS);
+        let injector = matcher.injector();
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        for entry in entries {
+            let () = injector.push(
+                HistoryPickerEntry::new(entry, now),
+                |e, cols| {
+                    cols[0] = e.query.clone().into();
+                },
+            );
+        }
+
+        HistoryPicker { matcher }
+    }
+
+    pub fn find(&mut self, pattern: &str) {
+        self.matcher.find(pattern);
+    }
+
+    pub fn results(
+        &mut self,
+        num_entries: u32,
+        offset: u32,
+    ) -> Vec<HistoryPickerEntry> {
+        self.matcher.tick();
+        self.matcher
+            .results(num_entries, offset)
+            .into_iter()
+            .map(|item| item.inner.with_match_indices(&item.match_indices))
+            .collect()
+    }
+
+    pub fn get_result(&mut self, index: u32) -> HistoryPickerEntry {
+        let item = self.matcher.get_result(index).expect("Invalid index");
+        item.inner.with_match_indices(&item.match_indices)
+    }
+
+    pub fn result_count(&self) -> u32 {
+        self.matcher.matched_item_count
+    }
+
+    pub fn total_count(&self) -> u32 {
+        self.matcher.total_item_count
+    }
+
+    pub fn running(&self) -> bool {
+        self.matcher.status.running
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_age() {
+        assert_eq!(format_age(0), "just now");
+        assert_eq!(format_age(59), "just now");
+        assert_eq!(format_age(5 * 60 + 30), "5m ago");
+        assert_eq!(format_age(3 * 3600), "3h ago");
+        assert_eq!(format_age(2 * 86400 + 10), "2d ago");
+        assert_eq!(format_age(15 * 86400), "2w ago");
+        assert_eq!(format_age(65 * 86400), "2mo ago");
+        assert_eq!(format_age(800 * 86400), "2y ago");
+    }
+
+    #[test]
+    fn test_history_picker_entry_details() {
+        let mut entry = HistoryEntry::new("foo".into(), "files".into());
+        entry.timestamp = 1_000;
+        let entry = HistoryPickerEntry::new(&entry, 1_000 + 7_200);
+        assert_eq!(entry.display(), "foo");
+        assert_eq!(entry.details(), Some("files · 2h ago"));
+    }
+}
@@ -2,5 +2,8 @@ pub mod action_picker;
 pub mod channel;
 pub mod entry;
 pub mod entry_processor;
+pub mod fields;
+pub mod history_picker;
 pub mod prototypes;
 pub mod remote_control;
+pub mod walker;
@@ -1,13 +1,20 @@
+use crate::channels::fields::FieldOptions;
 use crate::cli::parse_source_entry_delimiter;
 use crate::config::ui::{InputBarConfig, ThemeOverrides};
+use crate::frecency::{FrecencyKey, FrecencyScope};
+use crate::utils::hashmaps::FxIndexMap;
 use crate::utils::shell::Shell;
 use crate::utils::strings::SPACE;
 use crate::{
     config::{Keybindings, ui},
     event::Key,
+    matcher::{
+        config::{CaseMatching, Normalization},
+        tiebreak::Tiebreak,
+    },
     screen::layout::Orientation,
 };
-use anyhow::Result;
+use anyhow::{Result, bail};
 use rustc_hash::FxHashMap;
 use serde::{Deserialize, Serialize};
 use serde_with::{OneOrMany, serde_as};
@@ -20,13 +27,26 @@ use which::which;
 pub enum Template {
     StringPipeline(MultiTemplate),
     Raw(String),
+    /// Templates with values spliced in between them as plain text, which is
+    /// never interpreted as template expressions.
+    Spliced {
+        raw: String,
+        parts: Vec<TemplatePart>,
+    },
+}
+
+/// A part of a [`Template::Spliced`] template.
+#[derive(Debug, Clone)]
+pub enum TemplatePart {
+    Template(Template),
+    Text(String),
 }
 
 impl Template {
     pub fn raw(&self) -> &str {
         match self {
             Template::StringPipeline(template) => template.template_string(),
-            Template::Raw(raw) => raw,
+            Template::Raw(raw) | Template::Spliced { raw, .. } => raw,
         }
     }
 
@@ -50,10 +70,167 @@ impl Template {
                 })
             }
             Template::Raw(raw) => Ok(raw.replace("{}", input)),
+            Template::Spliced { parts, .. } => parts
+                .iter()
+                .map(|part| match part {
+                    TemplatePart::Template(template) => template.format(input),
+                    TemplatePart::Text(text) => Ok(text.clone()),
+                })
+                .collect(),
+        }
+    }
+
+    /// Whether the template refers to the selected entries with `{+}`.
+    pub fn uses_selection(&self) -> bool {
+        match self {
+            Template::Spliced { parts, .. } => parts.iter().any(|part| {
+                matches!(part, TemplatePart::Template(template)
+                    if template.uses_selection())
+            }),
+            _ => self.raw().contains(SELECTION_PLACEHOLDER),
+        }
+    }
+
+    /// Replaces the `(placeholder, value)` pairs of `values` by their value.
+    ///
+    /// Like `{+}`, the template is split around each placeholder and only
+    /// the parts in between are parsed, so that the values are kept as
+    /// plain text.
+    pub fn splice(&self, values: &[(String, String)]) -> Self {
+        let mut parts = Vec::new();
+        match self {
+            Template::Spliced { parts: current, .. } => {
+                for part in current {
+                    match part {
+                        TemplatePart::Template(template) => {
+                            splice_parts(template.raw(), values, &mut parts);
+                        }
+                        TemplatePart::Text(_) => parts.push(part.clone()),
+                    }
+                }
+            }
+            _ => splice_parts(self.raw(), values, &mut parts),
+        }
+        if !parts
+            .iter()
+            .any(|part| matches!(part, TemplatePart::Text(_)))
+        {
+            return self.clone();
+        }
+        let raw = parts
+            .iter()
+            .map(|part| match part {
+                TemplatePart::Template(template) => template.raw(),
+                TemplatePart::Text(text) => text,
+            })
+            .collect();
+        Template::Spliced { raw, parts }
+    }
+
+    /// Formats the template with `input`, replacing `{+}` by `selection`.
+    ///
+    /// The parts around each `{+}` are formatted separately so that the
+    /// selected entries are never interpreted as template expressions.
+    pub fn format_with_selection(
+        &self,
+        input: &str,
+        selection: &str,
+    ) -> Result<String> {
+        if !self.uses_selection() {
+            return self.format(input);
+        }
+        if let Template::Spliced { parts, .. } = self {
+            return parts
+                .iter()
+                .map(|part| match part {
+                    TemplatePart::Template(template) => {
+                        template.format_with_selection(input, selection)
+                    }
+                    TemplatePart::Text(text) => Ok(text.clone()),
+                })
+                .collect();
+        }
+        Ok(self
+            .raw()
+            .split(SELECTION_PLACEHOLDER)
+            .map(|part| {
+                Template::parse(part)
+                    .map_err(|e| anyhow::anyhow!(e))?
+                    .format(input)
+            })
+            .collect::<Result<Vec<_>>>()?
+            .join(selection))
+    }
+}
+
+/// Splits `raw` around the placeholders of `values`, pushing the parts in
+/// between as templates and the values as text.
+fn splice_parts(
+    raw: &str,
+    values: &[(String, String)],
+    parts: &mut Vec<TemplatePart>,
+) {
+    let mut rest = raw;
+    while let Some((index, placeholder, value)) = values
+        .iter()
+        .filter_map(|(placeholder, value)| {
+            rest.find(placeholder.as_str())
+                .map(|index| (index, placeholder, value))
+        })
+        .min_by_key(|(index, _, _)| *index)
+    {
+        if index > 0 {
+            parts.push(TemplatePart::Template(
+                Template::parse(&rest[..index]).unwrap(),
+            ));
         }
+        parts.push(TemplatePart::Text(value.clone()));
+        rest = &rest[index + placeholder.len()..];
+    }
+    if !rest.is_empty() {
+        parts.push(TemplatePart::Template(Template::parse(rest).unwrap()));
     }
 }
 
+/// The program a command runs, looking through `env` and `sh -c` style
+/// wrappers, e.g. `fd` for `env FOO=1 sh -c 'fd -t f'`.
+fn command_program(command: &str) -> Option<&str> {
+    const WRAPPERS: [&str; 4] = ["env", "exec", "command", "nohup"];
+    const SHELLS: [&str; 9] = [
+        "sh",
+        "bash",
+        "zsh",
+        "dash",
+        "fish",
+        "nu",
+        "pwsh",
+        "powershell",
+        "cmd",
+    ];
+    let mut tokens = command
+        .split_whitespace()
+        .map(|token| token.trim_start_matches(['\'', '"']))
+        .peekable();
+    while let Some(token) = tokens.next() {
+        let is_shell_script = SHELLS.contains(&token)
+            && tokens.peek().is_some_and(|flag| {
+                ["-c", "/c", "/C", "-Command"].contains(flag)
+            });
+        if is_shell_script {
+            tokens.next();
+        } else if !(WRAPPERS.contains(&token)
+            || token.contains('=')
+            || token.starts_with('-'))
+        {
+            return Some(token.trim_end_matches(['\'', '"', ';']));
+        }
+    }
+    None
+}
+
+/// Placeholder replaced by all the selected entries in preview commands.
+pub const SELECTION_PLACEHOLDER: &str = "{+}";
+
 impl Display for Template {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         write!(f, "{}", self.raw())
@@ -67,6 +244,7 @@ impl PartialEq for Template {
                 (self, other),
                 (Template::StringPipeline(_), Template::StringPipeline(_))
                     | (Template::Raw(_), Template::Raw(_))
+                    | (Template::Spliced { .. }, Template::Spliced { .. })
             )
     }
 }
@@ -138,7 +316,7 @@ impl From<Template> for SourceCommand {
     Debug, Clone, serde::Deserialize, serde::Serialize, PartialEq, Default,
 )]
 pub struct CommandSpec {
-    #[serde(rename = "command")]
+    #[serde(rename = "command", default)]
     #[serde_as(as = "OneOrMany<_>")]
     pub inner: Vec<SourceCommand>,
     #[serde(default)]
@@ -149,6 +327,10 @@ pub struct CommandSpec {
     /// If not specified, the shell is detected from the environment.
     #[serde(default)]
     pub shell: Option<Shell>,
+    /// Whether the program run by the commands was found to be missing when
+    /// the channel was loaded.
+    #[serde(skip)]
+    program_missing: bool,
 }
 
 impl Display for CommandSpec {
@@ -182,6 +364,118 @@ impl CommandSpec {
     pub fn get_nth(&self, index: usize) -> &SourceCommand {
         &self.inner[index % self.inner.len()]
     }
+
+    /// Looks up the program run by the first command in the `PATH`.
+    ///
+    /// This is done once when the channel is loaded, so that a builtin can
+    /// replace the commands when their program isn't installed.
+    pub fn resolve_program(&mut self) {
+        self.program_missing = self
+            .inner
+            .first()
+            .and_then(|c| command_program(c.template().raw()))
+            .is_none_or(|program| which(program).is_err());
+    }
+
+    /// Whether a builtin should run instead of these commands, either
+    /// because there are none or because their program is missing.
+    ///
+    /// This should be called after `resolve_program()`.
+    pub fn replaced_by_builtin(&self) -> bool {
+        self.inner.is_empty() || self.program_missing
+    }
+
+    /// Whether any of the commands uses the `{+}` selection placeholder.
+    pub fn uses_selection(&self) -> bool {
+        self.inner.iter().any(|c| c.template().uses_selection())
+    }
+
+    /// Returns a copy of this spec where every occurrence of `{query}` is
+    /// replaced by `query`, quoted for the shell running the commands.
+    pub fn with_query(&self, query: &str) -> Self {
+        let shell = self
+            .shell
+            .unwrap_or_else(|| Shell::from_env().unwrap_or_default());
+        let quoted = shell.quote(query);
+        let substitute = |template: &Template| {
+            Template::Raw(template.raw().replace(QUERY_PLACEHOLDER, &quoted))
+        };
+        Self {
+            inner: self
+                .inner
+                .iter()
+                .map(|command| match command {
+                    SourceCommand::Bare(run) => {
+                        SourceCommand::Bare(substitute(run))
+                    }
+                    SourceCommand::Named { name, run } => {
+                        SourceCommand::Named {
+                            name: name.clone(),
+                            run: substitute(run),
+                        }
+                    }
+                })
+                .collect(),
+            ..self.clone()
+        }
+    }
+
+    /// Returns a copy of this spec where the `{param:<name>}` placeholders
+    /// are replaced by the value of the matching parameter, quoted for the
+    /// shell running the commands.
+    ///
+    /// Parameters are also substituted, unquoted, in the values of `env`.
+    pub fn with_params(&self, params: &FxHashMap<String, String>) -> Self {
+        if params.is_empty() {
+            return self.clone();
+        }
+        let shell = self
+            .shell
+            .unwrap_or_else(|| Shell::from_env().unwrap_or_default());
+        let quoted = params
+            .iter()
+            .map(|(name, value)| (param_placeholder(name), shell.quote(value)))
+            .collect::<Vec<_>>();
+        let template = |template: &Template| template.splice(&quoted);
+        Self {
+            inner: self
+                .inner
+                .iter()
+                .map(|command| match command {
+                    SourceCommand::Bare(run) => {
+                        SourceCommand::Bare(template(run))
+                    }
+                    SourceCommand::Named { name, run } => {
+                        SourceCommand::Named {
+                            name: name.clone(),
+                            run: template(run),
+                        }
+                    }
+                })
+                .collect(),
+            env: self
+                .env
+                .iter()
+                .map(|(k, v)| {
+                    let v =
+                        params.iter().fold(v.clone(), |v, (name, value)| {
+                            v.replace(&param_placeholder(name), value)
+                        });
+                    (k.clone(), v)
+                })
+                .collect(),
+            ..self.clone()
+        }
+    }
+}
+
+/// Placeholder replaced by the current query in sources that reload on
+/// query changes.
+pub const QUERY_PLACEHOLDER: &str = "{query}";
+
+/// The placeholder replaced by the value of the channel parameter `name`.
+pub fn param_placeholder(name: &str) -> String {
+    format!("{{param:{name}}}")
 }
 
 /// Execution mode for external actions
@@ -197,7 +491,7 @@ pub enum ExecutionMode {
     Execute,
 }
 
-fn default_separator() -> String {
+pub(crate) fn default_separator() -> String {
     SPACE.to_string()
 }
 
@@ -263,6 +557,10 @@ pub struct ChannelPrototype {
     pub watch: f64,
     #[serde(default)]
     pub history: HistoryConfig,
+    /// Restore the query, source and preview commands, orientation and
+    /// preview visibility from the last time the channel was used.
+    #[serde(default)]
+    pub resume: bool,
     #[serde(default)]
     pub actions: FxHashMap<String, ActionSpec>,
 }
@@ -274,6 +572,8 @@ impl ChannelPrototype {
                 name: name.to_string(),
                 description: None,
                 requirements: vec![],
+                extends: None,
+                params: FxIndexMap::default(),
             },
             source: SourceSpec {
                 command: CommandSpec {
@@ -285,23 +585,51 @@ impl ChannelPrototype {
                     interactive: false,
                     env: FxHashMap::default(),
                     shell: None,
+                    program_missing: false,
                 },
                 entry_delimiter: None,
+                output_delimiter: None,
                 ansi: false,
                 display: None,
                 output: None,
                 no_sort: false,
                 frecency: true,
+                frecency_key: FrecencyKey::default(),
+                frecency_scope: FrecencyScope::default(),
+                frecency_global_weight: None,
+                case_matching: None,
+                normalization: None,
+                match_paths: false,
+                tiebreak: Vec::new(),
+                builtin: None,
+                walker: WalkerOptions::default(),
+                fields: FieldOptions::default(),
+                reload_on_query: false,
+                reload_debounce: default_reload_debounce(),
             },
             preview: None,
             ui: None,
             keybindings: None,
             watch: 0.0,
             history: HistoryConfig::default(),
+            resume: false,
             actions: FxHashMap::default(),
         }
     }
 
+    /// Resolves whether the commands backed by a builtin can run, see
+    /// [`CommandSpec::resolve_program`].
+    pub fn resolve_builtin_fallbacks(&mut self) {
+        if self.source.builtin.is_some() {
+            self.source.command.resolve_program();
+        }
+        if let Some(preview) = &mut self.preview
+            && preview.builtin.is_some()
+        {
+            preview.command.resolve_program();
+        }
+    }
+
     pub fn stdin() -> Self {
         Self {
             metadata: Metadata {
@@ -310,6 +638,8 @@ impl ChannelPrototype {
                     "A channel that reads from stdin".to_string(),
                 ),
                 requirements: vec![],
+                extends: None,
+                params: FxIndexMap::default(),
             },
             source: SourceSpec {
                 // The source command is unused for stdin channels — data is
@@ -326,6 +656,7 @@ impl ChannelPrototype {
             keybindings: None,
             watch: 0.0,
             history: HistoryConfig::default(),
+            resume: false,
             actions: FxHashMap::default(),
         }
     }
@@ -334,6 +665,86 @@ impl ChannelPrototype {
         self.preview = preview;
         self
     }
+
+    /// The parameters that have neither a value in `values` nor a
+    /// default, in declaration order.
+    pub fn missing_params(
+        &self,
+        values: &FxHashMap<String, String>,
+    ) -> Vec<(String, ChannelParam)> {
+        self.metadata
+            .params
+            .iter()
+            .filter(|(name, param)| {
+                param.default.is_none() && !values.contains_key(*name)
+            })
+            .map(|(name, param)| (name.clone(), param.clone()))
+            .collect()
+    }
+
+    /// Returns a copy of this prototype with its parameters substituted in
+    /// the source, preview and action commands.
+    ///
+    /// Parameters take their value from `values`, falling back to their
+    /// default, and are left as is if they have neither.
+    pub fn with_params(&self, values: &FxHashMap<String, String>) -> Self {
+        let params = self
+            .metadata
+            .params
+            .iter()
+            .filter_map(|(name, param)| {
+                let value = values.get(name).or(param.default.as_ref())?;
+                Some((name.clone(), value.clone()))
+            })
+            .collect::<FxHashMap<_, _>>();
+        if params.is_empty() {
+            return self.clone();
+        }
+        let mut prototype = self.clone();
+        prototype.source.command = self.source.command.with_params(&params);
+        if let Some(preview) = &mut prototype.preview {
+            preview.command = preview.command.with_params(&params);
+        }
+        for action in prototype.actions.values_mut() {
+            action.command = action.command.with_params(&params);
+        }
+        prototype
+    }
+
+    /// Checks the constraints that deserialization alone can't enforce.
+    ///
+    /// `command` may only be omitted from `[source]` and `[preview]` when
+    /// they use a builtin.
+    pub fn validate(&self) -> Result<()> {
+        if self.source.command.inner.is_empty()
+            && self.source.builtin.is_none()
+        {
+            bail!("missing field `command` in [source]");
+        }
+        if self.source.display.is_some()
+            && (self.source.fields.nth.is_some()
+                || self.source.fields.with_nth.is_some())
+        {
+            bail!(
+                "`display` can't be combined with `nth` or `with_nth` in [source]"
+            );
+        }
+        if self.source.reload_on_query && self.source.builtin.is_some() {
+            bail!("`reload_on_query` requires a `command` in [source]");
+        }
+        if let Some(preview) = &self.preview
+            && preview.command.inner.is_empty()
+            && preview.builtin.is_none()
+        {
+            bail!("missing field `command` in [preview]");
+        }
+        for (name, action) in &self.actions {
+            if action.command.inner.is_empty() {
+                bail!("missing field `command` in [actions.{}]", name);
+            }
+        }
+        Ok(())
+    }
 }
 
 impl Display for ChannelPrototype {
@@ -348,6 +759,29 @@ pub struct Metadata {
     pub description: Option<String>,
     #[serde(default)]
     pub requirements: Vec<BinaryRequirement>,
+    /// The name of a channel this channel inherits its settings from.
+    ///
+    /// This is resolved when loading the cable.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extends: Option<String>,
+    /// The parameters the channel takes, in the order they are prompted
+    /// for.
+    #[serde(default, skip_serializing_if = "FxIndexMap::is_empty")]
+    pub params: FxIndexMap<String, ChannelParam>,
+}
+
+/// A parameter substituted for `{param:<name>}` in the commands of a
+/// channel.
+#[derive(
+    Debug, Clone, Default, PartialEq, serde::Deserialize, serde::Serialize,
+)]
+pub struct ChannelParam {
+    /// The value used when none is given, the user is prompted for one if
+    /// this is missing.
+    #[serde(default)]
+    pub default: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
 }
 
 #[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
@@ -382,12 +816,17 @@ impl BinaryRequirement {
     }
 }
 
+#[allow(clippy::struct_excessive_bools)]
 #[derive(Debug, Clone, Default, serde::Deserialize, serde::Serialize)]
 pub struct SourceSpec {
     #[serde(flatten)]
     pub command: CommandSpec,
     #[serde(deserialize_with = "deserialize_entry_delimiter", default)]
     pub entry_delimiter: Option<char>,
+    /// The delimiter used to terminate selected entries when printing them
+    /// on exit, defaults to a newline.
+    #[serde(deserialize_with = "deserialize_entry_delimiter", default)]
+    pub output_delimiter: Option<char>,
     #[serde(default)]
     pub ansi: bool,
     #[serde(default)]
@@ -405,12 +844,149 @@ pub struct SourceSpec {
     /// (e.g., channels with dynamic/random results, or where order is meaningful).
     #[serde(default = "default_frecency")]
     pub frecency: bool,
+    /// What identifies entries in the frecency records, e.g. `path` so
+    /// that files keep their ranking whatever directory tv is launched from.
+    #[serde(default)]
+    pub frecency_key: FrecencyKey,
+    /// Rank entries per project (`cwd` or `git`) rather than the same way
+    /// wherever tv is launched.
+    #[serde(default)]
+    pub frecency_scope: FrecencyScope,
+    /// How much the entries selected in other projects count when ranking
+    /// per project, defaults to 0.5.
+    #[serde(default)]
+    pub frecency_global_weight: Option<f64>,
+    /// How the case of the query is matched, overrides the global setting.
+    #[serde(default)]
+    pub case_matching: Option<CaseMatching>,
+    /// Whether diacritics are ignored, overrides the global setting.
+    #[serde(default)]
+    pub normalization: Option<Normalization>,
+    /// Score matches as file paths, e.g. for channels listing files.
+    #[serde(default)]
+    pub match_paths: bool,
+    /// Criteria used to order matches, in order of priority.
+    #[serde(default)]
+    pub tiebreak: Vec<Tiebreak>,
+    /// Produce entries natively instead of running `command`.
+    #[serde(default)]
+    pub builtin: Option<BuiltinSource>,
+    /// Options for the builtin file walker, ignored for command sources.
+    #[serde(flatten)]
+    pub walker: WalkerOptions,
+    /// Splits entries into fields used for matching, display and output.
+    #[serde(flatten)]
+    pub fields: FieldOptions,
+    /// Run the source command again every time the query changes, with
+    /// `{query}` replaced by the current query.
+    #[serde(default)]
+    pub reload_on_query: bool,
+    /// How long to wait for the query to settle before reloading, in
+    /// milliseconds.
+    #[serde(default = "default_reload_debounce")]
+    pub reload_debounce: u64,
 }
 
 const fn default_frecency() -> bool {
     true
 }
 
+const fn default_reload_debounce() -> u64 {
+    150
+}
+
+/// Sources that tv produces in-process rather than by spawning a command.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize, serde::Serialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum BuiltinSource {
+    /// Recursively list files under the working directory.
+    Files,
+    /// Recursively list directories under the working directory.
+    Dirs,
+}
+
+impl BuiltinSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BuiltinSource::Files => "builtin:files",
+            BuiltinSource::Dirs => "builtin:dirs",
+        }
+    }
+}
+
+/// Previews that tv renders in-process rather than by spawning a command.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize, serde::Serialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum BuiltinPreview {
+    /// Syntax highlighted contents of text files.
+    Text,
+}
+
+impl BuiltinPreview {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BuiltinPreview::Text => "builtin:text",
+        }
+    }
+}
+
+/// Direction in which a split preview lays out its panes.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    serde::Deserialize,
+    serde::Serialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum PreviewSplit {
+    /// Panes are stacked on top of each other.
+    Vertical,
+    /// Panes are placed side by side.
+    Horizontal,
+}
+
+/// Options of the native file walker used by builtin sources.
+#[derive(
+    Debug, Clone, PartialEq, Eq, serde::Deserialize, serde::Serialize,
+)]
+pub struct WalkerOptions {
+    /// Include hidden files and directories.
+    #[serde(default)]
+    pub hidden: bool,
+    /// Respect `.gitignore` and `.ignore` files.
+    #[serde(default = "default_gitignore")]
+    pub gitignore: bool,
+    /// Maximum depth to descend to, unlimited if not set.
+    #[serde(default)]
+    pub max_depth: Option<usize>,
+    /// Follow symbolic links.
+    #[serde(default)]
+    pub follow_symlinks: bool,
+}
+
+const fn default_gitignore() -> bool {
+    true
+}
+
+impl Default for WalkerOptions {
+    fn default() -> Self {
+        Self {
+            hidden: false,
+            gitignore: default_gitignore(),
+            max_depth: None,
+            follow_symlinks: false,
+        }
+    }
+}
+
 /// Just a helper function to adapt cli parsing to serde deserialization.
 fn deserialize_entry_delimiter<'de, D>(
     deserializer: D,
@@ -431,10 +1007,24 @@ where
 pub struct PreviewSpec {
     #[serde(flatten)]
     pub command: CommandSpec,
+    /// Render the preview natively instead of running `command`.
+    #[serde(default)]
+    pub builtin: Option<BuiltinPreview>,
+    /// Template extracting the path of the file to preview from an entry,
+    /// only used by builtin previews (defaults to the entry itself).
+    #[serde(default)]
+    pub path: Option<Template>,
     #[serde(default)]
     pub offset: Option<Template>,
     #[serde(default = "cached_default")]
     pub cached: bool,
+    /// Separator used to join the selected entries substituted for `{+}`.
+    #[serde(default = "default_separator")]
+    pub separator: String,
+    /// Show every preview command at once in its own pane instead of
+    /// cycling through them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub split: Option<PreviewSplit>,
 }
 
 /// Preview caching is enabled by default.
@@ -451,8 +1041,12 @@ impl PreviewSpec {
     pub fn new(command: CommandSpec, offset: Option<Template>) -> Self {
         Self {
             command,
+            builtin: None,
+            path: None,
             offset,
             cached: false,
+            separator: default_separator(),
+            split: None,
         }
     }
 
@@ -467,9 +1061,14 @@ impl PreviewSpec {
                 interactive: false,
                 env: FxHashMap::default(),
                 shell: None,
+                program_missing: false,
             },
+            builtin: None,
+            path: None,
             offset: None,
             cached: false,
+            separator: default_separator(),
+            split: None,
         }
     }
 }
@@ -544,6 +1143,7 @@ mod tests {
             interactive: false,
             env: FxHashMap::default(),
             shell: None,
+            program_missing: false,
         };
 
         assert_eq!(command_spec.get_nth(0).template().raw(), "cmd1");
@@ -552,6 +1152,37 @@ mod tests {
         assert_eq!(command_spec.get_nth(3).template().raw(), "cmd1"); // wraps around
     }
 
+    #[test]
+    fn test_command_program() {
+        assert_eq!(command_program("fd -t f"), Some("fd"));
+        assert_eq!(command_program("env FOO=1 fd -t f"), Some("fd"));
+        assert_eq!(command_program("sh -c 'fd -t f | sort'"), Some("fd"));
+        assert_eq!(
+            command_program("env -i bash -c \"bat '{}'\""),
+            Some("bat")
+        );
+        assert_eq!(command_program("sh script.sh"), Some("sh"));
+        assert_eq!(command_program(""), None);
+    }
+
+    #[test]
+    fn test_command_spec_replaced_by_builtin() {
+        let mut installed: CommandSpec =
+            Template::parse("env sh -c 'echo {}'").unwrap().into();
+        let mut missing: CommandSpec =
+            Template::parse("sh -c 'tv-missing-program -t f'")
+                .unwrap()
+                .into();
+        // nothing is looked up until the program is resolved
+        assert!(!missing.replaced_by_builtin());
+
+        installed.resolve_program();
+        missing.resolve_program();
+        assert!(!installed.replaced_by_builtin());
+        assert!(missing.replaced_by_builtin());
+        assert!(CommandSpec::default().replaced_by_builtin());
+    }
+
     #[test]
     fn test_template_serialization() {
         #[derive(Deserialize, Serialize, Debug, PartialEq)]
@@ -828,6 +1459,128 @@ mod tests {
         assert_eq!(prototype.source.command.inner[2].name(), Some("All"));
     }
 
+    #[test]
+    fn test_channel_prototype_deserialization_builtin_source() {
+        let toml_data = r#"
+        [metadata]
+        name = "files"
+
+        [source]
+        builtin = "files"
+        hidden = true
+        max_depth = 3
+        "#;
+
+        let prototype: ChannelPrototype = from_str(toml_data).unwrap();
+
+        assert!(prototype.validate().is_ok());
+        assert_eq!(prototype.source.builtin, Some(BuiltinSource::Files));
+        assert!(prototype.source.command.inner.is_empty());
+        assert_eq!(
+            prototype.source.walker,
+            WalkerOptions {
+                hidden: true,
+                gitignore: true,
+                max_depth: Some(3),
+                follow_symlinks: false,
+            }
+        );
+    }
+
+    #[test]
+    fn test_channel_prototype_deserialization_builtin_preview() {
+        let toml_data = r#"
+        [metadata]
+        name = "text"
+
+        [source]
+        command = "rg . --no-heading --line-number"
+
+        [preview]
+        builtin = "text"
+        path = "{split:\\::0}"
+        offset = "{split:\\::1}"
+        "#;
+
+        let prototype: ChannelPrototype = from_str(toml_data).unwrap();
+
+        assert!(prototype.validate().is_ok());
+        let preview = prototype.preview.unwrap();
+        assert_eq!(preview.builtin, Some(BuiltinPreview::Text));
+        assert!(preview.command.inner.is_empty());
+        assert_eq!(preview.path.unwrap().raw(), "{split:\\::0}");
+    }
+
+    #[test]
+    fn test_channel_prototype_deserialization_output_delimiter() {
+        let toml_data = r#"
+        [metadata]
+        name = "git-log"
+
+        [source]
+        command = "git log -z"
+        entry_delimiter = "\\0"
+        output_delimiter = "\\0"
+        "#;
+
+        let prototype: ChannelPrototype = from_str(toml_data).unwrap();
+
+        assert_eq!(prototype.source.entry_delimiter, Some('\0'));
+        assert_eq!(prototype.source.output_delimiter, Some('\0'));
+    }
+
+    #[test]
+    fn test_channel_prototype_deserialization_matching_options() {
+        let toml_data = r#"
+        [metadata]
+        name = "files"
+
+        [source]
+        command = "fd -t f"
+        case_matching = "respect"
+        normalization = "never"
+        match_paths = true
+        tiebreak = ["score", "length", "begin", "index"]
+        "#;
+
+        let prototype: ChannelPrototype = from_str(toml_data).unwrap();
+
+        assert_eq!(
+            prototype.source.case_matching,
+            Some(CaseMatching::Respect)
+        );
+        assert_eq!(prototype.source.normalization, Some(Normalization::Never));
+        assert!(prototype.source.match_paths);
+        assert_eq!(
+            prototype.source.tiebreak,
+            vec![
+                Tiebreak::Score,
+                Tiebreak::Length,
+                Tiebreak::Begin,
+                Tiebreak::Index
+            ]
+        );
+
+        let prototype = ChannelPrototype::new("env", "printenv");
+        assert_eq!(prototype.source.case_matching, None);
+        assert!(!prototype.source.match_paths);
+    }
+
+    #[test]
+    fn test_channel_prototype_validate_missing_source_command() {
+        let toml_data = r#"
+        [metadata]
+        name = "files"
+
+        [source]
+        ansi = true
+        "#;
+
+        let prototype: ChannelPrototype = from_str(toml_data).unwrap();
+
+        assert!(prototype.validate().is_err());
+    }
+
     #[test]
     fn test_channel_prototype_deserialization_single_named_command() {
         let toml_data = r#"
@@ -1121,4 +1874,142 @@ mod tests {
         // Verify powershell deserializes correctly
         assert_eq!(prototype.source.command.shell, Some(Shell::Psh));
     }
+
+    #[test]
+    fn test_source_spec_reload_on_query() {
+        let toml_data = r#"
+        [metadata]
+        name = "live-grep"
+
+        [source]
+        command = ["rg --line-number {query}", { name = "hidden", run = "rg --hidden {query} ." }]
+        shell = "bash"
+        reload_on_query = true
+        "#;
+
+        let prototype: ChannelPrototype = from_str(toml_data).unwrap();
+        assert!(prototype.source.reload_on_query);
+        assert_eq!(prototype.source.reload_debounce, 150);
+
+        let command = prototype.source.command.with_query("it's");
+        assert_eq!(
+            command.get_nth(0).template().raw(),
+            r"rg --line-number 'it'\''s'"
+        );
+        assert_eq!(command.get_nth(1).name(), Some("hidden"));
+        assert_eq!(
+            command.get_nth(1).template().raw(),
+            r"rg --hidden 'it'\''s' ."
+        );
+    }
+
+    #[test]
+    fn test_template_format_with_selection() {
+        let template = Template::parse("git diff {+} -- {split:/:0}").unwrap();
+        assert!(template.uses_selection());
+        assert_eq!(
+            template
+                .format_with_selection("src/main.rs", "'a {0}' 'b'")
+                .unwrap(),
+            "git diff 'a {0}' 'b' -- src"
+        );
+
+        let template = Template::parse("cat {}").unwrap();
+        assert!(!template.uses_selection());
+        assert_eq!(
+            template.format_with_selection("foo", "bar").unwrap(),
+            "cat foo"
+        );
+    }
+
+    #[test]
+    fn test_prototype_with_params() {
+        let prototype: ChannelPrototype = toml::from_str(
+            r#"
+            [metadata]
+            name = "k8s-pods"
+
+            [metadata.params.namespace]
+            description = "Kubernetes namespace"
+
+            [metadata.params.context]
+            default = "kind"
+
+            [source]
+            command = "kubectl --context {param:context} -n {param:namespace} get pods"
+            shell = "bash"
+
+            [preview]
+            command = "kubectl -n {param:namespace} describe pod {split: :0}"
+            shell = "bash"
+            env = { NAMESPACE = "{param:namespace}" }
+            "#,
+        )
+        .unwrap();
+
+        let missing = prototype.missing_params(&FxHashMap::default());
+        assert_eq!(
+            missing
+                .iter()
+                .map(|(name, _)| name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["namespace"]
+        );
+
+        let values = FxHashMap::from_iter([(
+            "namespace".to_string(),
+            "kube system".to_string(),
+        )]);
+        assert!(prototype.missing_params(&values).is_empty());
+        let prototype = prototype.with_params(&values);
+        assert_eq!(
+            prototype.source.command.get_nth(0).template().raw(),
+            "kubectl --context 'kind' -n 'kube system' get pods"
+        );
+        let preview = prototype.preview.unwrap();
+        assert_eq!(
+            preview
+                .command
+                .get_nth(0)
+                .template()
+                .format("nginx-1 Running")
+                .unwrap(),
+            "kubectl -n 'kube system' describe pod nginx-1"
+        );
+        assert_eq!(preview.command.env["NAMESPACE"], "kube system");
+    }
+
+    #[test]
+    fn test_with_params_keeps_values_as_text() {
+        let mut preview = PreviewSpec::from_str_command(
+            "grep -e {param:pattern} '{split: :0}'",
+        );
+        preview.command.shell = Some(Shell::Bash);
+        let preview_with = |value: &str| {
+            preview.command.with_params(&FxHashMap::from_iter([(
+                "pattern".to_string(),
+                value.to_string(),
+            )]))
+        };
+
+        // the entry isn't substituted inside the quoted value
+        let command = preview_with("{}");
+        let template = command.get_nth(0).template();
+        assert_eq!(template.raw(), "grep -e '{}' '{split: :0}'");
+        assert_eq!(
+            template.format("main.rs 42").unwrap(),
+            "grep -e '{}' 'main.rs'"
+        );
+
+        // an unbalanced brace doesn't stop the rest from being formatted
+        let command = preview_with("{");
+        assert_eq!(
+            command
+                .get_nth(0)
+                .template()
+                .format_with_selection("main.rs 42", "")
+                .unwrap(),
+            "grep -e '{' 'main.rs'"
+        );
+    }
 }
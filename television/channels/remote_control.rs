@@ -5,7 +5,7 @@ use crate::{
         prototypes::{BinaryRequirement, ChannelPrototype},
     },
     event::Key,
-    matcher::Matcher,
+    matcher::{Matcher, config::Config},
     screen::result_item::ResultItem,
 };
 use anyhow::Result;
@@ -88,7 +88,11 @@ const REMOTE_NUM_THREADS: usize = 1;
 
 impl RemoteControl {
     pub fn new(cable_channels: Cable, sort_alphabetically: bool) -> Self {
-        let matcher = Matcher::new(SortStrategy::Score, REMOTE_NUM_THREADS);
+        let matcher = Matcher::new(
+            &Config::default(),
+            SortStrategy::Score,
+            REMOTE_NUM_THREADS,
+        );
         let injector = matcher.injector();
 
         // Sort channels based on configuration
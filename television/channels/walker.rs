@@ -0,0 +1,233 @@
+use crate::channels::{
+    entry_processor::{EntryInjector, EntryProcessor},
+    prototypes::{BuiltinSource, WalkerOptions},
+};
+use ignore::{WalkBuilder, WalkState};
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tracing::debug;
+
+/// A native file system walker backing the builtin `files` and `dirs`
+/// sources.
+///
+/// Directories are walked in parallel, honoring ignore files the same way
+/// `fd` does, and matching paths are reported relative to the walk root.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Walker {
+    pub kind: BuiltinSource,
+    pub options: WalkerOptions,
+}
+
+impl Walker {
+    pub fn new(kind: BuiltinSource, options: WalkerOptions) -> Self {
+        Self { kind, options }
+    }
+
+    /// Walks `root` and calls `emit` with every matching path, stopping early
+    /// once `cancelled` is set.
+    pub fn walk<F>(&self, root: &Path, cancelled: &AtomicBool, emit: F)
+    where
+        F: Fn(&Path) + Sync,
+    {
+        if self.options.max_depth == Some(0) {
+            return;
+        }
+        let gitignore = self.options.gitignore;
+        WalkBuilder::new(root)
+            .hidden(!self.options.hidden)
+            .ignore(gitignore)
+            .git_ignore(gitignore)
+            .git_global(gitignore)
+            .git_exclude(gitignore)
+            .parents(gitignore)
+            .require_git(false)
+            .max_depth(self.options.max_depth)
+            .follow_links(self.options.follow_symlinks)
+            .filter_entry(move |entry| {
+                !(gitignore && entry.file_name() == ".git")
+            })
+            .build_parallel()
+            .run(|| {
+                Box::new(|entry| {
+                    if cancelled.load(Ordering::Relaxed) {
+                        return WalkState::Quit;
+                    }
+                    let Ok(entry) = entry else {
+                        return WalkState::Continue;
+                    };
+                    let wanted = entry.depth() > 0
+                        && entry.file_type().is_some_and(
+                            |file_type| match self.kind {
+                                BuiltinSource::Files => file_type.is_file(),
+                                BuiltinSource::Dirs => file_type.is_dir(),
+                            },
+                        );
+                    if wanted {
+                        emit(
+                            entry
+                                .path()
+                                .strip_prefix(root)
+                                .unwrap_or(entry.path()),
+                        );
+                    }
+                    WalkState::Continue
+                })
+            });
+    }
+}
+
+/// Walks the current working directory and pushes every matching path to
+/// the injector.
+///
+/// The walk runs on a blocking thread and is stopped as soon as this future
+/// is dropped (e.g. when the channel is reloaded and the task aborted).
+pub async fn load_walker_candidates<P: EntryProcessor>(
+    walker: Walker,
+    processor: P,
+    injector: EntryInjector<P::Data>,
+) {
+    /// Flags the walk as cancelled when the loading task goes away.
+    struct CancelOnDrop(Arc<AtomicBool>);
+
+    impl Drop for CancelOnDrop {
+        fn drop(&mut self) {
+            self.0.store(true, Ordering::Relaxed);
+        }
+    }
+
+    debug!("Loading candidates from builtin source: {:?}", walker.kind);
+    let guard = CancelOnDrop(Arc::new(AtomicBool::new(false)));
+    let cancelled = guard.0.clone();
+    let _ = tokio::task::spawn_blocking(move || {
+        walker.walk(Path::new("."), &cancelled, |path| {
+            if let Some(path) = path.to_str() {
+                processor.push_to_injector(path.to_string(), &injector);
+            }
+        });
+    })
+    .await;
+    debug!("Finished walking the file system.");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parking_lot::Mutex;
+    use std::fs;
+
+    fn walk(root: &Path, walker: &Walker) -> Vec<String> {
+        let paths = Mutex::new(Vec::new());
+        walker.walk(root, &AtomicBool::new(false), |path| {
+            paths.lock().push(path.to_string_lossy().replace('\\', "/"));
+        });
+        let mut paths = paths.into_inner();
+        paths.sort();
+        paths
+    }
+
+    fn setup() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("src/nested")).unwrap();
+        fs::create_dir_all(root.join("target/debug")).unwrap();
+        fs::create_dir_all(root.join(".hidden")).unwrap();
+        fs::write(root.join("README.md"), "").unwrap();
+        fs::write(root.join("src/main.rs"), "").unwrap();
+        fs::write(root.join("src/nested/lib.rs"), "").unwrap();
+        fs::write(root.join("src/nested/debug.log"), "").unwrap();
+        fs::write(root.join("target/debug/tv"), "").unwrap();
+        fs::write(root.join(".hidden/config"), "").unwrap();
+        fs::write(root.join(".gitignore"), "/target\n").unwrap();
+        fs::write(root.join("src/.gitignore"), "*.log\n").unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_walk_files() {
+        let dir = setup();
+        let walker =
+            Walker::new(BuiltinSource::Files, WalkerOptions::default());
+        assert_eq!(
+            walk(dir.path(), &walker),
+            vec!["README.md", "src/main.rs", "src/nested/lib.rs"]
+        );
+    }
+
+    #[test]
+    fn test_walk_dirs() {
+        let dir = setup();
+        let walker =
+            Walker::new(BuiltinSource::Dirs, WalkerOptions::default());
+        assert_eq!(walk(dir.path(), &walker), vec!["src", "src/nested"]);
+    }
+
+    #[test]
+    fn test_walk_hidden_without_gitignore() {
+        let dir = setup();
+        let walker = Walker::new(
+            BuiltinSource::Files,
+            WalkerOptions {
+                hidden: true,
+                gitignore: false,
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            walk(dir.path(), &walker),
+            vec![
+                ".gitignore",
+                ".hidden/config",
+                "README.md",
+                "src/.gitignore",
+                "src/main.rs",
+                "src/nested/debug.log",
+                "src/nested/lib.rs",
+                "target/debug/tv",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_walk_gitignore_negation() {
+        let dir = setup();
+        let root = dir.path();
+        fs::write(root.join("src/nested/keep.log"), "").unwrap();
+        fs::write(root.join("src/nested/.gitignore"), "!keep.log\n").unwrap();
+        fs::write(root.join(".ignore"), "README.md\n").unwrap();
+        let walker =
+            Walker::new(BuiltinSource::Files, WalkerOptions::default());
+        assert_eq!(
+            walk(root, &walker),
+            vec!["src/main.rs", "src/nested/keep.log", "src/nested/lib.rs"]
+        );
+    }
+
+    #[test]
+    fn test_walk_max_depth() {
+        let dir = setup();
+        let walker = Walker::new(
+            BuiltinSource::Files,
+            WalkerOptions {
+                max_depth: Some(2),
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            walk(dir.path(), &walker),
+            vec!["README.md", "src/main.rs"]
+        );
+    }
+
+    #[test]
+    fn test_walk_cancelled() {
+        let dir = setup();
+        let walker =
+            Walker::new(BuiltinSource::Files, WalkerOptions::default());
+        let count = std::sync::atomic::AtomicUsize::new(0);
+        walker.walk(dir.path(), &AtomicBool::new(true), |_| {
+            count.fetch_add(1, Ordering::Relaxed);
+        });
+        assert_eq!(count.load(Ordering::Relaxed), 0);
+    }
+}
@@ -111,6 +111,94 @@ pub struct Cli {
     )]
     pub source_entry_delimiter: Option<String>,
 
+    /// The delimiter used to split entries into fields for --nth, --with-nth and --accept-nth.
+    ///
+    /// When a channel is specified: This overrides the field delimiter defined in the channel prototype.
+    ///
+    /// Defaults to splitting on runs of whitespace, in which case selected fields are joined
+    /// with a single space. Any other delimiter is matched literally and used to join fields.
+    #[arg(
+        long,
+        value_name = "STRING",
+        verbatim_doc_comment,
+        help_heading = "Source"
+    )]
+    pub field_delimiter: Option<String>,
+
+    /// The fields of each entry that are matched against the query.
+    ///
+    /// When a channel is specified: This overrides the fields defined in the channel prototype.
+    ///
+    /// Fields are a comma separated list of 1-based indices or ranges, negative indices
+    /// counting from the end: `1`, `-1`, `2..`, `..3`, `1..3`, `1,3..-2`.
+    /// Example: `docker ps | tv --nth -1` (only match on container names)
+    #[arg(
+        long,
+        value_name = "FIELDS",
+        verbatim_doc_comment,
+        help_heading = "Source"
+    )]
+    pub nth: Option<String>,
+
+    /// The fields of each entry that are displayed in the results list.
+    ///
+    /// When a channel is specified: This overrides the fields defined in the channel prototype.
+    ///
+    /// Uses the same syntax as --nth.
+    /// Example: `ps aux | tv --with-nth 2,11..` (only display the PID and command)
+    #[arg(
+        long,
+        value_name = "FIELDS",
+        verbatim_doc_comment,
+        conflicts_with = "source_display",
+        help_heading = "Source"
+    )]
+    pub with_nth: Option<String>,
+
+    /// The fields of each entry that are printed when it is selected.
+    ///
+    /// When a channel is specified: This overrides the fields defined in the channel prototype.
+    ///
+    /// Uses the same syntax as --nth. Ignored when an output template is set.
+    /// Example: `ps aux | tv --accept-nth 2` (output the PID)
+    #[arg(
+        long,
+        value_name = "FIELDS",
+        verbatim_doc_comment,
+        help_heading = "Source"
+    )]
+    pub accept_nth: Option<String>,
+
+    /// Run the source command again every time the query changes.
+    ///
+    /// When a channel is specified: This enables reloading for the channel's source command.
+    /// When no channel is specified: This enables reloading for the ad-hoc source command.
+    ///
+    /// `{query}` in the source command is replaced by the current query, quoted for the
+    /// shell, once typing has paused. The results are still filtered by the query.
+    /// Example: `tv --source-command 'rg --line-number {query}' --reload-on-query`
+    #[arg(
+        long,
+        default_value = "false",
+        verbatim_doc_comment,
+        help_heading = "Source"
+    )]
+    pub reload_on_query: bool,
+
+    /// Set a parameter declared by the channel, can be repeated.
+    ///
+    /// Parameters are declared in `[metadata.params]` and substituted for
+    /// `{param:<name>}` in the channel's commands. Parameters without a
+    /// value or a default are prompted for when the channel starts.
+    /// Example: `tv k8s-pods --param namespace=kube-system`
+    #[arg(
+        long = "param",
+        value_name = "KEY=VALUE",
+        verbatim_doc_comment,
+        help_heading = "Source"
+    )]
+    pub params: Vec<String>,
+
     /// Preview command to use for the current channel.
     ///
     /// When a channel is specified: This overrides the preview command defined in the channel prototype.
@@ -551,6 +639,68 @@ pub struct Cli {
     )]
     pub exact: bool,
 
+    /// Match entries against a regular expression instead of the fuzzy
+    /// query syntax.
+    ///
+    /// This flag works identically in both channel mode and ad-hoc mode.
+    ///
+    /// With smart case matching, the regular expression is case insensitive
+    /// unless it contains uppercase characters.
+    #[arg(
+        long,
+        default_value = "false",
+        conflicts_with = "exact",
+        verbatim_doc_comment,
+        help_heading = "Behavior"
+    )]
+    pub regex: bool,
+
+    /// How the case of the query is matched against entries.
+    ///
+    /// When a channel is specified: This overrides the case matching defined in the channel prototype.
+    ///
+    /// `smart` ignores case unless a term of the query contains uppercase characters.
+    #[arg(long, value_enum, verbatim_doc_comment, help_heading = "Behavior")]
+    pub case_matching: Option<CaseMatching>,
+
+    /// Whether latin characters with diacritics are matched by their ASCII counterpart.
+    ///
+    /// When a channel is specified: This overrides the normalization defined in the channel prototype.
+    ///
+    /// `smart` ignores diacritics unless a term of the query contains some, e.g. `cafe`
+    /// matches `café`. `never` always matches diacritics.
+    #[arg(long, value_enum, verbatim_doc_comment, help_heading = "Behavior")]
+    pub normalization: Option<Normalization>,
+
+    /// Score matches as file paths.
+    ///
+    /// This gives a bonus to matches following path separators rather than whitespace,
+    /// which ranks matches on file names higher.
+    #[arg(
+        long,
+        default_value = "false",
+        verbatim_doc_comment,
+        help_heading = "Behavior"
+    )]
+    pub match_paths: bool,
+
+    /// Comma-separated criteria used to order matches, in order of priority.
+    ///
+    /// When a channel is specified: This overrides the tiebreak defined in the channel prototype.
+    ///
+    /// Entries that are equal on every criterion are kept in source order.
+    /// Defaults to `score,length`.
+    /// Example: `tv files --tiebreak score,length,begin`
+    #[arg(
+        long,
+        value_enum,
+        value_delimiter = ',',
+        value_name = "CRITERIA",
+        verbatim_doc_comment,
+        help_heading = "Behavior"
+    )]
+    pub tiebreak: Option<Vec<Tiebreak>>,
+
     /// Automatically select and output the first entry if there is only one
     /// entry.
     ///
@@ -603,6 +753,25 @@ pub struct Cli {
     )]
     pub take_1_fast: bool,
 
+    /// Listen for commands on the given Unix socket.
+    ///
+    /// This flag works identically in both channel mode and ad-hoc mode.
+    ///
+    /// Each line sent to the socket is a command, either an action name optionally
+    /// followed by `:<argument>` or a JSON object with `action` and `argument` keys.
+    /// Besides the actions usable in keybindings, the following commands are supported:
+    /// `change_query:<query>`, `change_preview:<command>`, `switch_channel:<name>`,
+    /// `get_state` and `subscribe`. Every command is answered with a single JSON line.
+    ///
+    /// Example: `echo 'change_query:foo' | socat - UNIX-CONNECT:/tmp/tv.sock`
+    #[arg(
+        long,
+        value_name = "PATH",
+        verbatim_doc_comment,
+        help_heading = "Behavior"
+    )]
+    pub listen: Option<String>,
+
     /// Keybindings to override the default keybindings.
     ///
     /// This flag works identically in both channel mode and ad-hoc mode.
@@ -637,6 +806,57 @@ pub struct Cli {
     )]
     pub expect: Option<String>,
 
+    /// Format used to print the selection on exit.
+    ///
+    /// This flag works identically in both channel mode and ad-hoc mode.
+    ///
+    /// - `plain` (default): one entry per line, preceded by the `--expect` key if any
+    /// - `json`: a single JSON document containing the selected entries (raw, display
+    ///   and output), the expect key, the query, the channel name and the index of
+    ///   the active source command, written with no entries when nothing was selected
+    /// - `jsonl`: one JSON object per selected entry, each carrying the same fields
+    /// - `nul`: like `plain` but with entries terminated by a null byte
+    ///
+    /// Example: `tv files --output-format json | jq -r '.entries[].output'`
+    #[arg(
+        long,
+        value_enum,
+        value_name = "FORMAT",
+        verbatim_doc_comment,
+        help_heading = "Output"
+    )]
+    pub output_format: Option<OutputFormat>,
+
+    /// The delimiter used to terminate each selected entry when printing the selection.
+    ///
+    /// Only applies to the `plain` output format and defaults to a newline.
+    /// Supports the same escape sequences as
+    /// `--source-entry-delimiter` (`\n`, `\t`, `\r`, `\0`) which makes it possible to
+    /// round-trip multiline entries.
+    ///
+    /// Example: `tv --source-command "git log -z" --source-entry-delimiter '\0' --output-delimiter '\0'`
+    #[arg(
+        long,
+        value_name = "STRING",
+        verbatim_doc_comment,
+        conflicts_with = "print0",
+        help_heading = "Output"
+    )]
+    pub output_delimiter: Option<String>,
+
+    /// Terminate each selected entry with a null byte instead of a newline.
+    ///
+    /// Shorthand for `--output-format nul`, meant to be paired with
+    /// `xargs -0` or `--source-entry-delimiter '\0'`.
+    #[arg(
+        long,
+        default_value = "false",
+        verbatim_doc_comment,
+        conflicts_with = "output_format",
+        help_heading = "Output"
+    )]
+    pub print0: bool,
+
     /// Provide a custom configuration file to use.
     ///
     /// This flag works identically in both channel mode and ad-hoc mode.
@@ -679,15 +899,116 @@ pub enum Command {
         #[arg(value_enum)]
         shell: Shell,
     },
-    /// Downloads the latest collection of channel prototypes from github
-    /// and saves them to the local configuration directory.
+    /// Downloads the latest collection of channel prototypes and saves
+    /// them to the cable directory.
+    ///
+    /// Channels previously installed by this command are updated, while
+    /// channels created or edited locally are left untouched.
     UpdateChannels {
-        /// Force update on unsupported and already existing channels.
+        /// Force update on unsupported, locally edited and untracked
+        /// channels.
         #[arg(long, default_value = "false")]
         force: bool,
+        /// Where to fetch channels from instead of the television github
+        /// repository.
+        ///
+        /// Either a directory of channel files (e.g. a git checkout), a
+        /// local index file or the URL of an index file. An index is a
+        /// TOML file with a `channels` array of paths or URLs, relative
+        /// paths being resolved against the index location.
+        #[arg(long, value_name = "SOURCE")]
+        from: Option<String>,
+        /// Print the channels that would be added, changed or removed
+        /// without writing anything.
+        #[arg(long, default_value = "false")]
+        dry_run: bool,
+    },
+    /// Checks channel files for mistakes and reports them with their line
+    /// numbers.
+    ///
+    /// Exits with a non-zero status if any error is found.
+    Validate {
+        /// Channel files or directories to check, defaults to the cable
+        /// directory.
+        #[arg(value_name = "PATH")]
+        paths: Vec<String>,
+    },
+    /// Manages the frecency records used to rank the entries selected
+    /// before.
+    Frecency {
+        #[command(subcommand)]
+        command: FrecencyCommand,
+    },
+}
+
+#[derive(Subcommand, Debug, PartialEq, Clone)]
+pub enum FrecencyCommand {
+    /// Lists the entries of a channel along with their score, access
+    /// count and last access, from the highest score.
+    List {
+        /// The channel whose entries are listed.
+        channel: String,
+    },
+    /// Forgets the given entries of a channel.
+    Remove {
+        /// The channel to forget entries of.
+        channel: String,
+        /// The entries to forget, as they were selected.
+        #[arg(required = true, value_name = "ENTRY")]
+        entries: Vec<String>,
+    },
+    /// Forgets all the entries of a channel.
+    Reset {
+        /// The channel to forget entries of.
+        channel: String,
+    },
+    /// Prints the records of a channel as JSON.
+    Export {
+        /// The channel whose records are exported.
+        channel: String,
+    },
+    /// Merges records into a channel, from `tv frecency export` or from
+    /// the database of another tool.
+    ///
+    /// The access counts of entries already recorded are added up.
+    ///
+    /// Examples:
+    ///   tv frecency import dirs --from zoxide ~/.local/share/zoxide/db.zo
+    ///   tv frecency import dirs --from fasd ~/.fasd
+    ///   tv frecency import dirs --from autojump ~/.local/share/autojump/autojump.txt
+    #[command(verbatim_doc_comment)]
+    Import {
+        /// The channel to import records into.
+        channel: String,
+        /// The file to read records from, defaults to stdin.
+        #[arg(value_name = "PATH")]
+        file: Option<String>,
+        /// The format of the records.
+        ///
+        /// `zoxide` reads both zoxide's database and the output of
+        /// `zoxide query --list --score`.
+        #[arg(long, value_enum, default_value = "tv")]
+        from: FrecencyImportFormat,
+    },
+    /// Forgets the entries that are paths to files that no longer exist.
+    ///
+    /// Only absolute paths are checked since the directory relative paths
+    /// were selected from is unknown.
+    Prune {
+        /// The channel to prune, defaults to all channels.
+        channel: Option<String>,
     },
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum FrecencyImportFormat {
+    /// Records exported by `tv frecency export`.
+    Tv,
+    Zoxide,
+    Fasd,
+    Autojump,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
 pub enum Shell {
     Bash,
@@ -710,6 +1031,37 @@ pub enum InputPosition {
     Bottom,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum OutputFormat {
+    #[default]
+    Plain,
+    Json,
+    Jsonl,
+    Nul,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum CaseMatching {
+    Smart,
+    Respect,
+    Ignore,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum Normalization {
+    Smart,
+    Never,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum Tiebreak {
+    Score,
+    Length,
+    Begin,
+    End,
+    Index,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
 pub enum BorderType {
     None,
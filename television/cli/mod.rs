@@ -1,20 +1,27 @@
 use crate::{
     action::{Action, Actions},
     cable::Cable,
-    channels::prototypes::{ChannelPrototype, Template},
-    cli::args::{Cli, Command},
+    channels::{
+        fields::FieldSelector,
+        prototypes::{ChannelPrototype, Template},
+    },
+    cli::args::{Cli, Command, OutputFormat},
     config::{
         Keybindings, get_config_dir, get_data_dir, merge_keybindings,
         ui::{BorderType, Padding},
     },
     errors::{cli_parsing_error_exit, unknown_channel_exit},
     event::Key,
+    matcher::{
+        config::{CaseMatching, Normalization},
+        tiebreak::Tiebreak,
+    },
     screen::layout::{InputPosition, Orientation},
     utils::paths::expand_tilde,
 };
 use anyhow::{Result, anyhow};
-use clap::CommandFactory;
 use clap::error::ErrorKind;
+use clap::{CommandFactory, ValueEnum};
 use colored::Colorize;
 use rustc_hash::FxHashMap;
 use std::{
@@ -69,6 +76,14 @@ pub struct ChannelCli {
     pub source_display: Option<Template>,
     pub source_output: Option<Template>,
     pub source_entry_delimiter: Option<char>,
+    pub field_delimiter: Option<String>,
+    pub nth: Option<FieldSelector>,
+    pub with_nth: Option<FieldSelector>,
+    pub accept_nth: Option<FieldSelector>,
+    pub reload_on_query: bool,
+    /// Values of the channel parameters given with `--param`.
+    pub params: FxHashMap<String, String>,
+    pub output_delimiter: Option<char>,
     pub autocomplete_prompt: Option<String>,
     pub ansi: bool,
     pub no_sort: bool,
@@ -118,6 +133,11 @@ pub struct ChannelCli {
 
     // Behavior and matching configuration
     pub exact: bool,
+    pub regex: bool,
+    pub case_matching: Option<CaseMatching>,
+    pub normalization: Option<Normalization>,
+    pub match_paths: bool,
+    pub tiebreak: Option<Vec<Tiebreak>>,
     pub select_1: bool,
     pub take_1: bool,
     pub take_1_fast: bool,
@@ -143,6 +163,8 @@ pub struct GlobalCli {
     pub no_preview: bool,
     pub no_help_panel: bool,
     pub no_status_bar: bool,
+    pub output_format: OutputFormat,
+    pub listen: Option<PathBuf>,
 }
 
 /// Post-processes the raw CLI arguments into a structured format with validation.
@@ -189,6 +211,19 @@ pub fn post_process(
         }
     }
 
+    let params = cli
+        .params
+        .iter()
+        .map(|param| {
+            let (key, value) = param.split_once('=').unwrap_or_else(|| {
+                cli_parsing_error_exit(&format!(
+                    "Invalid parameter '{param}', expected KEY=VALUE"
+                ))
+            });
+            (key.to_string(), value.to_string())
+        })
+        .collect();
+
     // Parse preview overrides if provided
     let preview_command = cli.preview_command.as_ref().map(|preview_cmd| {
         Template::parse(preview_cmd).unwrap_or_else(|e| {
@@ -236,6 +271,24 @@ pub fn post_process(
         cmd.error(ErrorKind::ArgumentConflict, msg).exit();
     }
 
+    // `--output-delimiter` only applies to the `plain` output format
+    if cli.output_delimiter.is_some()
+        && let Some(format) = cli
+            .output_format
+            .filter(|format| *format != OutputFormat::Plain)
+    {
+        let mut cmd = Cli::command();
+        let arg1 = "'--output-delimiter <STRING>'".yellow();
+        let arg2 = format!(
+            "'--output-format {}'",
+            format.to_possible_value().unwrap().get_name()
+        )
+        .yellow();
+        let msg =
+            format!("The argument {} cannot be used with {}", arg1, arg2);
+        cmd.error(ErrorKind::ArgumentConflict, msg).exit();
+    }
+
     // Validate interdependent flags for ad-hoc mode (when no channel is specified)
     // This ensures ad-hoc channels have all necessary components to function properly
     validate_adhoc_mode_constraints(&cli, readable_stdin);
@@ -295,6 +348,20 @@ pub fn post_process(
             parse_source_entry_delimiter(delimiter)
                 .unwrap_or_else(|e| cli_parsing_error_exit(&e.to_string()))
         });
+    let parse_fields = |fields: &Option<String>| {
+        fields.as_ref().map(|fields| {
+            FieldSelector::parse(fields)
+                .unwrap_or_else(|e| cli_parsing_error_exit(&e.to_string()))
+        })
+    };
+    let nth = parse_fields(&cli.nth);
+    let with_nth = parse_fields(&cli.with_nth);
+    let accept_nth = parse_fields(&cli.accept_nth);
+
+    let output_delimiter = cli.output_delimiter.as_ref().map(|delimiter| {
+        parse_source_entry_delimiter(delimiter)
+            .unwrap_or_else(|e| cli_parsing_error_exit(&e.to_string()))
+    });
 
     // Determine layout
     let layout: Option<Orientation> = cli.layout.map(Orientation::from);
@@ -327,6 +394,13 @@ pub fn post_process(
             source_display,
             source_output,
             source_entry_delimiter,
+            field_delimiter: cli.field_delimiter,
+            nth,
+            with_nth,
+            accept_nth,
+            reload_on_query: cli.reload_on_query,
+            params,
+            output_delimiter,
             no_sort: cli.no_sort,
 
             // Autocomplete and ANSI configuration
@@ -378,6 +452,13 @@ pub fn post_process(
 
             // Behavior and matching configuration
             exact: cli.exact,
+            regex: cli.regex,
+            case_matching: cli.case_matching.map(CaseMatching::from),
+            normalization: cli.normalization.map(Normalization::from),
+            match_paths: cli.match_paths,
+            tiebreak: cli.tiebreak.map(|tiebreak| {
+                tiebreak.into_iter().map(Tiebreak::from).collect()
+            }),
             select_1: cli.select_1,
             take_1: cli.take_1,
             take_1_fast: cli.take_1_fast,
@@ -409,6 +490,16 @@ pub fn post_process(
             no_preview: cli.no_preview,
             no_help_panel: cli.no_help_panel,
             no_status_bar: cli.no_status_bar,
+
+            // Output
+            output_format: if cli.print0 {
+                OutputFormat::Nul
+            } else {
+                cli.output_format.unwrap_or_default()
+            },
+
+            // Control server
+            listen: cli.listen.map(|p| expand_tilde(&p)),
         },
     }
 }
@@ -668,6 +759,7 @@ Data directory: {data_dir_path}"
 #[cfg(test)]
 mod tests {
     use crate::{action::Action, event::Key};
+    use clap::Parser;
 
     use super::*;
 
@@ -719,6 +811,24 @@ mod tests {
         assert_eq!(post_processed_cli.global.command, None);
     }
 
+    #[test]
+    fn test_print0_is_nul_output_format() {
+        let cli = Cli {
+            channel: Some("files".to_string()),
+            print0: true,
+            ..Default::default()
+        };
+
+        let post_processed_cli = post_process(cli, false, &test_cable());
+
+        assert_eq!(post_processed_cli.global.output_format, OutputFormat::Nul);
+        assert_eq!(post_processed_cli.channel.output_delimiter, None);
+        assert!(
+            Cli::try_parse_from(["tv", "--print0", "--output-format", "json"])
+                .is_err()
+        );
+    }
+
     /// Regression test for issue #1043: a registered channel name must take
     /// precedence over a same-named subdirectory in the current working dir.
     #[test]
@@ -4,27 +4,34 @@ use crate::{
 };
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use rustc_hash::FxHashMap;
-use serde::Deserialize;
+use serde::{Deserialize, Deserializer};
 use std::ops::Deref;
 use std::ops::DerefMut;
 use std::str::FromStr;
 use tracing::debug;
 
 /// A hashmap of keyboard key bindings to actions.
-#[derive(Default, Debug, Clone, PartialEq, Eq, Deserialize)]
-pub struct Keybindings(pub FxHashMap<Key, Actions>);
+///
+/// Besides single keys, actions can be bound to sequences of keys pressed one
+/// after the other, written as space separated keys (e.g. `"g g"`).
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct Keybindings {
+    pub keys: FxHashMap<Key, Actions>,
+    /// Key sequences, each made of at least two keys.
+    pub sequences: FxHashMap<Vec<Key>, Actions>,
+}
 
 impl Deref for Keybindings {
     type Target = FxHashMap<Key, Actions>;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.keys
     }
 }
 
 impl DerefMut for Keybindings {
     fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.0
+        &mut self.keys
     }
 }
 
@@ -34,18 +41,119 @@ impl From<Vec<(Key, Action)>> for Keybindings {
         for (key, action) in bindings {
             map.insert(key, action.into());
         }
-        Keybindings(map)
+        Keybindings::from(map)
+    }
+}
+
+impl From<FxHashMap<Key, Actions>> for Keybindings {
+    fn from(keys: FxHashMap<Key, Actions>) -> Self {
+        Keybindings {
+            keys,
+            sequences: FxHashMap::default(),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Keybindings {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let raw = FxHashMap::<String, Actions>::deserialize(deserializer)?;
+        let mut keybindings = Keybindings::new();
+        for (raw_keys, actions) in raw {
+            let keys = parse_key_sequence(&raw_keys)
+                .map_err(serde::de::Error::custom)?;
+            keybindings.bind(keys, actions);
+        }
+        Ok(keybindings)
     }
 }
 
 impl Keybindings {
     pub fn new() -> Self {
-        Keybindings(FxHashMap::default())
+        Keybindings::default()
     }
 
     pub fn merge(self, new: &Keybindings) -> Keybindings {
         merge_keybindings(self, new)
     }
+
+    /// Binds actions to a key, or to a sequence of keys.
+    pub fn bind(&mut self, keys: Vec<Key>, actions: Actions) {
+        if let [key] = keys.as_slice() {
+            self.keys.insert(*key, actions);
+        } else {
+            self.sequences.insert(keys, actions);
+        }
+    }
+
+    /// Gets the actions bound to a key, or to a sequence of keys.
+    pub fn get_keys(&self, keys: &[Key]) -> Option<&Actions> {
+        match keys {
+            [key] => self.keys.get(key),
+            _ => self.sequences.get(keys),
+        }
+    }
+
+    /// Whether `keys` are the beginning of a longer key sequence.
+    pub fn starts_sequence(&self, keys: &[Key]) -> bool {
+        self.sequences.keys().any(|sequence| {
+            sequence.len() > keys.len() && sequence.starts_with(keys)
+        })
+    }
+
+    /// Iterates over the actions of every key and key sequence.
+    pub fn all_actions(&self) -> impl Iterator<Item = &Actions> {
+        self.keys.values().chain(self.sequences.values())
+    }
+}
+
+/// Parses a key, or a sequence of space separated keys such as `"g g"` or
+/// `"space f"`.
+///
+/// # Examples
+///
+/// ```rust
+/// use television::config::keybindings::parse_key_sequence;
+/// use television::event::Key;
+///
+/// assert_eq!(parse_key_sequence("ctrl-a").unwrap(), vec![Key::Ctrl('a')]);
+/// assert_eq!(
+///     parse_key_sequence("g g").unwrap(),
+///     vec![Key::Char('g'), Key::Char('g')]
+/// );
+/// assert_eq!(parse_key_sequence(" ").unwrap(), vec![Key::Char(' ')]);
+/// ```
+pub fn parse_key_sequence(raw: &str) -> Result<Vec<Key>, String> {
+    if raw.trim().is_empty() || !raw.trim().contains(char::is_whitespace) {
+        return Ok(vec![Key::from_str(raw)?]);
+    }
+    raw.split_whitespace().map(Key::from_str).collect()
+}
+
+/// Formats a sequence of keys the way it is written in the configuration.
+pub fn key_sequence_to_string(keys: &[Key]) -> String {
+    keys.iter()
+        .map(|key| match key {
+            Key::Char(' ') => "space".to_string(),
+            key => key.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Vim-like modal keybindings, where keys type text in insert mode and only
+/// trigger actions in normal mode.
+#[derive(Default, Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct ModalKeybindings {
+    /// Whether modes are enabled, tv starting in insert mode.
+    pub enabled: bool,
+    /// Bindings taking precedence over the other ones in normal mode.
+    pub normal: Keybindings,
+    /// Bindings taking precedence over the other ones in insert mode.
+    pub insert: Keybindings,
 }
 
 /// Merges two binding collections, with new bindings taking precedence.
@@ -85,14 +193,17 @@ pub fn merge_keybindings(
     mut base: Keybindings,
     new: &Keybindings,
 ) -> Keybindings {
-    debug!("bindings before: {:?}", base.0);
+    debug!("bindings before: {:?}", base.keys);
 
     // Merge new bindings - they take precedence over existing ones
-    for (key, actions) in &new.0 {
-        base.0.insert(*key, actions.clone());
+    for (key, actions) in &new.keys {
+        base.keys.insert(*key, actions.clone());
+    }
+    for (keys, actions) in &new.sequences {
+        base.sequences.insert(keys.clone(), actions.clone());
     }
 
-    debug!("bindings after: {:?}", base.0);
+    debug!("bindings after: {:?}", base.keys);
 
     base
 }
@@ -577,21 +688,21 @@ mod tests {
         let merged = merge_keybindings(base, &new);
 
         // Should contain both base and custom keybindings
-        assert!(merged.0.contains_key(&Key::Esc));
-        assert_eq!(merged.0.get(&Key::Esc), Some(&Action::Quit.into()));
-        assert!(merged.0.contains_key(&Key::Down));
+        assert!(merged.contains_key(&Key::Esc));
+        assert_eq!(merged.get(&Key::Esc), Some(&Action::Quit.into()));
+        assert!(merged.contains_key(&Key::Down));
         assert_eq!(
-            merged.0.get(&Key::Down),
+            merged.get(&Key::Down),
             Some(&Action::SelectNextEntry.into())
         );
-        assert!(merged.0.contains_key(&Key::Ctrl('j')));
+        assert!(merged.contains_key(&Key::Ctrl('j')));
         assert_eq!(
-            merged.0.get(&Key::Ctrl('j')),
+            merged.get(&Key::Ctrl('j')),
             Some(&Action::SelectNextEntry.into())
         );
-        assert!(merged.0.contains_key(&Key::PageDown));
+        assert!(merged.contains_key(&Key::PageDown));
         assert_eq!(
-            merged.0.get(&Key::PageDown),
+            merged.get(&Key::PageDown),
             Some(&Action::SelectNextPage.into())
         );
     }
@@ -608,15 +719,15 @@ mod tests {
         .unwrap();
 
         // Normal action binding should work
-        assert_eq!(keybindings.0.get(&Key::Esc), Some(&Action::Quit.into()));
+        assert_eq!(keybindings.get(&Key::Esc), Some(&Action::Quit.into()));
         assert_eq!(
-            keybindings.0.get(&Key::Down),
+            keybindings.get(&Key::Down),
             Some(&Action::SelectNextEntry.into())
         );
 
         // false should bind to NoOp (unbinding)
         assert_eq!(
-            keybindings.0.get(&Key::Ctrl('c')),
+            keybindings.get(&Key::Ctrl('c')),
             Some(&Action::NoOp.into())
         );
     }
@@ -633,11 +744,11 @@ mod tests {
         .unwrap();
 
         // Single action should work
-        assert_eq!(keybindings.0.get(&Key::Esc), Some(&Action::Quit.into()));
+        assert_eq!(keybindings.get(&Key::Esc), Some(&Action::Quit.into()));
 
         // Multiple actions should work
         assert_eq!(
-            keybindings.0.get(&Key::Ctrl('s')),
+            keybindings.get(&Key::Ctrl('s')),
             Some(&Actions::multiple(vec![
                 Action::ReloadSource,
                 Action::CopyEntryToClipboard
@@ -646,7 +757,7 @@ mod tests {
 
         // Three actions should work
         assert_eq!(
-            keybindings.0.get(&Key::F(1)),
+            keybindings.get(&Key::F(1)),
             Some(&Actions::multiple(vec![
                 Action::ToggleHelp,
                 Action::TogglePreview,
@@ -671,13 +782,13 @@ mod tests {
             ]),
         );
         custom_bindings.insert(Key::Esc, Action::NoOp.into()); // Override
-        let custom_keybindings = Keybindings(custom_bindings);
+        let custom_keybindings = Keybindings::from(custom_bindings);
 
         let merged = merge_keybindings(base_keybindings, &custom_keybindings);
 
         // Custom multiple actions should be present
         assert_eq!(
-            merged.0.get(&Key::Ctrl('s')),
+            merged.get(&Key::Ctrl('s')),
             Some(&Actions::multiple(vec![
                 Action::ReloadSource,
                 Action::CopyEntryToClipboard
@@ -685,11 +796,11 @@ mod tests {
         );
 
         // Override should work
-        assert_eq!(merged.0.get(&Key::Esc), Some(&Action::NoOp.into()));
+        assert_eq!(merged.get(&Key::Esc), Some(&Action::NoOp.into()));
 
         // Original binding should be preserved
         assert_eq!(
-            merged.0.get(&Key::Enter),
+            merged.get(&Key::Enter),
             Some(&Action::ConfirmSelection.into())
         );
     }
@@ -715,26 +826,26 @@ mod tests {
         )
         .unwrap();
 
-        assert_eq!(keybindings.0.len(), 6);
+        assert_eq!(keybindings.len(), 6);
 
         // Verify all binding types work correctly
         assert_eq!(
-            keybindings.0.get(&Key::Esc),
+            keybindings.get(&Key::Esc),
             Some(&Actions::single(Action::Quit))
         );
         assert_eq!(
-            keybindings.0.get(&Key::Enter),
+            keybindings.get(&Key::Enter),
             Some(&Action::ConfirmSelection.into())
         );
         assert_eq!(
-            keybindings.0.get(&Key::Ctrl('s')),
+            keybindings.get(&Key::Ctrl('s')),
             Some(&Actions::multiple(vec![
                 Action::ReloadSource,
                 Action::CopyEntryToClipboard
             ]))
         );
         assert_eq!(
-            keybindings.0.get(&Key::F(1)),
+            keybindings.get(&Key::F(1)),
             Some(&Actions::multiple(vec![
                 Action::ToggleHelp,
                 Action::TogglePreview,
@@ -742,12 +853,50 @@ mod tests {
             ]))
         );
         assert_eq!(
-            keybindings.0.get(&Key::Ctrl('c')),
+            keybindings.get(&Key::Ctrl('c')),
             Some(&Actions::single(Action::NoOp))
         );
         assert_eq!(
-            keybindings.0.get(&Key::Tab),
+            keybindings.get(&Key::Tab),
             Some(&Actions::multiple(vec![Action::ToggleSelectionDown]))
         );
     }
+
+    #[test]
+    fn test_deserialize_key_sequences() {
+        let keybindings: Keybindings = toml::from_str(
+            r#"
+                esc = "quit"
+                "g g" = "reload_source"
+                "space f" = ["toggle_preview", "toggle_help"]
+                " " = "toggle_selection_down"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(keybindings.len(), 2);
+        assert_eq!(
+            keybindings.get(&Key::Char(' ')),
+            Some(&Action::ToggleSelectionDown.into())
+        );
+        assert_eq!(
+            keybindings.get_keys(&[Key::Char('g'), Key::Char('g')]),
+            Some(&Action::ReloadSource.into())
+        );
+        assert_eq!(
+            keybindings.get_keys(&[Key::Char(' '), Key::Char('f')]),
+            Some(&Actions::multiple(vec![
+                Action::TogglePreview,
+                Action::ToggleHelp
+            ]))
+        );
+        assert!(keybindings.starts_sequence(&[Key::Char('g')]));
+        assert!(
+            !keybindings.starts_sequence(&[Key::Char('g'), Key::Char('g')])
+        );
+
+        assert!(
+            toml::from_str::<Keybindings>(r#""g nope" = "quit""#).is_err()
+        );
+    }
 }
@@ -1,19 +1,30 @@
 use crate::{
     action::{Action, CUSTOM_ACTION_PREFIX},
-    channels::prototypes::{
-        ActionSpec, BinaryRequirement, ChannelPrototype, CommandSpec, Template,
+    channels::{
+        fields::FieldOptions,
+        prototypes::{
+            ActionSpec, BinaryRequirement, ChannelParam, ChannelPrototype,
+            CommandSpec, PreviewSplit, Template, default_separator,
+        },
+        walker::Walker,
     },
     cli::{ChannelCli, GlobalCli, PostProcessedCli},
     config::{
         Config, Keybindings, merge_keybindings,
         ui::{BorderType, Padding, ThemeOverrides},
     },
+    frecency::{
+        DEFAULT_FRECENCY_GLOBAL_WEIGHT, FrecencyConfig, FrecencyKey,
+        FrecencyScope,
+    },
     keymap::InputMap,
+    matcher::config::Config as MatcherConfig,
+    previewer::builtin::BuiltinPreviewer,
     screen::layout::{InputPosition, Orientation},
     utils::shell::Shell,
 };
 use rustc_hash::FxHashMap;
-use std::path::PathBuf;
+use std::{path::PathBuf, time::Duration};
 
 /// Represents the different layers of configuration that make up the final
 /// merged configuration used by the application.
@@ -29,6 +40,11 @@ pub struct ConfigLayers {
     channel_cli: ChannelCli,
     /// The global CLI configuration options that will persist across channels.
     global_cli: GlobalCli,
+    /// Values of the channel parameters given with `--param`, along with
+    /// the name of the channel they were given for.
+    cli_params: (String, FxHashMap<String, String>),
+    /// Values of the channel parameters entered in the TUI.
+    channel_params: FxHashMap<String, String>,
 }
 
 impl ConfigLayers {
@@ -37,11 +53,18 @@ impl ConfigLayers {
         channel: ChannelPrototype,
         cli: PostProcessedCli,
     ) -> Self {
+        let mut channel_cli = cli.channel;
+        let cli_params = (
+            channel.metadata.name.clone(),
+            std::mem::take(&mut channel_cli.params),
+        );
         Self {
             base_config,
             channel,
-            channel_cli: cli.channel,
+            channel_cli,
             global_cli: cli.global,
+            cli_params,
+            channel_params: FxHashMap::default(),
         }
     }
 
@@ -50,22 +73,71 @@ impl ConfigLayers {
         self.channel = channel;
         // Reset channel-specific CLI options to defaults
         self.channel_cli = ChannelCli::default();
+        self.channel_params.clear();
+    }
+
+    /// Set the values of the current channel parameters, on top of the ones
+    /// given on the command line.
+    pub fn set_channel_params(&mut self, params: FxHashMap<String, String>) {
+        self.channel_params = params;
+    }
+
+    /// The values given with `--param` that apply to `channel`, which are
+    /// none unless it is the channel they were given for.
+    fn cli_params_for(
+        &self,
+        channel: &ChannelPrototype,
+    ) -> FxHashMap<String, String> {
+        let (name, params) = &self.cli_params;
+        if *name == channel.metadata.name {
+            params.clone()
+        } else {
+            FxHashMap::default()
+        }
+    }
+
+    /// The values of the current channel parameters.
+    fn channel_params(&self) -> FxHashMap<String, String> {
+        let mut params = self.cli_params_for(&self.channel);
+        params.extend(self.channel_params.clone());
+        params
+    }
+
+    /// The parameters of the current channel that still need a value.
+    pub fn missing_channel_params(&self) -> Vec<(String, ChannelParam)> {
+        self.channel.missing_params(&self.channel_params())
+    }
+
+    /// The parameters of `channel` that will need a value once switched to.
+    pub fn missing_params_on_switch(
+        &self,
+        channel: &ChannelPrototype,
+    ) -> Vec<(String, ChannelParam)> {
+        channel.missing_params(&self.cli_params_for(channel))
+    }
+
+    /// The CLI options of the current channel.
+    pub fn channel_cli(&self) -> &ChannelCli {
+        &self.channel_cli
     }
 
     /// Merges the different configuration layers into a single `MergedConfig`.
     pub fn merge(&self) -> MergedConfig {
+        let channel = &self.channel.with_params(&self.channel_params());
         // CLI-only fields
         let config_file = self.global_cli.config_file.clone();
         let working_directory = self.global_cli.workdir.clone();
         let autocomplete_prompt = self.channel_cli.autocomplete_prompt.clone();
         let input = self.channel_cli.input.clone();
         let exact_match = self.channel_cli.exact;
+        let regex_match = self.channel_cli.regex;
         let select_1 = self.channel_cli.select_1;
         let take_1 = self.channel_cli.take_1;
         let take_1_fast = self.channel_cli.take_1_fast;
         let inline = self.global_cli.inline;
         let height = self.global_cli.height;
         let width = self.global_cli.width;
+        let listen = self.global_cli.listen.clone();
 
         // base config only fields
         let data_dir = self.base_config.application.data_dir.clone();
@@ -74,6 +146,7 @@ impl ConfigLayers {
         let history_size = self.base_config.application.history_size;
         let frecency_max_entries =
             self.base_config.application.frecency_max_entries;
+        let frecency_config = self.base_config.application.frecency.clone();
         let theme = self.base_config.ui.theme.clone();
         let shell_integration_commands =
             self.base_config.shell_integration.commands.clone();
@@ -81,9 +154,9 @@ impl ConfigLayers {
             self.base_config.shell_integration.fallback_channel.clone();
 
         // channel only fields
-        let channel_description = self.channel.metadata.description.clone();
-        let channel_requirements = self.channel.metadata.requirements.clone();
-        let channel_actions = self.channel.actions.clone();
+        let channel_description = channel.metadata.description.clone();
+        let channel_requirements = channel.metadata.requirements.clone();
+        let channel_actions = channel.actions.clone();
 
         // CLI > base config fields
         let cable_dir = self
@@ -98,17 +171,35 @@ impl ConfigLayers {
             .unwrap_or(self.base_config.application.tick_rate);
 
         // CLI > channel fields
-        let watch = self
-            .channel_cli
-            .watch_interval
-            .unwrap_or(self.channel.watch);
+        let watch = self.channel_cli.watch_interval.unwrap_or(channel.watch);
         // Determine if sorting is disabled: --no-sort CLI flag OR channel config
-        let no_sort = self.channel_cli.no_sort || self.channel.source.no_sort;
+        let no_sort = self.channel_cli.no_sort || channel.source.no_sort;
+        // CLI > channel > base config
+        let matcher_config = MatcherConfig {
+            case_matching: self
+                .channel_cli
+                .case_matching
+                .or(channel.source.case_matching)
+                .unwrap_or(self.base_config.application.case_matching),
+            normalization: self
+                .channel_cli
+                .normalization
+                .or(channel.source.normalization)
+                .unwrap_or(self.base_config.application.normalization),
+            match_paths: self.channel_cli.match_paths
+                || channel.source.match_paths,
+            tiebreak: self
+                .channel_cli
+                .tiebreak
+                .as_ref()
+                .unwrap_or(&channel.source.tiebreak)
+                .clone(),
+        };
         let channel_name = self
             .channel_cli
             .channel
             .as_ref()
-            .unwrap_or(&self.channel.metadata.name)
+            .unwrap_or(&channel.metadata.name)
             .clone();
 
         // Global shell from base config (channel-specific shell overrides this)
@@ -119,32 +210,85 @@ impl ConfigLayers {
             if let Some(template) = &self.channel_cli.source_command {
                 CommandSpec::from(template.clone())
             } else {
-                self.channel.source.command.clone()
+                channel.source.command.clone()
             };
         if channel_source_command.shell.is_none() {
             channel_source_command.shell = global_shell;
         }
+        // An explicit source command takes precedence over a builtin source,
+        // which otherwise only replaces a `command` that isn't installed
+        let channel_source_walker =
+            if self.channel_cli.source_command.is_some()
+                || !channel.source.command.replaced_by_builtin()
+            {
+                None
+            } else {
+                channel.source.builtin.map(|kind| {
+                    Walker::new(kind, channel.source.walker.clone())
+                })
+            };
 
         let channel_source_entry_delimiter = self
             .channel_cli
             .source_entry_delimiter
-            .or(self.channel.source.entry_delimiter);
-        let channel_source_ansi =
-            self.channel_cli.ansi || self.channel.source.ansi;
+            .or(channel.source.entry_delimiter);
+        let channel_output_delimiter = self
+            .channel_cli
+            .output_delimiter
+            .or(channel.source.output_delimiter);
+        let channel_source_ansi = self.channel_cli.ansi || channel.source.ansi;
         // Per-channel frecency setting (defaults to true, can be disabled per-channel)
-        let channel_frecency = self.channel.source.frecency;
+        let channel_frecency = channel.source.frecency;
+        let channel_frecency_key = channel.source.frecency_key;
+        let channel_frecency_scope = channel.source.frecency_scope;
+        let channel_frecency_global_weight = channel
+            .source
+            .frecency_global_weight
+            .unwrap_or(DEFAULT_FRECENCY_GLOBAL_WEIGHT);
+        let channel_resume = channel.resume;
+        // Fields given on the command line take precedence over a display
+        // template defined in the channel
+        let cli_fields = self.channel_cli.nth.is_some()
+            || self.channel_cli.with_nth.is_some();
         let channel_source_display = self
             .channel_cli
             .source_display
             .as_ref()
-            .or(self.channel.source.display.as_ref())
+            .or(channel.source.display.as_ref().filter(|_| !cli_fields))
             .cloned();
         let channel_source_output = self
             .channel_cli
             .source_output
             .as_ref()
-            .or(self.channel.source.output.as_ref())
+            .or(channel.source.output.as_ref())
             .cloned();
+        let channel_source_fields = FieldOptions {
+            field_delimiter: self
+                .channel_cli
+                .field_delimiter
+                .clone()
+                .or_else(|| channel.source.fields.field_delimiter.clone()),
+            nth: self
+                .channel_cli
+                .nth
+                .clone()
+                .or_else(|| channel.source.fields.nth.clone()),
+            with_nth: self
+                .channel_cli
+                .with_nth
+                .clone()
+                .or_else(|| channel.source.fields.with_nth.clone()),
+            accept_nth: self
+                .channel_cli
+                .accept_nth
+                .clone()
+                .or_else(|| channel.source.fields.accept_nth.clone()),
+        };
+        let channel_source_reload_on_query = (self
+            .channel_cli
+            .reload_on_query
+            || channel.source.reload_on_query)
+            .then(|| Duration::from_millis(channel.source.reload_debounce));
 
         // Build preview command and apply global shell if no channel-specific shell
         let mut channel_preview_command = self
@@ -152,22 +296,54 @@ impl ConfigLayers {
             .preview_command
             .as_ref()
             .map(|t| CommandSpec::from(t.clone()))
-            .or(self.channel.preview.as_ref().map(|p| p.command.clone()));
+            .or(channel.preview.as_ref().map(|p| p.command.clone()));
         if let Some(ref mut cmd) = channel_preview_command
             && cmd.shell.is_none()
         {
             cmd.shell = global_shell;
         }
+        // An explicit preview command takes precedence over a builtin preview,
+        // which otherwise only replaces a `command` that isn't installed
+        let channel_preview_builtin =
+            if self.channel_cli.preview_command.is_some() {
+                None
+            } else {
+                channel
+                    .preview
+                    .as_ref()
+                    .filter(|preview| preview.command.replaced_by_builtin())
+                    .and_then(|preview| {
+                        preview.builtin.map(|kind| {
+                            BuiltinPreviewer::new(kind, preview.path.clone())
+                        })
+                    })
+            };
+        // Splitting only makes sense when there are several commands to show
+        let channel_preview_split = channel
+            .preview
+            .as_ref()
+            .and_then(|preview| preview.split)
+            .filter(|_| {
+                channel_preview_builtin.is_none()
+                    && channel_preview_command
+                        .as_ref()
+                        .is_some_and(|command| command.inner.len() > 1)
+            });
         let channel_preview_offset =
             self.channel_cli.preview_offset.clone().or(
-                if let Some(preview) = &self.channel.preview {
+                if let Some(preview) = &channel.preview {
                     preview.offset.clone()
                 } else {
                     None
                 },
             );
         let channel_preview_cached = self.channel_cli.cache_preview
-            || self.channel.preview.as_ref().is_some_and(|p| p.cached);
+            || channel.preview.as_ref().is_some_and(|p| p.cached);
+        let channel_preview_separator = self
+            .channel
+            .preview
+            .as_ref()
+            .map_or_else(default_separator, |p| p.separator.clone());
 
         // Channel > base config fields
         let remote_show_channel_descriptions = self
@@ -226,7 +402,7 @@ impl ConfigLayers {
                 |sb| sb.separator_close.clone(),
             );
         let input_bar_position = self.channel_cli.input_position.unwrap_or(
-            self.channel
+            channel
                 .ui
                 .as_ref()
                 .and_then(|ui| ui.input_bar.as_ref())
@@ -237,14 +413,14 @@ impl ConfigLayers {
 
         // CLI > channel > base config fields
         let ui_scale = self.channel_cli.ui_scale.unwrap_or(
-            self.channel
+            channel
                 .ui
                 .as_ref()
                 .and_then(|ui| ui.ui_scale)
                 .unwrap_or(self.base_config.ui.ui_scale),
         );
         let layout = self.channel_cli.layout.unwrap_or(
-            self.channel
+            channel
                 .ui
                 .as_ref()
                 .and_then(|ui| ui.orientation)
@@ -255,7 +431,7 @@ impl ConfigLayers {
             .input_header
             .clone()
             .or_else(|| {
-                self.channel.ui.as_ref()?.input_bar.as_ref()?.header.clone()
+                channel.ui.as_ref()?.input_bar.as_ref()?.header.clone()
             })
             .or_else(|| self.base_config.ui.input_bar.header.clone());
         let input_bar_prompt = self
@@ -263,22 +439,20 @@ impl ConfigLayers {
             .input_prompt
             .clone()
             .or_else(|| {
-                self.channel.ui.as_ref()?.input_bar.as_ref()?.prompt.clone()
+                channel.ui.as_ref()?.input_bar.as_ref()?.prompt.clone()
             })
             .or_else(|| self.base_config.ui.input_bar.prompt.clone());
         let input_bar_border_type = self
             .channel_cli
             .input_border
             .or_else(|| {
-                Some(self.channel.ui.as_ref()?.input_bar.as_ref()?.border_type)
+                Some(channel.ui.as_ref()?.input_bar.as_ref()?.border_type)
             })
             .unwrap_or(self.base_config.ui.input_bar.border_type);
         let input_bar_padding = self
             .channel_cli
             .input_padding
-            .or_else(|| {
-                Some(self.channel.ui.as_ref()?.input_bar.as_ref()?.padding)
-            })
+            .or_else(|| Some(channel.ui.as_ref()?.input_bar.as_ref()?.padding))
             .unwrap_or(self.base_config.ui.input_bar.padding);
         let status_bar_disabled = self.global_cli.no_status_bar;
         let status_bar_hidden = if status_bar_disabled {
@@ -299,21 +473,14 @@ impl ConfigLayers {
             .channel_cli
             .results_border
             .or_else(|| {
-                Some(
-                    self.channel
-                        .ui
-                        .as_ref()?
-                        .results_panel
-                        .as_ref()?
-                        .border_type,
-                )
+                Some(channel.ui.as_ref()?.results_panel.as_ref()?.border_type)
             })
             .unwrap_or(self.base_config.ui.results_panel.border_type);
         let results_panel_padding = self
             .channel_cli
             .results_padding
             .or_else(|| {
-                Some(self.channel.ui.as_ref()?.results_panel.as_ref()?.padding)
+                Some(channel.ui.as_ref()?.results_panel.as_ref()?.padding)
             })
             .unwrap_or(self.base_config.ui.results_panel.padding);
         let results_panel_header = self
@@ -323,6 +490,14 @@ impl ConfigLayers {
             .and_then(|ui| ui.results_panel.as_ref())
             .and_then(|rp| rp.header.clone())
             .or_else(|| self.base_config.ui.results_panel.header.clone());
+        let results_panel_show_selection_index = self
+            .channel
+            .ui
+            .as_ref()
+            .and_then(|ui| ui.results_panel.as_ref())
+            .and_then(|rp| rp.show_selection_index)
+            .or(self.base_config.ui.results_panel.show_selection_index)
+            .unwrap_or(false);
         let merge_input_and_results = self
             .channel
             .ui
@@ -333,7 +508,7 @@ impl ConfigLayers {
             .channel_cli
             .preview_size
             .or_else(|| {
-                Some(self.channel.ui.as_ref()?.preview_panel.as_ref()?.size)
+                Some(channel.ui.as_ref()?.preview_panel.as_ref()?.size)
             })
             .unwrap_or(self.base_config.ui.preview_panel.size);
         let preview_panel_header = self
@@ -342,7 +517,7 @@ impl ConfigLayers {
             .clone()
             .or_else(|| {
                 Some(
-                    self.channel
+                    channel
                         .ui
                         .as_ref()?
                         .preview_panel
@@ -358,13 +533,7 @@ impl ConfigLayers {
             .preview_footer
             .clone()
             .or_else(|| {
-                self.channel
-                    .ui
-                    .as_ref()?
-                    .preview_panel
-                    .as_ref()?
-                    .footer
-                    .clone()
+                channel.ui.as_ref()?.preview_panel.as_ref()?.footer.clone()
             })
             .or_else(|| self.base_config.ui.preview_panel.footer.clone());
         let preview_panel_scrollbar = !self.channel_cli.hide_preview_scrollbar
@@ -380,21 +549,14 @@ impl ConfigLayers {
             .channel_cli
             .preview_border
             .or_else(|| {
-                Some(
-                    self.channel
-                        .ui
-                        .as_ref()?
-                        .preview_panel
-                        .as_ref()?
-                        .border_type,
-                )
+                Some(channel.ui.as_ref()?.preview_panel.as_ref()?.border_type)
             })
             .unwrap_or(self.base_config.ui.preview_panel.border_type);
         let preview_panel_padding = self
             .channel_cli
             .preview_padding
             .or_else(|| {
-                Some(self.channel.ui.as_ref()?.preview_panel.as_ref()?.padding)
+                Some(channel.ui.as_ref()?.preview_panel.as_ref()?.padding)
             })
             .unwrap_or(self.base_config.ui.preview_panel.padding);
 
@@ -402,7 +564,7 @@ impl ConfigLayers {
         let preview_panel_word_wrap = if self.channel_cli.preview_word_wrap {
             true
         // Otherwise, we check the channel UI config
-        } else if let Some(ui) = self.channel.ui.as_ref()
+        } else if let Some(ui) = channel.ui.as_ref()
             && let Some(panel) = ui.preview_panel.as_ref()
         {
             panel.word_wrap
@@ -440,7 +602,7 @@ impl ConfigLayers {
                 || self.base_config.ui.help_panel.hidden
         };
         let help_panel_disabled = self.global_cli.no_help_panel
-            || self.channel.ui.as_ref().is_some_and(|ui| {
+            || channel.ui.as_ref().is_some_and(|ui| {
                 ui.help_panel.as_ref().is_some_and(|hp| hp.disabled)
             })
             || self.base_config.ui.help_panel.disabled;
@@ -452,12 +614,12 @@ impl ConfigLayers {
                 .and_then(|ui| ui.remote_control.as_ref())
                 .is_some_and(|rc| rc.disabled);
         let global_history = self.global_cli.global_history
-            || self.channel.history.global_mode.unwrap_or_default()
+            || channel.history.global_mode.unwrap_or_default()
             || self.base_config.application.global_history;
 
         // Do we have any channel-specific keybindings?
         let mut channel_keybindings = Keybindings::default();
-        if let Some(channel_bindings) = &self.channel.keybindings {
+        if let Some(channel_bindings) = &channel.keybindings {
             channel_keybindings = channel_bindings.bindings.clone();
         }
         if let Some(cli_bindings) = &self.channel_cli.keybindings {
@@ -466,8 +628,8 @@ impl ConfigLayers {
         }
 
         // Validate that all external actions referenced in channel keybindings exist
-        if let Some(channel_bindings) = &self.channel.keybindings {
-            for (_, actions) in channel_bindings.bindings.iter() {
+        if let Some(channel_bindings) = &channel.keybindings {
+            for actions in channel_bindings.bindings.all_actions() {
                 for action in actions.as_slice() {
                     if let Action::ExternalAction(custom_with_prefix) = action
                         && !channel_actions.contains_key(
@@ -485,10 +647,16 @@ impl ConfigLayers {
             }
         }
 
-        let input_map = InputMap::new(
+        let mut input_map = InputMap::new(
             self.base_config.keybindings.clone(),
             channel_keybindings,
         );
+        if self.base_config.modal.enabled {
+            input_map = input_map.with_modes(
+                self.base_config.modal.normal.clone(),
+                self.base_config.modal.insert.clone(),
+            );
+        }
 
         MergedConfig {
             // General
@@ -500,11 +668,15 @@ impl ConfigLayers {
             history_size,
             global_history,
             frecency_max_entries,
+            frecency_config,
             working_directory,
             autocomplete_prompt,
+            listen,
             shell: global_shell,
             // matcher configuration
             exact_match,
+            regex_match,
+            matcher_config,
             select_1,
             take_1,
             take_1_fast,
@@ -536,6 +708,7 @@ impl ConfigLayers {
             results_panel_border_type,
             results_panel_padding,
             results_panel_header,
+            results_panel_show_selection_index,
             // fork-specific
             merge_input_and_results,
             // preview panel
@@ -572,20 +745,32 @@ impl ConfigLayers {
             channel_requirements,
             // source
             channel_source_command,
+            channel_source_walker,
             channel_source_entry_delimiter,
+            channel_output_delimiter,
             channel_source_ansi,
             channel_source_display,
             channel_source_output,
+            channel_source_fields,
+            channel_source_reload_on_query,
             // preview
             channel_preview_command,
+            channel_preview_builtin,
             channel_preview_offset,
             channel_preview_cached,
+            channel_preview_separator,
+            channel_preview_split,
             // actions
             channel_actions,
             // frecency
             channel_frecency,
+            channel_frecency_key,
+            channel_frecency_scope,
+            channel_frecency_global_weight,
+            // session
+            channel_resume,
             // stdin
-            is_stdin: self.channel.metadata.name == "stdin",
+            is_stdin: channel.metadata.name == "stdin",
         }
     }
 }
@@ -604,13 +789,18 @@ pub struct MergedConfig {
     pub history_size: usize,
     pub global_history: bool,
     pub frecency_max_entries: usize,
+    pub frecency_config: FrecencyConfig,
     pub working_directory: Option<PathBuf>,
     pub autocomplete_prompt: Option<String>,
+    /// The Unix socket the control server listens on.
+    pub listen: Option<PathBuf>,
     /// Global shell for command execution (from base config).
     /// Already applied to `channel_source_command` and `channel_preview_command`.
     pub shell: Option<Shell>,
     // matcher configuration
     pub exact_match: bool,
+    pub regex_match: bool,
+    pub matcher_config: MatcherConfig,
     pub select_1: bool,
     pub take_1: bool,
     pub take_1_fast: bool,
@@ -642,6 +832,7 @@ pub struct MergedConfig {
     pub results_panel_border_type: BorderType,
     pub results_panel_padding: Padding,
     pub results_panel_header: Option<String>,
+    pub results_panel_show_selection_index: bool,
     // fork-specific
     pub merge_input_and_results: bool,
     // preview panel
@@ -677,17 +868,47 @@ pub struct MergedConfig {
     pub channel_requirements: Vec<BinaryRequirement>,
     // source
     pub channel_source_command: CommandSpec,
+    pub channel_source_walker: Option<Walker>,
     pub channel_source_entry_delimiter: Option<char>,
+    pub channel_output_delimiter: Option<char>,
     pub channel_source_ansi: bool,
     pub channel_source_display: Option<Template>,
     pub channel_source_output: Option<Template>,
+    pub channel_source_fields: FieldOptions,
+    /// The debounce delay of sources that reload on query changes.
+    pub channel_source_reload_on_query: Option<Duration>,
     // preview
     pub channel_preview_command: Option<CommandSpec>,
+    pub channel_preview_builtin: Option<BuiltinPreviewer>,
     pub channel_preview_offset: Option<Template>,
     pub channel_preview_cached: bool,
+    /// Joins the selected entries substituted for `{+}` in preview commands.
+    pub channel_preview_separator: String,
+    /// Shows every preview command at once, one pane each, when set.
+    pub channel_preview_split: Option<PreviewSplit>,
     pub channel_actions: FxHashMap<String, ActionSpec>,
     /// Whether frecency is enabled for the current channel (per-channel override)
     pub channel_frecency: bool,
+    /// What identifies the entries of the current channel in its frecency
+    /// records
+    pub channel_frecency_key: FrecencyKey,
+    /// Whether the entries of the current channel are ranked per project
+    pub channel_frecency_scope: FrecencyScope,
+    /// How much the records of all projects count when ranking per project
+    pub channel_frecency_global_weight: f64,
+    /// Whether the session state of the current channel is restored
+    pub channel_resume: bool,
     /// Whether the current channel reads from stdin directly
     pub is_stdin: bool,
 }
+
+impl MergedConfig {
+    /// The number of preview panes: one per preview command when the preview
+    /// is split, a single one otherwise.
+    pub fn preview_pane_count(&self) -> usize {
+        match (&self.channel_preview_split, &self.channel_preview_command) {
+            (Some(_), Some(command)) => command.inner.len(),
+            _ => 1,
+        }
+    }
+}
@@ -1,6 +1,10 @@
 use crate::{
-    cable::CABLE_DIR_NAME, channels::prototypes::DEFAULT_PROTOTYPE_NAME,
-    history::DEFAULT_HISTORY_SIZE, utils::shell::Shell,
+    cable::CABLE_DIR_NAME,
+    channels::prototypes::DEFAULT_PROTOTYPE_NAME,
+    frecency::FrecencyConfig,
+    history::DEFAULT_HISTORY_SIZE,
+    matcher::config::{CaseMatching, Normalization},
+    utils::shell::Shell,
 };
 use anyhow::{Context, Result};
 use directories::ProjectDirs;
@@ -13,7 +17,7 @@ use std::{
 };
 use tracing::{debug, warn};
 
-pub use keybindings::{Keybindings, merge_keybindings};
+pub use keybindings::{Keybindings, ModalKeybindings, merge_keybindings};
 pub use themes::Theme;
 pub use ui::UiConfig;
 
@@ -48,11 +52,22 @@ pub struct AppConfig {
     /// Maximum number of frecency entries to keep per channel
     #[serde(default = "default_frecency_max_entries")]
     pub frecency_max_entries: usize,
+    /// How frecency scores are computed
+    #[serde(default)]
+    pub frecency: FrecencyConfig,
     /// Global shell used for executing commands.
     /// If not specified, the shell is detected from the environment.
     /// Channel-specific shell settings override this.
     #[serde(default)]
     pub shell: Option<Shell>,
+    /// How the case of the query is matched against entries.
+    /// Channel-specific settings override this.
+    #[serde(default)]
+    pub case_matching: CaseMatching,
+    /// Whether diacritics are ignored when matching entries.
+    /// Channel-specific settings override this.
+    #[serde(default)]
+    pub normalization: Normalization,
 }
 
 impl Default for AppConfig {
@@ -65,7 +80,10 @@ impl Default for AppConfig {
             history_size: default_history_size(),
             global_history: default_global_history(),
             frecency_max_entries: default_frecency_max_entries(),
+            frecency: FrecencyConfig::default(),
             shell: None,
+            case_matching: CaseMatching::default(),
+            normalization: Normalization::default(),
         }
     }
 }
@@ -95,7 +113,10 @@ impl Hash for AppConfig {
         self.history_size.hash(state);
         self.global_history.hash(state);
         self.frecency_max_entries.hash(state);
+        self.frecency.hash(state);
         self.shell.hash(state);
+        self.case_matching.hash(state);
+        self.normalization.hash(state);
     }
 }
 
@@ -109,6 +130,9 @@ pub struct Config {
     /// Keybindings configuration
     #[serde(default)]
     pub keybindings: Keybindings,
+    /// Vim-like modal keybindings configuration
+    #[serde(default)]
+    pub modal: ModalKeybindings,
     /// UI configuration
     #[serde(default)]
     pub ui: UiConfig,
@@ -245,10 +269,15 @@ impl Config {
         let keybindings =
             merge_keybindings(default.keybindings.clone(), &new.keybindings);
         new.keybindings = keybindings;
+        new.modal.normal =
+            merge_keybindings(default.modal.normal.clone(), &new.modal.normal);
+        new.modal.insert =
+            merge_keybindings(default.modal.insert.clone(), &new.modal.insert);
 
         Config {
             application: new.application,
             keybindings: new.keybindings,
+            modal: new.modal,
             ui: new.ui,
             shell_integration: new.shell_integration,
         }
@@ -51,6 +51,9 @@ pub struct ResultsPanelConfig {
     pub header: Option<String>,
     pub border_type: BorderType,
     pub padding: Padding,
+    /// Show the position of selected entries in the selection instead of a
+    /// checkmark.
+    pub show_selection_index: Option<bool>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Hash)]
@@ -1,26 +1,34 @@
 use crate::{
     action::Action,
     channels::{
-        action_picker::ActionEntry, entry::Entry, remote_control::CableEntry,
+        action_picker::ActionEntry, entry::Entry,
+        history_picker::HistoryPickerEntry, remote_control::CableEntry,
     },
     config::layers::MergedConfig,
+    event::Key,
+    keymap::InputMode,
     picker::Picker,
     previewer::state::PreviewState,
     screen::{
-        action_picker::draw_action_picker, colors::Colorscheme,
-        help_panel::draw_help_panel, input::draw_input_box, layout::Layout,
+        action_picker::draw_action_picker,
+        colors::Colorscheme,
+        help_panel::draw_help_panel,
+        history_picker::draw_history_picker,
+        input::draw_input_box,
+        layout::{Layout, preview_panes},
         merged_input_results::draw_merged_input_results,
         missing_requirements_popup::draw_missing_requirements_popup,
+        param_prompt::draw_param_prompt,
         preview::draw_preview_content_block,
-        remote_control::draw_remote_control, results::draw_results_list,
+        remote_control::draw_remote_control,
+        results::draw_results_list,
         status_bar,
     },
-    television::{MissingRequirementsPopup, Mode},
-    utils::metadata::AppMetadata,
+    television::{MissingRequirementsPopup, Mode, ParamPrompt},
+    utils::{hashmaps::FxIndexSet, metadata::AppMetadata},
 };
 use anyhow::Result;
 use ratatui::{Frame, layout::Rect};
-use rustc_hash::FxHashSet;
 use std::{hash::Hash, sync::Arc, time::Instant};
 
 #[derive(Debug, Clone, PartialEq)]
@@ -29,26 +37,29 @@ use std::{hash::Hash, sync::Arc, time::Instant};
 /// This struct is passed along to the UI thread as part of the `TvState` struct.
 pub struct ChannelState {
     pub current_channel_name: String,
-    pub selected_entries: FxHashSet<Entry>,
+    pub selected_entries: FxIndexSet<Entry>,
     pub total_count: u32,
     pub running: bool,
     pub current_command: String,
     pub current_source_name: Option<String>,
     pub source_index: usize,
     pub source_count: usize,
+    /// Why the current query is invalid, if it is.
+    pub query_error: Option<String>,
 }
 
 impl ChannelState {
     #[allow(clippy::too_many_arguments)]
     pub fn new(
         current_channel_name: String,
-        selected_entries: FxHashSet<Entry>,
+        selected_entries: FxIndexSet<Entry>,
         total_count: u32,
         running: bool,
         current_command: String,
         current_source_name: Option<String>,
         source_index: usize,
         source_count: usize,
+        query_error: Option<String>,
     ) -> Self {
         Self {
             current_channel_name,
@@ -59,6 +70,7 @@ impl ChannelState {
             current_source_name,
             source_index,
             source_count,
+            query_error,
         }
     }
 }
@@ -75,6 +87,7 @@ impl Hash for ChannelState {
         self.current_source_name.hash(state);
         self.source_index.hash(state);
         self.source_count.hash(state);
+        self.query_error.hash(state);
     }
 }
 
@@ -88,9 +101,17 @@ pub struct TvState {
     pub results_picker: Picker<Entry>,
     pub rc_picker: Picker<CableEntry>,
     pub ap_picker: Picker<ActionEntry>,
+    pub hp_picker: Picker<HistoryPickerEntry>,
     pub channel_state: ChannelState,
-    pub preview_state: PreviewState,
+    /// The state of each preview pane.
+    pub preview_panes: Vec<PreviewState>,
+    pub focused_preview_pane: usize,
+    /// The current mode, if modal keybindings are enabled.
+    pub input_mode: Option<InputMode>,
+    /// Keys pressed so far of a key sequence.
+    pub pending_keys: Vec<Key>,
     pub missing_requirements_popup: Option<MissingRequirementsPopup>,
+    pub param_prompt: Option<ParamPrompt>,
 }
 
 impl TvState {
@@ -101,9 +122,14 @@ impl TvState {
         results_picker: Picker<Entry>,
         rc_picker: Picker<CableEntry>,
         ap_picker: Picker<ActionEntry>,
+        hp_picker: Picker<HistoryPickerEntry>,
         channel_state: ChannelState,
-        preview_state: PreviewState,
+        preview_panes: Vec<PreviewState>,
+        focused_preview_pane: usize,
+        input_mode: Option<InputMode>,
+        pending_keys: Vec<Key>,
         missing_requirements_popup: Option<MissingRequirementsPopup>,
+        param_prompt: Option<ParamPrompt>,
     ) -> Self {
         Self {
             mode,
@@ -111,9 +137,14 @@ impl TvState {
             results_picker,
             rc_picker,
             ap_picker,
+            hp_picker,
             channel_state,
-            preview_state,
+            preview_panes,
+            focused_preview_pane,
+            input_mode,
+            pending_keys,
             missing_requirements_popup,
+            param_prompt,
         }
     }
 }
@@ -212,6 +243,7 @@ pub fn draw(ctx: Ctx, f: &mut Frame<'_>, area: Rect) -> Result<Layout> {
             &ctx.tv_state.results_picker.input,
             &mut ctx.tv_state.results_picker.relative_state.clone(),
             ctx.tv_state.channel_state.running,
+            ctx.tv_state.channel_state.query_error.as_deref(),
             &ctx.tv_state.channel_state.current_channel_name,
             &ctx.tv_state.results_picker.entries,
             &ctx.tv_state.channel_state.selected_entries,
@@ -225,6 +257,7 @@ pub fn draw(ctx: Ctx, f: &mut Frame<'_>, area: Rect) -> Result<Layout> {
             &ctx.config.input_bar_border_type,
             ctx.config.input_bar_prompt.as_ref(),
             &ctx.config.results_panel_padding,
+            ctx.config.results_panel_show_selection_index,
         )?;
     } else {
         draw_results_list(
@@ -238,6 +271,7 @@ pub fn draw(ctx: Ctx, f: &mut Frame<'_>, area: Rect) -> Result<Layout> {
             &ctx.config.results_panel_padding,
             &ctx.config.results_panel_border_type,
             &ctx.config.results_panel_header,
+            ctx.config.results_panel_show_selection_index,
             ctx.tv_state.channel_state.source_index,
             ctx.tv_state.channel_state.source_count,
             ctx.tv_state.channel_state.current_source_name.as_deref(),
@@ -252,6 +286,7 @@ pub fn draw(ctx: Ctx, f: &mut Frame<'_>, area: Rect) -> Result<Layout> {
             &ctx.tv_state.results_picker.input,
             &ctx.tv_state.results_picker.state,
             ctx.tv_state.channel_state.running,
+            ctx.tv_state.channel_state.query_error.as_deref(),
             &ctx.tv_state.channel_state.current_channel_name,
             &ctx.colorscheme,
             ctx.config.input_bar_position,
@@ -273,17 +308,29 @@ pub fn draw(ctx: Ctx, f: &mut Frame<'_>, area: Rect) -> Result<Layout> {
             .config
             .input_map
             .get_key_for_action(&Action::CyclePreviews);
-        draw_preview_content_block(
-            f,
-            preview_rect,
-            ctx.tv_state.preview_state,
-            &ctx.colorscheme,
-            &ctx.config.preview_panel_border_type,
-            &ctx.config.preview_panel_padding,
-            ctx.config.preview_panel_scrollbar,
-            ctx.config.preview_panel_word_wrap,
-            cycle_previews_key,
-        )?;
+        let split = ctx.tv_state.preview_panes.len() > 1;
+        for (index, (pane, mut preview_state)) in
+            preview_panes(preview_rect, &ctx.config)
+                .into_iter()
+                .zip(ctx.tv_state.preview_panes)
+                .enumerate()
+        {
+            // in a split preview, the cycle indicator marks the focused pane
+            if split && index != ctx.tv_state.focused_preview_pane {
+                preview_state.preview.preview_count = 1;
+            }
+            draw_preview_content_block(
+                f,
+                pane,
+                preview_state,
+                &ctx.colorscheme,
+                &ctx.config.preview_panel_border_type,
+                &ctx.config.preview_panel_padding,
+                ctx.config.preview_panel_scrollbar,
+                ctx.config.preview_panel_word_wrap,
+                cycle_previews_key,
+            )?;
+        }
     }
 
     // remote control
@@ -314,10 +361,26 @@ pub fn draw(ctx: Ctx, f: &mut Frame<'_>, area: Rect) -> Result<Layout> {
         )?;
     }
 
+    // history picker
+    if ctx.tv_state.mode == Mode::HistoryPicker {
+        draw_history_picker(
+            f,
+            layout.history_picker.unwrap(),
+            &ctx.tv_state.hp_picker.entries,
+            &mut ctx.tv_state.hp_picker.relative_state.clone(),
+            &mut ctx.tv_state.hp_picker.input.clone(),
+            &ctx.colorscheme,
+        )?;
+    }
+
     if let Some(popup) = &ctx.tv_state.missing_requirements_popup {
         draw_missing_requirements_popup(f, area, popup, &ctx.colorscheme);
     }
 
+    if let Some(prompt) = &ctx.tv_state.param_prompt {
+        draw_param_prompt(f, area, prompt, &ctx.colorscheme);
+    }
+
     // floating help panel (rendered last to appear on top)
     if let Some(help_area) = layout.help_panel {
         draw_help_panel(
@@ -8,11 +8,16 @@ use anyhow::{Context, Result};
 use parking_lot::RwLock;
 use rustc_hash::FxHashMap;
 use serde::{Deserialize, Serialize};
-use std::path::{Path, PathBuf};
+use std::borrow::Cow;
+use std::hash::Hash;
+use std::path::{Component, Path, PathBuf};
 use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
 use tracing::debug;
 
+use crate::channels::prototypes::Template;
+use crate::utils::paths::expand_tilde;
+
 /// A single frecency record for an entry.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FrecencyRecord {
@@ -42,26 +47,231 @@ impl FrecencyRecord {
 
     /// Calculate the frecency score for this record.
     ///
-    /// Uses Mozilla-style time-decay buckets combined with access count.
-    /// Higher scores indicate more relevant items.
-    pub fn score(&self, now: u64) -> u64 {
-        let age_hours = now.saturating_sub(self.last_access) / 3600;
-
-        // Time-decay buckets (Mozilla-style)
-        let recency_weight = match age_hours {
-            0..=4 => 100,    // Last 4 hours
-            5..=24 => 70,    // Last day
-            25..=168 => 50,  // Last week
-            169..=720 => 30, // Last month
-            _ => 10,         // Older
+    /// Combines a recency weight, decaying as configured, with the access
+    /// count. Higher scores indicate more relevant items.
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    pub fn score(&self, now: u64, config: &FrecencyConfig) -> u64 {
+        let age = now.saturating_sub(self.last_access);
+
+        let recency_weight = match config.decay {
+            FrecencyDecay::Buckets => match age / 3600 {
+                0..=4 => 100.0,    // Last 4 hours
+                5..=24 => 70.0,    // Last day
+                25..=168 => 50.0,  // Last week
+                169..=720 => 30.0, // Last month
+                _ => 10.0,         // Older
+            },
+            FrecencyDecay::HalfLife => {
+                let half_life = (config.half_life.max(1) * 3600) as f64;
+                100.0 * 0.5_f64.powf(age as f64 / half_life)
+            }
         };
 
         // Cap access count contribution to prevent runaway scores
-        let count_factor = u64::from(self.access_count).min(20);
+        let count_factor =
+            f64::from(self.access_count.min(20)).powf(config.count_weight);
+
+        // Max score with the default weight: 100 * 20 = 2000
+        (recency_weight * count_factor).round() as u64
+    }
+}
+
+/// How the weight of a record decays as time goes by since its last access.
+#[derive(
+    Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq, Hash,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum FrecencyDecay {
+    /// Mozilla-style buckets: last 4 hours, day, week, month and older.
+    #[default]
+    Buckets,
+    /// The weight halves every `half_life` hours.
+    HalfLife,
+}
+
+/// How frecency scores are computed, the `[frecency]` section of the config.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct FrecencyConfig {
+    pub decay: FrecencyDecay,
+    /// The half-life of the recency weight in hours, used by the
+    /// `half_life` decay.
+    pub half_life: u64,
+    /// The exponent applied to the access count: `0` ranks entries by
+    /// recency only while higher values favor frequently selected ones.
+    pub count_weight: f64,
+    /// Once the access counts of a channel add up to more than this, they
+    /// are all scaled down and entries dropping to zero are forgotten, like
+    /// zoxide's `_ZO_MAXAGE`. `0` disables aging.
+    pub max_age: u32,
+    /// The maximum number of projects to keep per-project records for, the
+    /// least recently used project is forgotten past this. `0` disables the
+    /// limit.
+    pub max_scopes: usize,
+}
+
+impl Default for FrecencyConfig {
+    fn default() -> Self {
+        Self {
+            decay: FrecencyDecay::default(),
+            half_life: 7 * 24,
+            count_weight: 1.0,
+            max_age: 0,
+            max_scopes: 100,
+        }
+    }
+}
+
+impl Hash for FrecencyConfig {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.decay.hash(state);
+        self.half_life.hash(state);
+        self.count_weight.to_bits().hash(state);
+        self.max_age.hash(state);
+        self.max_scopes.hash(state);
+    }
+}
+
+/// What identifies the entries of a channel in its frecency records.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum FrecencyKey {
+    /// The entry as produced by the source command.
+    #[default]
+    Raw,
+    /// The result of the channel's `output` template.
+    Output,
+    /// The `output` of the entry as an absolute path, so that a file keeps
+    /// its ranking whatever directory tv is launched from.
+    Path,
+}
+
+/// Whether the entries of a channel are ranked per project.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum FrecencyScope {
+    /// The same ranking wherever tv is launched.
+    #[default]
+    Global,
+    /// A ranking per working directory.
+    Cwd,
+    /// A ranking per git repository, or per working directory outside of
+    /// repositories.
+    Git,
+}
+
+impl FrecencyScope {
+    /// Get the directory whose records rank entries when tv is launched
+    /// from `cwd`, if any.
+    pub fn resolve(self, cwd: &Path) -> Option<String> {
+        let dir = match self {
+            FrecencyScope::Global => return None,
+            FrecencyScope::Cwd => cwd,
+            FrecencyScope::Git => cwd
+                .ancestors()
+                .find(|dir| dir.join(".git").exists())
+                .unwrap_or(cwd),
+        };
+        Some(dir.to_string_lossy().into_owned())
+    }
+}
+
+/// The weight of the records of all projects when ranking the entries of a
+/// channel per project.
+pub const DEFAULT_FRECENCY_GLOBAL_WEIGHT: f64 = 0.5;
+
+/// How the entries of a channel are recorded and ranked.
+#[derive(Debug, Clone)]
+pub struct ChannelFrecency {
+    pub channel_name: String,
+    pub key: FrecencyKey,
+    /// The project directory entries are ranked in, if ranked per project.
+    pub scope: Option<String>,
+    /// How much the records of all projects count next to the ones of
+    /// `scope`.
+    pub global_weight: f64,
+}
+
+impl ChannelFrecency {
+    pub fn new(channel_name: String) -> Self {
+        Self {
+            channel_name,
+            key: FrecencyKey::default(),
+            scope: None,
+            global_weight: DEFAULT_FRECENCY_GLOBAL_WEIGHT,
+        }
+    }
+}
+
+/// Computes the frecency keys of the entries of a channel.
+#[derive(Debug, Clone, Default)]
+pub struct FrecencyKeyer {
+    key: FrecencyKey,
+    output: Option<Template>,
+    /// The directory relative paths are resolved against.
+    cwd: PathBuf,
+}
+
+impl FrecencyKeyer {
+    pub fn new(key: FrecencyKey, output: Option<Template>) -> Self {
+        Self {
+            key,
+            output,
+            cwd: std::env::current_dir().unwrap_or_default(),
+        }
+    }
+
+    /// Get the frecency key of a raw entry.
+    pub fn key<'a>(&self, raw: &'a str) -> Cow<'a, str> {
+        match self.key {
+            FrecencyKey::Raw => Cow::Borrowed(raw),
+            FrecencyKey::Output => self.output(raw),
+            FrecencyKey::Path => Cow::Owned(
+                normalize_path(&self.cwd, &self.output(raw))
+                    .to_string_lossy()
+                    .into_owned(),
+            ),
+        }
+    }
+
+    fn output<'a>(&self, raw: &'a str) -> Cow<'a, str> {
+        self.output
+            .as_ref()
+            .and_then(|template| template.format(raw).ok())
+            .map_or(Cow::Borrowed(raw), Cow::Owned)
+    }
+}
 
-        // Max score: 100 * 20 = 2000
-        recency_weight * count_factor
+/// Make a path absolute and resolve its `.` and `..` components and
+/// symlinks.
+///
+/// Paths that can't be resolved, e.g. because they don't exist, are only
+/// made absolute and rid of their `.` and `..` components, without touching
+/// the filesystem.
+pub fn normalize_path(cwd: &Path, path: &str) -> PathBuf {
+    let path = cwd.join(expand_tilde(path));
+    if let Ok(canonical) = std::fs::canonicalize(&path) {
+        return canonical;
+    }
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
     }
+    normalized
 }
 
 /// Serializable frecency data structure.
@@ -69,6 +279,14 @@ impl FrecencyRecord {
 pub struct FrecencyData {
     /// Per-channel frecency records, keyed by channel name then entry raw string.
     pub channels: FxHashMap<String, FxHashMap<String, FrecencyRecord>>,
+    /// Records of the channels ranked per project, keyed by project
+    /// directory then like `channels`. Entries are recorded in `channels`
+    /// as well.
+    #[serde(default, skip_serializing_if = "FxHashMap::is_empty")]
+    pub scopes: FxHashMap<
+        String,
+        FxHashMap<String, FxHashMap<String, FrecencyRecord>>,
+    >,
 }
 
 /// Thread-safe frecency manager.
@@ -79,6 +297,8 @@ pub struct Frecency {
     file_path: PathBuf,
     /// Maximum number of entries to keep per channel.
     max_entries_per_channel: usize,
+    /// How scores are computed.
+    config: FrecencyConfig,
 }
 
 /// A handle to the frecency manager, shareable across threads.
@@ -107,19 +327,42 @@ impl FrecencyScores {
 /// Thread-safe cache for frecency scores, refreshed periodically.
 pub struct FrecencyCache {
     scores: RwLock<FrecencyScores>,
-    channel_name: String,
+    channel: ChannelFrecency,
+    keyer: Arc<FrecencyKeyer>,
 }
 
 impl FrecencyCache {
-    pub fn new(channel_name: String) -> Self {
+    pub fn new(channel: ChannelFrecency, keyer: FrecencyKeyer) -> Self {
         Self {
             scores: RwLock::new(FrecencyScores::default()),
-            channel_name,
+            channel,
+            keyer: Arc::new(keyer),
         }
     }
 
+    pub fn keyer(&self) -> &Arc<FrecencyKeyer> {
+        &self.keyer
+    }
+
+    /// What identifies entries in the frecency records.
+    pub fn key(&self) -> FrecencyKey {
+        self.channel.key
+    }
+
+    /// The project directory entries are ranked in, if ranked per project.
+    pub fn scope(&self) -> Option<&str> {
+        self.channel.scope.as_deref()
+    }
+
     pub fn refresh(&self, frecency: &Frecency) {
-        *self.scores.write() = frecency.get_channel_scores(&self.channel_name);
+        *self.scores.write() = match &self.channel.scope {
+            Some(scope) => frecency.get_scoped_scores(
+                &self.channel.channel_name,
+                scope,
+                self.channel.global_weight,
+            ),
+            None => frecency.get_channel_scores(&self.channel.channel_name),
+        };
     }
 
     #[inline]
@@ -142,9 +385,17 @@ impl Frecency {
             data: RwLock::new(FrecencyData::default()),
             file_path: data_dir.join(FRECENCY_FILE_NAME),
             max_entries_per_channel,
+            config: FrecencyConfig::default(),
         }
     }
 
+    /// Use the given scoring configuration instead of the default one.
+    #[must_use]
+    pub fn with_config(mut self, config: FrecencyConfig) -> Self {
+        self.config = config;
+        self
+    }
+
     /// Initialize the frecency manager by loading data from disk.
     pub fn init(&self) -> Result<()> {
         self.load_from_file()
@@ -200,7 +451,41 @@ impl Frecency {
         let mut data = self.data.write();
         let channel_entries =
             data.channels.entry(channel_name.to_string()).or_default();
+        self.record_entry_access(channel_entries, channel_name, raw);
+    }
+
+    /// Record an access for an entry of a channel ranked per project, in
+    /// the records of the project directory `scope`.
+    ///
+    /// This doesn't record the access in the records of all projects, see
+    /// [`Frecency::record_access`].
+    pub fn record_scoped_access(
+        &self,
+        scope: &str,
+        channel_name: &str,
+        raw: &str,
+    ) {
+        let mut data = self.data.write();
+        let channel_entries = data
+            .scopes
+            .entry(scope.to_string())
+            .or_default()
+            .entry(channel_name.to_string())
+            .or_default();
+        self.record_entry_access(channel_entries, channel_name, raw);
+        if self.config.max_scopes > 0
+            && data.scopes.len() > self.config.max_scopes
+        {
+            evict_least_recent_scope(&mut data.scopes, scope);
+        }
+    }
 
+    fn record_entry_access(
+        &self,
+        channel_entries: &mut FxHashMap<String, FrecencyRecord>,
+        channel_name: &str,
+        raw: &str,
+    ) {
         if let Some(record) = channel_entries.get_mut(raw) {
             record.record_access();
             debug!(
@@ -220,6 +505,10 @@ impl Frecency {
                 self.prune_channel_entries(channel_entries);
             }
         }
+
+        if self.config.max_age > 0 {
+            age_channel_entries(channel_entries, self.config.max_age);
+        }
     }
 
     /// Get the frecency score for an entry, if it exists.
@@ -241,7 +530,7 @@ impl Frecency {
         data.channels
             .get(channel_name)
             .and_then(|entries| entries.get(raw))
-            .map(|record| record.score(now))
+            .map(|record| record.score(now, &self.config))
     }
 
     pub fn get_channel_scores(&self, channel_name: &str) -> FrecencyScores {
@@ -253,7 +542,9 @@ impl Frecency {
             .map(|entries| {
                 entries
                     .iter()
-                    .map(|(key, record)| (key.clone(), record.score(now)))
+                    .map(|(key, record)| {
+                        (key.clone(), record.score(now, &self.config))
+                    })
                     .collect()
             })
             .unwrap_or_default();
@@ -262,12 +553,199 @@ impl Frecency {
         }
     }
 
-    pub fn create_cache(&self, channel_name: String) -> FrecencyCacheHandle {
-        let cache = Arc::new(FrecencyCache::new(channel_name));
+    /// Get the scores of a channel ranked in a project: the scores of the
+    /// entries selected in the project plus the scores of all the entries
+    /// scaled by `global_weight`.
+    pub fn get_scoped_scores(
+        &self,
+        channel_name: &str,
+        scope: &str,
+        global_weight: f64,
+    ) -> FrecencyScores {
+        let now = current_timestamp();
+        let data = self.data.read();
+        let mut scores: FxHashMap<String, u64> = FxHashMap::default();
+        if let Some(entries) = data.channels.get(channel_name) {
+            for (key, record) in entries {
+                #[allow(
+                    clippy::cast_precision_loss,
+                    clippy::cast_possible_truncation,
+                    clippy::cast_sign_loss
+                )]
+                let score = (record.score(now, &self.config) as f64
+                    * global_weight)
+                    .round() as u64;
+                scores.insert(key.clone(), score);
+            }
+        }
+        if let Some(entries) = data
+            .scopes
+            .get(scope)
+            .and_then(|channels| channels.get(channel_name))
+        {
+            for (key, record) in entries {
+                *scores.entry(key.clone()).or_default() +=
+                    record.score(now, &self.config);
+            }
+        }
+        FrecencyScores {
+            scores: Arc::new(scores),
+        }
+    }
+
+    pub fn create_cache(
+        &self,
+        channel: ChannelFrecency,
+        keyer: FrecencyKeyer,
+    ) -> FrecencyCacheHandle {
+        let cache = Arc::new(FrecencyCache::new(channel, keyer));
         cache.refresh(self);
         cache
     }
 
+    /// Get the records of a channel along with their score, from the
+    /// highest score.
+    pub fn scored_records(
+        &self,
+        channel_name: &str,
+    ) -> Vec<(FrecencyRecord, u64)> {
+        let now = current_timestamp();
+        let data = self.data.read();
+        let mut records: Vec<_> = data
+            .channels
+            .get(channel_name)
+            .map(|entries| {
+                entries
+                    .values()
+                    .map(|record| {
+                        (record.clone(), record.score(now, &self.config))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        records.sort_by(|(r1, s1), (r2, s2)| {
+            s2.cmp(s1).then_with(|| r1.raw.cmp(&r2.raw))
+        });
+        records
+    }
+
+    /// Forget an entry of a channel, returning whether it had a record.
+    pub fn remove(&self, channel_name: &str, raw: &str) -> bool {
+        let mut data = self.data.write();
+        let mut removed = data
+            .channels
+            .get_mut(channel_name)
+            .is_some_and(|entries| entries.remove(raw).is_some());
+        for channels in data.scopes.values_mut() {
+            removed |= channels
+                .get_mut(channel_name)
+                .is_some_and(|entries| entries.remove(raw).is_some());
+        }
+        if removed {
+            debug!(
+                "Removed frecency record for '{}' in channel '{}'",
+                raw, channel_name
+            );
+        }
+        removed
+    }
+
+    /// Forget all the entries of a channel, returning how many there were.
+    ///
+    /// The records of the channel in every project are forgotten as well.
+    pub fn reset(&self, channel_name: &str) -> usize {
+        let mut data = self.data.write();
+        for channels in data.scopes.values_mut() {
+            channels.remove(channel_name);
+        }
+        data.scopes.retain(|_, channels| !channels.is_empty());
+        data.channels
+            .remove(channel_name)
+            .map_or(0, |entries| entries.len())
+    }
+
+    /// Merge records into a channel, adding up the access counts of the
+    /// entries already recorded and keeping their latest access.
+    ///
+    /// Returns the number of records imported.
+    pub fn import(
+        &self,
+        channel_name: &str,
+        records: Vec<FrecencyRecord>,
+    ) -> usize {
+        let mut data = self.data.write();
+        let channel_entries =
+            data.channels.entry(channel_name.to_string()).or_default();
+        let count = records.len();
+        for record in records {
+            if let Some(existing) = channel_entries.get_mut(&record.raw) {
+                existing.access_count =
+                    existing.access_count.saturating_add(record.access_count);
+                existing.last_access =
+                    existing.last_access.max(record.last_access);
+            } else {
+                channel_entries.insert(record.raw.clone(), record);
+            }
+        }
+        if channel_entries.len() > self.max_entries_per_channel {
+            self.prune_channel_entries(channel_entries);
+        }
+        if self.config.max_age > 0 {
+            age_channel_entries(channel_entries, self.config.max_age);
+        }
+        count
+    }
+
+    /// Forget the entries that are absolute paths to files that no longer
+    /// exist, in the given channel or in all of them.
+    ///
+    /// Relative paths are kept since the directory they are relative to is
+    /// unknown. When pruning all channels, the records of projects whose
+    /// directory is gone are forgotten altogether.
+    ///
+    /// Returns the number of records forgotten, counting the records of
+    /// projects.
+    pub fn prune_missing(&self, channel_name: Option<&str>) -> usize {
+        fn prune(entries: &mut FxHashMap<String, FrecencyRecord>) -> usize {
+            let len = entries.len();
+            entries.retain(|raw, _| {
+                let path = expand_tilde(raw);
+                !path.is_absolute() || path.exists()
+            });
+            len - entries.len()
+        }
+
+        let mut data = self.data.write();
+        let mut pruned = 0;
+        for (name, entries) in &mut data.channels {
+            if channel_name.is_none_or(|channel| channel == name) {
+                pruned += prune(entries);
+            }
+        }
+        data.channels.retain(|_, entries| !entries.is_empty());
+        if channel_name.is_none() {
+            data.scopes.retain(|scope, channels| {
+                let exists = Path::new(scope).exists();
+                if !exists {
+                    pruned +=
+                        channels.values().map(FxHashMap::len).sum::<usize>();
+                }
+                exists
+            });
+        }
+        for channels in data.scopes.values_mut() {
+            for (name, entries) in channels.iter_mut() {
+                if channel_name.is_none_or(|channel| channel == name) {
+                    pruned += prune(entries);
+                }
+            }
+            channels.retain(|_, entries| !entries.is_empty());
+        }
+        data.scopes.retain(|_, channels| !channels.is_empty());
+        debug!("Pruned {} missing frecency entries", pruned);
+        pruned
+    }
+
     /// Prune the oldest entries from a channel to stay within limits.
     fn prune_channel_entries(
         &self,
@@ -284,7 +762,9 @@ impl Frecency {
         let now = current_timestamp();
         let mut scores: Vec<_> = entries
             .iter()
-            .map(|(key, record)| (key.clone(), record.score(now)))
+            .map(|(key, record)| {
+                (key.clone(), record.score(now, &self.config))
+            })
             .collect();
 
         // Sort by score ascending (lowest first)
@@ -303,6 +783,191 @@ impl Frecency {
     }
 }
 
+/// Forget the records of the project that was used the least recently,
+/// other than `current`.
+fn evict_least_recent_scope(
+    scopes: &mut FxHashMap<
+        String,
+        FxHashMap<String, FxHashMap<String, FrecencyRecord>>,
+    >,
+    current: &str,
+) {
+    let last_access =
+        |channels: &FxHashMap<_, FxHashMap<_, FrecencyRecord>>| {
+            channels
+                .values()
+                .flat_map(FxHashMap::values)
+                .map(|record| record.last_access)
+                .max()
+                .unwrap_or(0)
+        };
+    let Some(scope) = scopes
+        .iter()
+        .filter(|(scope, _)| *scope != current)
+        .min_by_key(|(_, channels)| last_access(channels))
+        .map(|(scope, _)| scope.clone())
+    else {
+        return;
+    };
+    scopes.remove(&scope);
+    debug!("Forgot the frecency records of {}", scope);
+}
+
+/// Scale the access counts of a channel down once they add up to more than
+/// `max_age`, forgetting the entries dropping to zero.
+///
+/// Like zoxide, counts are scaled so that they add up to 90% of `max_age`.
+fn age_channel_entries(
+    entries: &mut FxHashMap<String, FrecencyRecord>,
+    max_age: u32,
+) {
+    let total: u64 = entries
+        .values()
+        .map(|record| u64::from(record.access_count))
+        .sum();
+    if total <= u64::from(max_age) {
+        return;
+    }
+    for record in entries.values_mut() {
+        let scaled = u64::from(record.access_count) * 9 * u64::from(max_age)
+            / (10 * total);
+        record.access_count = u32::try_from(scaled).unwrap_or(u32::MAX);
+    }
+    entries.retain(|_, record| record.access_count > 0);
+    debug!("Aged frecency entries, {} remaining", entries.len());
+}
+
+/// Version of the zoxide database format that can be imported.
+const ZOXIDE_DB_VERSION: u32 = 3;
+
+/// Convert a zoxide database into frecency records.
+///
+/// Both the binary database (`db.zo` in zoxide's data directory) and the
+/// output of `zoxide query --list --score` are supported. zoxide's rank grows
+/// by one on each access, which maps to an access count.
+pub fn parse_zoxide(contents: &[u8], now: u64) -> Result<Vec<FrecencyRecord>> {
+    if contents.starts_with(&ZOXIDE_DB_VERSION.to_le_bytes()) {
+        return parse_zoxide_db(&contents[4..]);
+    }
+    let contents =
+        std::str::from_utf8(contents).context("Invalid zoxide database")?;
+    contents
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let (rank, path) = line
+                .trim_start()
+                .split_once(' ')
+                .with_context(|| format!("Invalid zoxide entry: {line}"))?;
+            Ok(FrecencyRecord {
+                raw: path.trim_start().to_string(),
+                last_access: now,
+                access_count: rank_to_count(parse_rank(rank)?),
+            })
+        })
+        .collect()
+}
+
+/// Read the entries of a binary zoxide database, which is a bincode encoded
+/// list of `(path, rank, last_accessed)` after the version.
+fn parse_zoxide_db(mut bytes: &[u8]) -> Result<Vec<FrecencyRecord>> {
+    fn take<'a>(bytes: &mut &'a [u8], n: usize) -> Result<&'a [u8]> {
+        if bytes.len() < n {
+            anyhow::bail!("Truncated zoxide database");
+        }
+        let (head, tail) = bytes.split_at(n);
+        *bytes = tail;
+        Ok(head)
+    }
+    fn take_u64(bytes: &mut &[u8]) -> Result<u64> {
+        Ok(u64::from_le_bytes(take(bytes, 8)?.try_into()?))
+    }
+
+    let len = take_u64(&mut bytes)?;
+    let mut records = Vec::new();
+    for _ in 0..len {
+        let path_len = usize::try_from(take_u64(&mut bytes)?)?;
+        let path = std::str::from_utf8(take(&mut bytes, path_len)?)
+            .context("Invalid path in zoxide database")?;
+        let rank = f64::from_le_bytes(take(&mut bytes, 8)?.try_into()?);
+        let last_accessed = take_u64(&mut bytes)?;
+        records.push(FrecencyRecord {
+            raw: path.to_string(),
+            last_access: last_accessed,
+            access_count: rank_to_count(rank),
+        });
+    }
+    Ok(records)
+}
+
+/// Convert a fasd database (`~/.fasd`) into frecency records.
+///
+/// Each line is `path|rank|last_access`, where the rank grows by one on each
+/// access like zoxide's.
+pub fn parse_fasd(contents: &str) -> Result<Vec<FrecencyRecord>> {
+    contents
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let invalid = || format!("Invalid fasd entry: {line}");
+            // paths may contain `|`, so split from the end
+            let mut fields = line.rsplitn(3, '|');
+            let last_access = fields.next().with_context(invalid)?;
+            let rank = fields.next().with_context(invalid)?;
+            let path = fields.next().with_context(invalid)?;
+            Ok(FrecencyRecord {
+                raw: path.to_string(),
+                last_access: last_access
+                    .trim()
+                    .parse()
+                    .with_context(invalid)?,
+                access_count: rank_to_count(parse_rank(rank)?),
+            })
+        })
+        .collect()
+}
+
+/// Convert an autojump database (`autojump.txt` in autojump's data
+/// directory) into frecency records.
+///
+/// Each line is `weight<TAB>path`. autojump starts entries at a weight of 10
+/// and sets it to `sqrt(weight² + 10²)` on each access, so the access count
+/// is `(weight / 10)²`. Access times aren't recorded, entries are imported as
+/// last accessed `now`.
+pub fn parse_autojump(
+    contents: &str,
+    now: u64,
+) -> Result<Vec<FrecencyRecord>> {
+    contents
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let (weight, path) = line
+                .split_once('\t')
+                .with_context(|| format!("Invalid autojump entry: {line}"))?;
+            let weight = parse_rank(weight)?;
+            Ok(FrecencyRecord {
+                raw: path.to_string(),
+                last_access: now,
+                access_count: rank_to_count((weight / 10.0).powi(2)),
+            })
+        })
+        .collect()
+}
+
+fn parse_rank(rank: &str) -> Result<f64> {
+    rank.trim()
+        .parse()
+        .with_context(|| format!("Invalid rank: {rank}"))
+}
+
+/// Round a rank to an access count, keeping at least one access.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn rank_to_count(rank: f64) -> u32 {
+    // `as` saturates, and maps NaN to 0
+    (rank.round() as u32).max(1)
+}
+
 /// Get the current Unix timestamp in seconds.
 pub fn current_timestamp() -> u64 {
     SystemTime::now()
@@ -347,7 +1012,7 @@ mod tests {
         };
 
         // Recent (within 4 hours): 100 * 5 = 500
-        let score = record.score(now);
+        let score = record.score(now, &FrecencyConfig::default());
         assert_eq!(score, 500);
     }
 
@@ -362,7 +1027,7 @@ mod tests {
         };
 
         // Within last week: 50 * 10 = 500
-        let score = record.score(now);
+        let score = record.score(now, &FrecencyConfig::default());
         assert_eq!(score, 500);
     }
 
@@ -377,7 +1042,7 @@ mod tests {
         };
 
         // Very old: 10 * 20 = 200
-        let score = record.score(now);
+        let score = record.score(now, &FrecencyConfig::default());
         assert_eq!(score, 200);
     }
 
@@ -391,7 +1056,7 @@ mod tests {
         };
 
         // Recent with capped count: 100 * 20 = 2000
-        let score = record.score(now);
+        let score = record.score(now, &FrecencyConfig::default());
         assert_eq!(score, 2000);
     }
 
@@ -515,4 +1180,391 @@ mod tests {
         let entries = data.channels.get("files").unwrap();
         assert!(entries.len() <= 3);
     }
+
+    #[test]
+    fn test_frecency_score_config() {
+        let now = current_timestamp();
+        let record = FrecencyRecord {
+            raw: "test".to_string(),
+            last_access: now - 2 * 24 * 3600,
+            access_count: 4,
+        };
+
+        // Two half-lives: 100 / 4 * 4 = 100
+        let half_life = FrecencyConfig {
+            decay: FrecencyDecay::HalfLife,
+            half_life: 24,
+            ..FrecencyConfig::default()
+        };
+        assert_eq!(record.score(now, &half_life), 100);
+
+        // Recency only: 50 * 1 = 50
+        let recency_only = FrecencyConfig {
+            count_weight: 0.0,
+            ..FrecencyConfig::default()
+        };
+        assert_eq!(record.score(now, &recency_only), 50);
+
+        // Favoring counts: 50 * 4^2 = 800
+        let count_heavy = FrecencyConfig {
+            count_weight: 2.0,
+            ..FrecencyConfig::default()
+        };
+        assert_eq!(record.score(now, &count_heavy), 800);
+    }
+
+    #[test]
+    fn test_frecency_aging() {
+        let dir = tempdir().unwrap();
+        let frecency =
+            Frecency::new(100, dir.path()).with_config(FrecencyConfig {
+                max_age: 10,
+                ..FrecencyConfig::default()
+            });
+
+        for _ in 0..8 {
+            frecency.record_access("files", "a");
+        }
+        frecency.record_access("files", "b");
+        frecency.record_access("files", "c");
+        assert_eq!(frecency.scored_records("files").len(), 3);
+
+        // 11 accesses in total, scaled down to 9 with b forgotten
+        frecency.record_access("files", "c");
+        let records = frecency.scored_records("files");
+        assert_eq!(
+            records
+                .iter()
+                .map(|(r, _)| (r.raw.as_str(), r.access_count))
+                .collect::<Vec<_>>(),
+            vec![("a", 6), ("c", 1)]
+        );
+    }
+
+    #[test]
+    fn test_frecency_remove_reset_import() {
+        let dir = tempdir().unwrap();
+        let frecency = Frecency::new(100, dir.path());
+        frecency.record_access("files", "a");
+        frecency.record_access("files", "b");
+        frecency.record_access("files", "b");
+
+        let records = frecency.scored_records("files");
+        assert_eq!(
+            records
+                .iter()
+                .map(|(r, _)| r.raw.as_str())
+                .collect::<Vec<_>>(),
+            vec!["b", "a"]
+        );
+
+        assert!(frecency.remove("files", "a"));
+        assert!(!frecency.remove("files", "a"));
+        assert!(!frecency.remove("dirs", "b"));
+
+        let imported = frecency.import(
+            "files",
+            vec![
+                FrecencyRecord {
+                    raw: "b".to_string(),
+                    last_access: 0,
+                    access_count: 3,
+                },
+                FrecencyRecord {
+                    raw: "c".to_string(),
+                    last_access: 0,
+                    access_count: 1,
+                },
+            ],
+        );
+        assert_eq!(imported, 2);
+        let records = frecency.scored_records("files");
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].0.raw, "b");
+        assert_eq!(records[0].0.access_count, 5);
+        assert!(records[0].0.last_access > 0);
+
+        assert_eq!(frecency.reset("files"), 2);
+        assert!(frecency.scored_records("files").is_empty());
+    }
+
+    #[test]
+    fn test_frecency_prune_missing() {
+        let dir = tempdir().unwrap();
+        let existing = dir.path().join("existing");
+        std::fs::write(&existing, "").unwrap();
+        let missing = dir.path().join("missing");
+        let existing = existing.to_string_lossy();
+        let missing = missing.to_string_lossy();
+
+        let frecency = Frecency::new(100, dir.path());
+        frecency.record_access("files", &existing);
+        frecency.record_access("files", &missing);
+        frecency.record_access("files", "relative/missing");
+        frecency.record_access("dirs", &missing);
+
+        assert_eq!(frecency.prune_missing(Some("files")), 1);
+        assert_eq!(frecency.scored_records("files").len(), 2);
+        assert_eq!(frecency.scored_records("dirs").len(), 1);
+
+        assert_eq!(frecency.prune_missing(None), 1);
+        assert!(frecency.scored_records("dirs").is_empty());
+    }
+
+    #[test]
+    fn test_frecency_prune_missing_scopes() {
+        let dir = tempdir().unwrap();
+        let project = dir.path().to_string_lossy();
+        let gone = dir.path().join("gone");
+        let gone = gone.to_string_lossy();
+        let missing = dir.path().join("missing");
+        let missing = missing.to_string_lossy();
+
+        let frecency = Frecency::new(100, dir.path());
+        frecency.record_access("files", "relative");
+        frecency.record_scoped_access(&project, "files", &missing);
+        frecency.record_scoped_access(&project, "dirs", &missing);
+        frecency.record_scoped_access(&gone, "dirs", "relative");
+
+        // pruning a channel leaves the projects of other channels alone
+        assert_eq!(frecency.prune_missing(Some("files")), 1);
+        assert_eq!(frecency.data.read().scopes.len(), 2);
+        assert!(
+            !frecency.data.read().scopes[project.as_ref()]
+                .contains_key("files")
+        );
+
+        assert_eq!(frecency.prune_missing(None), 2);
+        assert!(frecency.data.read().scopes.is_empty());
+    }
+
+    fn summary(records: &[FrecencyRecord]) -> Vec<(&str, u64, u32)> {
+        records
+            .iter()
+            .map(|r| (r.raw.as_str(), r.last_access, r.access_count))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_zoxide() {
+        let mut db = ZOXIDE_DB_VERSION.to_le_bytes().to_vec();
+        db.extend(2u64.to_le_bytes());
+        for (path, rank, last_accessed) in
+            [("/home/user", 12.4_f64, 100u64), ("/tmp", 0.3, 200)]
+        {
+            db.extend((path.len() as u64).to_le_bytes());
+            db.extend(path.as_bytes());
+            db.extend(rank.to_le_bytes());
+            db.extend(last_accessed.to_le_bytes());
+        }
+        let records = parse_zoxide(&db, 1_000).unwrap();
+        assert_eq!(
+            summary(&records),
+            vec![("/home/user", 100, 12), ("/tmp", 200, 1)]
+        );
+        assert!(parse_zoxide(&db[..db.len() - 1], 1_000).is_err());
+
+        let list = "  12.0 /home/user\n   2.5 /path with spaces\n";
+        let records = parse_zoxide(list.as_bytes(), 1_000).unwrap();
+        assert_eq!(
+            summary(&records),
+            vec![("/home/user", 1_000, 12), ("/path with spaces", 1_000, 3)]
+        );
+    }
+
+    #[test]
+    fn test_parse_fasd_and_autojump() {
+        let fasd = "/home/user|8.5|1700000000\n/odd|path|1|1600000000\n";
+        let records = parse_fasd(fasd).unwrap();
+        assert_eq!(
+            summary(&records),
+            vec![
+                ("/home/user", 1_700_000_000, 9),
+                ("/odd|path", 1_600_000_000, 1)
+            ]
+        );
+        assert!(parse_fasd("/home/user|8.5").is_err());
+
+        let autojump = "10.0\t/home/user\n30.0\t/tmp\n";
+        let records = parse_autojump(autojump, 1_000).unwrap();
+        assert_eq!(
+            summary(&records),
+            vec![("/home/user", 1_000, 1), ("/tmp", 1_000, 9)]
+        );
+    }
+
+    #[test]
+    fn test_normalize_path() {
+        let cwd = Path::new("/home/user/project");
+        assert_eq!(
+            normalize_path(cwd, "src/x.rs"),
+            Path::new("/home/user/project/src/x.rs")
+        );
+        assert_eq!(
+            normalize_path(cwd, "./src/../src/./x.rs"),
+            Path::new("/home/user/project/src/x.rs")
+        );
+        assert_eq!(
+            normalize_path(cwd, "../other"),
+            Path::new("/home/user/other")
+        );
+        assert_eq!(
+            normalize_path(cwd, "/missing/hosts"),
+            Path::new("/missing/hosts")
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_normalize_path_resolves_symlinks() {
+        let dir = tempdir().unwrap();
+        let dir = std::fs::canonicalize(dir.path()).unwrap();
+        std::fs::create_dir(dir.join("real")).unwrap();
+        std::fs::write(dir.join("real/x.rs"), "").unwrap();
+        std::os::unix::fs::symlink(dir.join("real"), dir.join("link"))
+            .unwrap();
+
+        assert_eq!(normalize_path(&dir, "link/x.rs"), dir.join("real/x.rs"));
+        assert_eq!(
+            normalize_path(&dir, "link/../real/./x.rs"),
+            dir.join("real/x.rs")
+        );
+        // missing files keep their symlinks
+        assert_eq!(normalize_path(&dir, "link/y.rs"), dir.join("link/y.rs"));
+    }
+
+    #[test]
+    fn test_frecency_keyer() {
+        let keyer = FrecencyKeyer {
+            key: FrecencyKey::Path,
+            output: None,
+            cwd: PathBuf::from("/project"),
+        };
+        assert_eq!(keyer.key("./src/x.rs"), "/project/src/x.rs");
+        assert_eq!(keyer.key("src/x.rs"), "/project/src/x.rs");
+
+        let output = Some(Template::parse("{split: :1}").unwrap());
+        let keyer = FrecencyKeyer {
+            key: FrecencyKey::Output,
+            output: output.clone(),
+            cwd: PathBuf::from("/project"),
+        };
+        assert_eq!(keyer.key("M src/x.rs"), "src/x.rs");
+        let keyer = FrecencyKeyer {
+            key: FrecencyKey::Path,
+            output,
+            cwd: PathBuf::from("/project"),
+        };
+        assert_eq!(keyer.key("M src/x.rs"), "/project/src/x.rs");
+
+        let keyer = FrecencyKeyer::new(FrecencyKey::Raw, None);
+        assert!(matches!(
+            keyer.key("./src/x.rs"),
+            Cow::Borrowed("./src/x.rs")
+        ));
+
+        let dir = tempdir().unwrap();
+        let frecency = Frecency::new(100, dir.path());
+        frecency.record_access("files", "/project/src/x.rs");
+        let cache = frecency.create_cache(
+            ChannelFrecency::new("files".to_string()),
+            FrecencyKeyer {
+                key: FrecencyKey::Path,
+                output: None,
+                cwd: PathBuf::from("/project"),
+            },
+        );
+        let scores = cache.snapshot();
+        let score = |raw| scores.get(&cache.keyer().key(raw));
+        assert!(score("./src/x.rs").is_some());
+        assert!(score("src/x.rs").is_some());
+        assert!(score("src/y.rs").is_none());
+    }
+
+    #[test]
+    fn test_frecency_scope_resolve() {
+        let dir = tempdir().unwrap();
+        let repo = dir.path().join("repo");
+        let nested = repo.join("src/nested");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::create_dir(repo.join(".git")).unwrap();
+
+        assert_eq!(FrecencyScope::Global.resolve(&nested), None);
+        assert_eq!(
+            FrecencyScope::Cwd.resolve(&nested).as_deref(),
+            nested.to_str()
+        );
+        assert_eq!(
+            FrecencyScope::Git.resolve(&nested).as_deref(),
+            repo.to_str()
+        );
+        assert_eq!(
+            FrecencyScope::Git.resolve(dir.path()).as_deref(),
+            dir.path().to_str()
+        );
+    }
+
+    #[test]
+    fn test_frecency_scoped_scores() {
+        let dir = tempdir().unwrap();
+        let frecency = Frecency::new(100, dir.path());
+        for _ in 0..4 {
+            frecency.record_access("files", "elsewhere.rs");
+        }
+        frecency.record_access("files", "here.rs");
+        frecency.record_scoped_access("/repo", "files", "here.rs");
+
+        let global = frecency.get_channel_scores("files");
+        assert!(global.get("elsewhere.rs") > global.get("here.rs"));
+
+        let scoped = frecency.get_scoped_scores("files", "/repo", 0.5);
+        assert_eq!(scoped.get("elsewhere.rs"), Some(200));
+        assert_eq!(scoped.get("here.rs"), Some(150));
+        let scoped = frecency.get_scoped_scores("files", "/repo", 0.0);
+        assert_eq!(scoped.get("elsewhere.rs"), Some(0));
+        assert_eq!(scoped.get("here.rs"), Some(100));
+        let other = frecency.get_scoped_scores("files", "/other", 0.5);
+        assert_eq!(other.get("here.rs"), Some(50));
+
+        // scoped records are saved and forgotten along with global ones
+        frecency.save_to_file().unwrap();
+        let reloaded = Frecency::new(100, dir.path());
+        reloaded.init().unwrap();
+        let scoped = reloaded.get_scoped_scores("files", "/repo", 0.0);
+        assert_eq!(scoped.get("here.rs"), Some(100));
+        assert!(reloaded.remove("files", "here.rs"));
+        let scoped = reloaded.get_scoped_scores("files", "/repo", 0.0);
+        assert_eq!(scoped.get("here.rs"), None);
+        assert_eq!(reloaded.reset("files"), 1);
+        assert!(reloaded.data.read().scopes.is_empty());
+    }
+
+    #[test]
+    fn test_frecency_max_scopes() {
+        let dir = tempdir().unwrap();
+        let frecency =
+            Frecency::new(100, dir.path()).with_config(FrecencyConfig {
+                max_scopes: 2,
+                ..FrecencyConfig::default()
+            });
+        frecency.record_scoped_access("/old", "files", "a");
+        frecency.record_scoped_access("/recent", "files", "a");
+        frecency.record_scoped_access("/recent", "dirs", "b");
+        for (scope, channels) in &mut frecency.data.write().scopes {
+            if scope == "/old" {
+                channels
+                    .get_mut("files")
+                    .unwrap()
+                    .get_mut("a")
+                    .unwrap()
+                    .last_access -= 60;
+            }
+        }
+
+        frecency.record_scoped_access("/new", "files", "a");
+        let data = frecency.data.read();
+        let mut scopes: Vec<_> = data.scopes.keys().collect();
+        scopes.sort();
+        assert_eq!(scopes, vec!["/new", "/recent"]);
+    }
 }
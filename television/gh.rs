@@ -5,11 +5,7 @@ use std::path::Path;
 use tracing::debug;
 use ureq::{RequestBuilder, get, http::HeaderValue, typestate::WithoutBody};
 
-use crate::{
-    cable::{CABLE_DIR_NAME, CHANNEL_FILE_FORMAT},
-    channels::prototypes::{BinaryRequirement, ChannelPrototype},
-    config::get_config_dir,
-};
+use crate::update::{ChannelSource, DownloadedPrototype};
 
 #[derive(Debug, Clone, serde::Deserialize)]
 struct GhNode {
@@ -87,21 +83,39 @@ fn fetch_raw_content_from_url(url: &str) -> Result<String> {
     }
 }
 
-struct DownloadedPrototype {
-    pub name: String,
-    pub content: String,
+#[cfg(unix)]
+pub(crate) const DEFAULT_CABLE_DIR_PATH: &str = "cable/unix";
+#[cfg(windows)]
+pub(crate) const DEFAULT_CABLE_DIR_PATH: &str = "cable/windows";
+
+/// The channels published in the television repository, the default source
+/// of `tv update-channels`.
+pub struct GithubSource {
+    /// The git ref the channels are fetched at.
+    git_ref: String,
 }
 
-impl DownloadedPrototype {
-    pub fn new(name: String, content: String) -> Self {
-        Self { name, content }
+impl GithubSource {
+    pub fn new(git_ref: &str) -> Self {
+        Self {
+            git_ref: git_ref.to_string(),
+        }
     }
 }
 
-#[cfg(unix)]
-const DEFAULT_CABLE_DIR_PATH: &str = "cable/unix";
-#[cfg(windows)]
-const DEFAULT_CABLE_DIR_PATH: &str = "cable/windows";
+impl ChannelSource for GithubSource {
+    fn id(&self) -> String {
+        "github".to_string()
+    }
+
+    fn describe(&self) -> String {
+        format!("alexpasmantier/television@{}", self.git_ref)
+    }
+
+    fn fetch(&self) -> Result<Vec<DownloadedPrototype>> {
+        get_default_prototypes_from_repo(&self.git_ref)
+    }
+}
 
 fn get_default_prototypes_from_repo(
     tv_version: &str,
@@ -126,84 +140,9 @@ fn get_default_prototypes_from_repo(
                 None
             }
         })
-        .filter_map(|url| fetch_raw_content_from_url(&url).ok())
-        .filter_map(|content| {
-            let name = toml::from_str::<ChannelPrototype>(&content)
-                .map(|p| p.metadata.name)
-                .ok()?;
-            Some(DownloadedPrototype::new(name, content))
+        .filter_map(|url| {
+            let content = fetch_raw_content_from_url(&url).ok()?;
+            DownloadedPrototype::parse(&url, content)
         })
         .collect())
 }
-
-pub fn update_local_channels(force: &bool) -> Result<()> {
-    let tv_version = env!("CARGO_PKG_VERSION");
-    println!(
-        "{}{}",
-        "Fetching cable channels for ".bold(),
-        tv_version.green().bold()
-    );
-    let default_prototypes = get_default_prototypes_from_repo(tv_version)?;
-    println!("{}", "\nSaving channels locally...".bold());
-    let cable_path = get_config_dir().join(CABLE_DIR_NAME);
-    if !cable_path.exists() {
-        println!("  Creating cable directory at {}", cable_path.display());
-        std::fs::create_dir_all(&cable_path)?;
-    }
-    for p in default_prototypes {
-        let file_path =
-            cable_path.join(&p.name).with_extension(CHANNEL_FILE_FORMAT);
-        // if the file already exists, don't overwrite it
-        if file_path.exists() && !force {
-            println!(
-                "  Channel {} already exists at {}, SKIPPING...",
-                p.name.blue().bold(),
-                file_path.display().to_string().yellow().bold()
-            );
-            continue;
-        }
-        // skip prototypes with unmet binary requirements unless force is true
-        if !force {
-            let mut channel_requirements =
-                toml::from_str::<ChannelPrototype>(&p.content)?
-                    .metadata
-                    .requirements;
-
-            channel_requirements
-                .iter_mut()
-                .for_each(BinaryRequirement::init);
-
-            let missing_requirements = channel_requirements
-                .iter_mut()
-                .filter(|r| !r.is_met())
-                .collect::<Vec<&mut BinaryRequirement>>();
-
-            if !missing_requirements.is_empty() {
-                println!(
-                    "  Channel {} requirement {} is unavailable on this system, SKIPPING...",
-                    p.name.blue().bold(),
-                    missing_requirements
-                        .iter()
-                        .map(|r| r.bin_name.as_str())
-                        .collect::<Vec<&str>>()
-                        .join(", ")
-                        .red()
-                        .bold()
-                );
-                continue;
-            }
-        }
-
-        std::fs::write(&file_path, p.content)?;
-        println!(
-            "  Saved channel {} to {}",
-            p.name.blue().bold(),
-            file_path.display().to_string().yellow().bold()
-        );
-    }
-    println!(
-        "{}",
-        "\nCable channels updated successfully.".green().bold()
-    );
-    Ok(())
-}
@@ -217,6 +217,20 @@ impl History {
         &self.entries
     }
 
+    /// Get the entries of the current channel, or of all channels in global
+    /// mode, from the most recent one and without duplicates.
+    pub fn get_scoped_entries(&self) -> Vec<&HistoryEntry> {
+        let mut scoped: Vec<&HistoryEntry> = Vec::new();
+        for entry in self.entries.iter().rev() {
+            if (self.global_mode || entry.channel == self.current_channel)
+                && !scoped.contains(&entry)
+            {
+                scoped.push(entry);
+            }
+        }
+        scoped
+    }
+
     /// Update the current channel context for this history instance.
     pub fn update_channel_context(
         &mut self,
@@ -500,6 +514,30 @@ mod tests {
         assert_eq!(dump_entries(&hist), vec!["file3", "dir2", "file2"]);
     }
 
+    /// Scoped entries are the newest first, without duplicates.
+    #[test]
+    fn scoped_entries_channel_and_global_mode() {
+        let mut entries = make_entries();
+        entries.push(HistoryEntry::new("file1".into(), "files".into()));
+        let dir = setup_history_file(&entries);
+        let queries = |hist: &History| -> Vec<String> {
+            hist.get_scoped_entries()
+                .iter()
+                .map(|e| e.query.clone())
+                .collect()
+        };
+
+        let mut hist = History::new(10, "files", false, dir.path());
+        hist.init().unwrap();
+        assert_eq!(queries(&hist), vec!["file1", "file3", "file2"]);
+
+        hist.update_channel_context("files", true);
+        assert_eq!(
+            queries(&hist),
+            vec!["file1", "file3", "dir2", "file2", "dir1"]
+        );
+    }
+
     /// Test loading from a non-existent history file.
     #[test]
     fn init_from_nonexistent_file() {
@@ -13,6 +13,15 @@ pub fn convert_action_to_input_request(
         Action::GoToNextChar => Some(InputRequest::GoToNextChar),
         Action::GoToInputStart => Some(InputRequest::GoToStart),
         Action::GoToInputEnd => Some(InputRequest::GoToEnd),
+        Action::GoToPrevWord => Some(InputRequest::GoToPrevWord),
+        Action::GoToNextWord => Some(InputRequest::GoToNextWord),
+        Action::DeleteNextWord => Some(InputRequest::DeleteNextWord),
+        Action::DeleteToInputEnd => Some(InputRequest::DeleteTillEnd),
+        Action::TransposeChars => Some(InputRequest::TransposeChars),
+        Action::Yank => Some(InputRequest::Yank),
+        Action::YankPop => Some(InputRequest::YankPop),
+        Action::Undo => Some(InputRequest::Undo),
+        Action::Redo => Some(InputRequest::Redo),
         _ => None,
     }
 }
@@ -9,10 +9,34 @@ use crate::{
 };
 use rustc_hash::FxHashMap;
 
+/// Vim-like input modes, only used when modal keybindings are enabled.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InputMode {
+    /// Keys type text unless they are bound to actions.
+    #[default]
+    Insert,
+    /// Keys only trigger the actions they are bound to.
+    Normal,
+}
+
+/// What a key press resolves to, given the keys pressed before it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeyResolution {
+    /// The keys are bound to these actions.
+    Actions(Actions),
+    /// The keys are the beginning of a key sequence.
+    Pending,
+    /// The keys aren't bound to anything.
+    Unbound,
+}
+
 #[derive(Default, Debug, Clone, PartialEq, Eq)]
 pub struct InputMap {
     pub global_keybindings: Keybindings,
     pub channel_keybindings: Keybindings,
+    /// Bindings of the normal and insert modes, set when modal keybindings
+    /// are enabled.
+    pub mode_keybindings: Option<(Keybindings, Keybindings)>,
 
     /// This is a reverse mapping of global and channel keybindings
     /// to facilitate lookups from actions to keys.
@@ -34,21 +58,80 @@ impl InputMap {
         Self {
             global_keybindings: global,
             channel_keybindings: channel,
+            mode_keybindings: None,
             actions_keys,
         }
     }
 
+    /// Enables modal keybindings, with bindings specific to the normal and
+    /// insert modes taking precedence over the other ones.
+    #[must_use]
+    pub fn with_modes(
+        mut self,
+        normal: Keybindings,
+        insert: Keybindings,
+    ) -> Self {
+        self.mode_keybindings = Some((normal, insert));
+        self
+    }
+
+    pub fn is_modal(&self) -> bool {
+        self.mode_keybindings.is_some()
+    }
+
+    /// The keybindings to look keys up in, by order of precedence.
+    fn layers(&self, mode: Mode, input_mode: InputMode) -> Vec<&Keybindings> {
+        let mut layers = Vec::with_capacity(3);
+        if let Some((normal, insert)) = &self.mode_keybindings {
+            layers.push(match input_mode {
+                InputMode::Normal => normal,
+                InputMode::Insert => insert,
+            });
+        }
+        if mode == Mode::Channel {
+            layers.push(&self.channel_keybindings);
+        }
+        layers.push(&self.global_keybindings);
+        layers
+    }
+
+    /// Resolves the keys pressed so far, the last one included.
+    ///
+    /// Within the same keybindings, a key bound on its own takes precedence
+    /// over the sequences it starts.
+    /// Sequences starting with a character key are only looked up in normal
+    /// mode since that key types text otherwise.
+    pub fn resolve(
+        &self,
+        keys: &[Key],
+        mode: &Mode,
+        input_mode: InputMode,
+    ) -> KeyResolution {
+        let sequences = !matches!(keys.first(), Some(Key::Char(_)))
+            || (self.is_modal() && input_mode == InputMode::Normal);
+        for layer in self.layers(*mode, input_mode) {
+            if let Some(actions) = layer.get_keys(keys) {
+                return KeyResolution::Actions(actions.clone());
+            }
+            if sequences && layer.starts_sequence(keys) {
+                return KeyResolution::Pending;
+            }
+        }
+        KeyResolution::Unbound
+    }
+
     /// Gets all actions bound to a specific key for the current mode.
     ///
     /// - `Mode::Channel` checks both global and channel-specific keybindings.
-    /// - `Mode::RemoteControl` and `Mode::ActionPicker` only check global keybindings.
+    /// - `Mode::RemoteControl`, `Mode::ActionPicker` and `Mode::HistoryPicker` only check
+    ///   global keybindings.
     pub fn get_actions_for_key(
         &self,
         key: &Key,
         mode: &Mode,
     ) -> Option<&Actions> {
         match mode {
-            Mode::RemoteControl | Mode::ActionPicker => {
+            Mode::RemoteControl | Mode::ActionPicker | Mode::HistoryPicker => {
                 self.global_keybindings.get(key)
             }
             Mode::Channel => self
@@ -228,4 +311,76 @@ mod tests {
             &[Action::ExternalAction(String::from("custom_enter"))]
         );
     }
+
+    #[test]
+    fn test_input_map_resolve_sequences_and_modes() {
+        let mut global = Keybindings::default();
+        global.insert(Key::Esc, Actions::single(Action::Quit));
+        global.bind(
+            vec![Key::Ctrl('g'), Key::Char('r')],
+            Actions::single(Action::ReloadSource),
+        );
+        global.bind(
+            vec![Key::Char('g'), Key::Char('s')],
+            Actions::single(Action::CycleSources),
+        );
+        let mut normal = Keybindings::default();
+        normal
+            .insert(Key::Char('j'), Actions::single(Action::SelectNextEntry));
+        let mut insert = Keybindings::default();
+        insert.insert(Key::Esc, Actions::single(Action::EnterNormalMode));
+
+        let input_map = InputMap::new(global.clone(), Keybindings::default());
+        let resolve = |input_map: &InputMap, keys: &[Key], input_mode| {
+            input_map.resolve(keys, &Mode::Channel, input_mode)
+        };
+
+        // sequences starting with a character key would prevent typing it
+        assert_eq!(
+            resolve(&input_map, &[Key::Char('g')], InputMode::Insert),
+            KeyResolution::Unbound
+        );
+        assert_eq!(
+            resolve(&input_map, &[Key::Ctrl('g')], InputMode::Insert),
+            KeyResolution::Pending
+        );
+        assert_eq!(
+            resolve(
+                &input_map,
+                &[Key::Ctrl('g'), Key::Char('r')],
+                InputMode::Insert
+            ),
+            KeyResolution::Actions(Actions::single(Action::ReloadSource))
+        );
+        assert_eq!(
+            resolve(
+                &input_map,
+                &[Key::Ctrl('g'), Key::Char('x')],
+                InputMode::Insert
+            ),
+            KeyResolution::Unbound
+        );
+
+        let input_map = input_map.with_modes(normal, insert);
+        assert_eq!(
+            resolve(&input_map, &[Key::Esc], InputMode::Insert),
+            KeyResolution::Actions(Actions::single(Action::EnterNormalMode))
+        );
+        assert_eq!(
+            resolve(&input_map, &[Key::Esc], InputMode::Normal),
+            KeyResolution::Actions(Actions::single(Action::Quit))
+        );
+        assert_eq!(
+            resolve(&input_map, &[Key::Char('j')], InputMode::Normal),
+            KeyResolution::Actions(Actions::single(Action::SelectNextEntry))
+        );
+        assert_eq!(
+            resolve(&input_map, &[Key::Char('j')], InputMode::Insert),
+            KeyResolution::Unbound
+        );
+        assert_eq!(
+            resolve(&input_map, &[Key::Char('g')], InputMode::Normal),
+            KeyResolution::Pending
+        );
+    }
 }
@@ -12,13 +12,18 @@ pub mod gh;
 pub mod history;
 pub mod input;
 pub mod keymap;
+pub mod listen;
 pub mod logging;
 pub mod matcher;
 pub mod mouse;
+pub mod output;
 pub mod picker;
 pub mod previewer;
 pub mod render;
 pub mod screen;
+pub mod session;
 pub mod television;
 pub mod tui;
+pub mod update;
 pub mod utils;
+pub mod validate;
@@ -0,0 +1,376 @@
+//! A control server listening on a Unix socket, enabled with `--listen`.
+//!
+//! Clients send newline-delimited commands, either as plain text
+//! (`change_query:foo`, `select_next_entry`) or as JSON objects
+//! (`{"action": "change_query", "argument": "foo"}`), and get back exactly
+//! one JSON line per command. Besides the regular actions, `get_state`
+//! returns a snapshot of the picker and `subscribe` streams a new snapshot
+//! every time it changes.
+use std::path::Path;
+
+use anyhow::{Result, anyhow, bail};
+use serde::{Deserialize, Serialize};
+use tokio::{
+    sync::{mpsc::UnboundedSender, watch},
+    task::JoinHandle,
+};
+
+use crate::{
+    action::{Action, CUSTOM_ACTION_PREFIX},
+    channels::prototypes::Template,
+};
+
+/// Commands that take an argument, written `<command>:<argument>` in the
+/// text form.
+const ARGUMENT_COMMANDS: [&str; 3] =
+    ["change_query", "change_preview", "switch_channel"];
+
+/// A snapshot of the application state sent to clients.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ListenState {
+    pub channel: String,
+    pub query: String,
+    /// The raw entry under the cursor.
+    pub current_entry: Option<String>,
+    /// The raw entries selected so far, in selection order.
+    pub selected_entries: Vec<String>,
+    pub matched_count: u32,
+    pub total_count: u32,
+    /// Whether the channel is still loading entries.
+    pub running: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Command {
+    Action(Action),
+    GetState,
+    Subscribe,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonCommand {
+    action: String,
+    #[serde(default)]
+    argument: Option<String>,
+}
+
+impl Command {
+    fn parse(line: &str) -> Result<Self> {
+        let (name, argument) = if line.starts_with('{') {
+            let command: JsonCommand = serde_json::from_str(line)
+                .map_err(|e| anyhow!("Invalid JSON command: {}", e))?;
+            (command.action, command.argument)
+        } else {
+            match line.split_once(':') {
+                Some((name, argument))
+                    if ARGUMENT_COMMANDS.contains(&name) =>
+                {
+                    (name.to_string(), Some(argument.to_string()))
+                }
+                _ => (line.to_string(), None),
+            }
+        };
+
+        match (name.as_str(), argument) {
+            ("change_query", Some(query)) => {
+                Ok(Self::Action(Action::SetQuery(query)))
+            }
+            ("change_preview", Some(command)) => {
+                Template::parse(&command).map_err(|e| {
+                    anyhow!("Invalid preview command '{}': {}", command, e)
+                })?;
+                Ok(Self::Action(Action::ChangePreview(command)))
+            }
+            ("switch_channel", Some(channel)) => {
+                Ok(Self::Action(Action::SwitchToChannel(channel)))
+            }
+            (name, None) if ARGUMENT_COMMANDS.contains(&name) => {
+                bail!("'{}' requires an argument", name)
+            }
+            ("get_state", None) => Ok(Self::GetState),
+            ("subscribe", None) => Ok(Self::Subscribe),
+            (name, None) => {
+                let action = serde_json::from_value::<Action>(
+                    serde_json::Value::String(name.to_string()),
+                )
+                .map_err(|_| anyhow!("Unknown action: '{}'", name))?;
+                // any other string deserializes to an external action
+                if matches!(&action, Action::ExternalAction(a) if !a.starts_with(CUSTOM_ACTION_PREFIX))
+                {
+                    bail!("Unknown action: '{}'", name);
+                }
+                Ok(Self::Action(action))
+            }
+            (name, Some(_)) => bail!("'{}' does not take an argument", name),
+        }
+    }
+}
+
+fn ok() -> serde_json::Value {
+    serde_json::json!({ "ok": true })
+}
+
+fn error(e: &anyhow::Error) -> serde_json::Value {
+    serde_json::json!({ "error": e.to_string() })
+}
+
+/// Handles a single command line, returning the reply to send back.
+fn handle_line(
+    line: &str,
+    action_tx: &UnboundedSender<Action>,
+    state_rx: &mut watch::Receiver<ListenState>,
+    subscribed: &mut bool,
+) -> Result<serde_json::Value> {
+    Ok(match Command::parse(line) {
+        Ok(Command::Action(action)) => {
+            action_tx.send(action)?;
+            ok()
+        }
+        Ok(Command::GetState) => {
+            serde_json::to_value(&*state_rx.borrow_and_update())?
+        }
+        Ok(Command::Subscribe) => {
+            *subscribed = true;
+            serde_json::to_value(&*state_rx.borrow_and_update())?
+        }
+        Err(e) => error(&e),
+    })
+}
+
+/// Starts listening on `path` and forwards the commands received to the
+/// application through `action_tx`.
+///
+/// A stale socket left behind by a previous instance is replaced, but this
+/// fails if another instance is still listening on `path`.
+///
+/// Clients subscribe to `state_tx` when they connect, so the application
+/// only needs to publish its state while some are connected.
+#[cfg(unix)]
+pub fn listen(
+    path: &Path,
+    action_tx: UnboundedSender<Action>,
+    state_tx: watch::Sender<ListenState>,
+) -> Result<JoinHandle<()>> {
+    use tokio::net::UnixListener;
+    use tracing::{debug, warn};
+
+    if path.exists() {
+        if std::os::unix::net::UnixStream::connect(path).is_ok() {
+            bail!(
+                "Another instance is already listening on {}",
+                path.display()
+            );
+        }
+        std::fs::remove_file(path)?;
+    }
+    let listener = UnixListener::bind(path).map_err(|e| {
+        anyhow!("Failed to listen on {}: {}", path.display(), e)
+    })?;
+    debug!("Listening for commands on {:?}", path);
+
+    Ok(tokio::spawn(async move {
+        loop {
+            match listener.accept().await {
+                Ok((stream, _)) => {
+                    let action_tx = action_tx.clone();
+                    let state_rx = state_tx.subscribe();
+                    // wake the application up so that it publishes its
+                    // current state for the new client
+                    let _ = action_tx.send(Action::Tick);
+                    tokio::spawn(async move {
+                        if let Err(e) =
+                            serve(stream, action_tx, state_rx).await
+                        {
+                            debug!("Listen client disconnected: {}", e);
+                        }
+                    });
+                }
+                Err(e) => {
+                    warn!("Failed to accept listen connection: {}", e);
+                }
+            }
+        }
+    }))
+}
+
+#[cfg(not(unix))]
+pub fn listen(
+    _path: &Path,
+    _action_tx: UnboundedSender<Action>,
+    _state_tx: watch::Sender<ListenState>,
+) -> Result<JoinHandle<()>> {
+    bail!("--listen is only supported on Unix platforms")
+}
+
+#[cfg(unix)]
+async fn serve(
+    stream: tokio::net::UnixStream,
+    action_tx: UnboundedSender<Action>,
+    mut state_rx: watch::Receiver<ListenState>,
+) -> Result<()> {
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+    let (reader, mut writer) = stream.into_split();
+    let mut lines = BufReader::new(reader).lines();
+    let mut subscribed = false;
+    loop {
+        let reply = tokio::select! {
+            line = lines.next_line() => {
+                let Some(line) = line? else {
+                    return Ok(());
+                };
+                let line = line.trim();
+                if line.is_empty() {
+                    continue;
+                }
+                handle_line(line, &action_tx, &mut state_rx, &mut subscribed)?
+            }
+            changed = state_rx.changed(), if subscribed => {
+                // the application has exited
+                if changed.is_err() {
+                    return Ok(());
+                }
+                serde_json::to_value(&*state_rx.borrow_and_update())?
+            }
+        };
+        let mut reply = serde_json::to_vec(&reply)?;
+        reply.push(b'\n');
+        writer.write_all(&reply).await?;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_text_commands() {
+        assert_eq!(
+            Command::parse("select_next_entry").unwrap(),
+            Command::Action(Action::SelectNextEntry)
+        );
+        assert_eq!(
+            Command::parse("change_query:foo:bar").unwrap(),
+            Command::Action(Action::SetQuery("foo:bar".to_string()))
+        );
+        assert_eq!(
+            Command::parse("change_query:").unwrap(),
+            Command::Action(Action::SetQuery(String::new()))
+        );
+        assert_eq!(
+            Command::parse("actions:edit").unwrap(),
+            Command::Action(Action::ExternalAction(
+                "actions:edit".to_string()
+            ))
+        );
+        assert_eq!(Command::parse("get_state").unwrap(), Command::GetState);
+    }
+
+    #[test]
+    fn test_parse_json_commands() {
+        assert_eq!(
+            Command::parse(
+                r#"{"action": "switch_channel", "argument": "files"}"#
+            )
+            .unwrap(),
+            Command::Action(Action::SwitchToChannel("files".to_string()))
+        );
+        assert_eq!(
+            Command::parse(r#"{"action": "reload_source"}"#).unwrap(),
+            Command::Action(Action::ReloadSource)
+        );
+        assert_eq!(
+            Command::parse(r#"{"action": "subscribe"}"#).unwrap(),
+            Command::Subscribe
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_commands() {
+        assert!(Command::parse("foo").is_err());
+        assert!(Command::parse("render").is_err());
+        assert!(Command::parse("change_preview").is_err());
+        assert!(
+            Command::parse(r#"{"action": "quit", "argument": "x"}"#).is_err()
+        );
+        assert!(Command::parse("{not json").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_handle_line_replies() {
+        let (action_tx, mut action_rx) =
+            tokio::sync::mpsc::unbounded_channel();
+        let (state_tx, mut state_rx) = watch::channel(ListenState::default());
+        state_tx.send_modify(|state| state.matched_count = 3);
+        let mut subscribed = false;
+
+        let reply = handle_line(
+            "confirm_selection",
+            &action_tx,
+            &mut state_rx,
+            &mut subscribed,
+        )
+        .unwrap();
+        assert_eq!(reply, ok());
+        assert_eq!(action_rx.recv().await, Some(Action::ConfirmSelection));
+
+        let reply = handle_line(
+            "get_state",
+            &action_tx,
+            &mut state_rx,
+            &mut subscribed,
+        )
+        .unwrap();
+        assert_eq!(reply["matched_count"], 3);
+        assert!(!subscribed);
+
+        let reply =
+            handle_line("nope", &action_tx, &mut state_rx, &mut subscribed)
+                .unwrap();
+        assert_eq!(reply["error"], "Unknown action: 'nope'");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_listen_socket_roundtrip() {
+        use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("tv.sock");
+        let (action_tx, mut action_rx) =
+            tokio::sync::mpsc::unbounded_channel();
+        let (state_tx, _) = watch::channel(ListenState::default());
+        let task = listen(&path, action_tx.clone(), state_tx.clone()).unwrap();
+        assert!(listen(&path, action_tx, state_tx.clone()).is_err());
+        assert_eq!(state_tx.receiver_count(), 0);
+
+        let stream = tokio::net::UnixStream::connect(&path).await.unwrap();
+        // connecting wakes the application up, once for the probe of the
+        // second `listen` above and once for this client
+        for _ in 0..2 {
+            assert_eq!(action_rx.recv().await, Some(Action::Tick));
+        }
+        assert!(state_tx.receiver_count() > 0);
+        let (reader, mut writer) = stream.into_split();
+        let mut lines = BufReader::new(reader).lines();
+        let mut reply = async || {
+            let line = lines.next_line().await.unwrap().unwrap();
+            serde_json::from_str::<serde_json::Value>(&line).unwrap()
+        };
+
+        writer
+            .write_all(b"change_query:foo\nsubscribe\n")
+            .await
+            .unwrap();
+        assert_eq!(reply().await, ok());
+        assert_eq!(
+            action_rx.recv().await,
+            Some(Action::SetQuery("foo".into()))
+        );
+        assert_eq!(reply().await["query"], "");
+
+        state_tx.send_modify(|state| state.query = "foo".to_string());
+        assert_eq!(reply().await["query"], "foo");
+        task.abort();
+    }
+}
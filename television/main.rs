@@ -1,8 +1,8 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use clap::Parser;
 use std::env;
-use std::io::{BufWriter, IsTerminal, Write, stderr, stdout};
-use std::path::PathBuf;
+use std::io::{BufWriter, IsTerminal, Read, Write, stderr, stdin, stdout};
+use std::path::{Path, PathBuf};
 use std::process::exit;
 use television::channels::prototypes::remove_enter_keybinding;
 use television::cli::ChannelCli;
@@ -11,15 +11,21 @@ use television::config::shell_integration::ShellIntegrationConfig;
 use television::{
     app::App,
     cable::{Cable, load_cable},
+    channels::history_picker::format_age,
     channels::prototypes::ChannelPrototype,
     cli::{
-        args::{Cli, Command},
+        args::{Cli, Command, FrecencyCommand, FrecencyImportFormat},
         guess_channel_from_prompt, list_channels, post_process,
     },
-    config::{Config, ConfigEnv},
-    errors::os_error_exit,
-    gh::update_local_channels,
+    config::{AppConfig, Config, ConfigEnv},
+    errors::{cli_parsing_error_exit, os_error_exit},
+    frecency::{
+        Frecency, FrecencyRecord, current_timestamp, parse_autojump,
+        parse_fasd, parse_zoxide,
+    },
+    output::write_output,
     television::Mode,
+    update::update_local_channels,
     utils::clipboard::CLIPBOARD,
     utils::paths::expand_tilde,
     utils::{
@@ -29,6 +35,7 @@ use television::{
         },
         stdin::is_readable_stdin,
     },
+    validate::{Severity, collect_channel_files, validate_files},
 };
 use tracing::{debug, info};
 
@@ -65,7 +72,13 @@ async fn main() -> Result<()> {
     // handle subcommands
     debug!("Handling subcommands...");
     if let Some(subcommand) = &cli.global.command {
-        handle_subcommand(subcommand, &cable, &base_config.shell_integration)?;
+        handle_subcommand(
+            subcommand,
+            &cable,
+            &cable_dir,
+            &base_config.application,
+            &base_config.shell_integration,
+        )?;
     }
 
     // optionally change the working directory
@@ -79,6 +92,17 @@ async fn main() -> Result<()> {
     let channel_prototype =
         determine_channel(&cli.channel, &base_config, readable_stdin, &cable);
 
+    if let Some(name) =
+        cli.channel.params.keys().find(|name| {
+            !channel_prototype.metadata.params.contains_key(*name)
+        })
+    {
+        cli_parsing_error_exit(&format!(
+            "Channel '{}' has no parameter '{}'",
+            channel_prototype.metadata.name, name
+        ));
+    }
+
     let layered_config =
         ConfigLayers::new(base_config, channel_prototype, cli.clone());
 
@@ -107,20 +131,24 @@ async fn main() -> Result<()> {
         || app.television.merged_config.take_1_fast;
     let no_tty_available = !stdout().is_terminal() && !stderr().is_terminal();
     let headless = has_auto_select && no_tty_available;
+    if headless && let Some(prompt) = &app.television.param_prompt {
+        cli_parsing_error_exit(&format!(
+            "Missing value for parameter '{}', set it with --param",
+            prompt.params[0].0
+        ));
+    }
 
     let output = app.run(stdout().is_terminal(), headless).await?;
     info!("App output: {:?}", output);
 
     let stdout_handle = stdout().lock();
     let mut bufwriter = BufWriter::new(stdout_handle);
-    if let Some(key) = output.expect_key {
-        writeln!(bufwriter, "{}", key)?;
-    }
-    if let Some(entries) = output.selected_entries {
-        for entry in &entries {
-            writeln!(bufwriter, "{}", entry.output()?)?;
-        }
-    }
+    write_output(
+        &mut bufwriter,
+        &output,
+        cli.global.output_format,
+        app.television.merged_config.channel_output_delimiter,
+    )?;
     bufwriter.flush()?;
     exit(0);
 }
@@ -133,6 +161,8 @@ pub fn set_current_dir(path: &PathBuf) -> Result<()> {
 pub fn handle_subcommand(
     command: &Command,
     cable: &Cable,
+    cable_dir: &Path,
+    app_config: &AppConfig,
     shell_integration_config: &ShellIntegrationConfig,
 ) -> Result<()> {
     match command {
@@ -165,11 +195,138 @@ pub fn handle_subcommand(
             }
             exit(0);
         }
-        Command::UpdateChannels { force } => {
-            update_local_channels(force)?;
+        Command::UpdateChannels {
+            force,
+            from,
+            dry_run,
+        } => {
+            update_local_channels(
+                cable_dir,
+                from.as_deref(),
+                *force,
+                *dry_run,
+            )?;
             exit(0);
         }
+        Command::Validate { paths } => {
+            let paths = if paths.is_empty() {
+                vec![cable_dir.to_path_buf()]
+            } else {
+                paths.iter().map(expand_tilde).collect()
+            };
+            let files = collect_channel_files(&paths);
+            let diagnostics = validate_files(&files, cable, cable_dir);
+            let mut stdout = stdout().lock();
+            for diagnostic in &diagnostics {
+                let _ = writeln!(stdout, "{diagnostic}");
+            }
+            let errors = diagnostics
+                .iter()
+                .filter(|d| d.severity == Severity::Error)
+                .count();
+            let _ = writeln!(
+                stdout,
+                "Checked {} channel file(s): {} error(s), {} warning(s)",
+                files.len(),
+                errors,
+                diagnostics.len() - errors
+            );
+            exit(i32::from(errors > 0));
+        }
+        Command::Frecency { command } => {
+            handle_frecency_command(command, app_config)?;
+            exit(0);
+        }
+    }
+}
+
+/// Runs a `tv frecency` subcommand on the frecency file of the data
+/// directory.
+fn handle_frecency_command(
+    command: &FrecencyCommand,
+    app_config: &AppConfig,
+) -> Result<()> {
+    let frecency =
+        Frecency::new(app_config.frecency_max_entries, &app_config.data_dir)
+            .with_config(app_config.frecency.clone());
+    frecency.init()?;
+    let mut stdout = stdout().lock();
+    match command {
+        FrecencyCommand::List { channel } => {
+            let now = current_timestamp();
+            for (record, score) in frecency.scored_records(channel) {
+                writeln!(
+                    stdout,
+                    "{:>6} {:>5} {:>9}  {}",
+                    score,
+                    record.access_count,
+                    format_age(now.saturating_sub(record.last_access)),
+                    record.raw
+                )?;
+            }
+        }
+        FrecencyCommand::Export { channel } => {
+            let records: Vec<FrecencyRecord> = frecency
+                .scored_records(channel)
+                .into_iter()
+                .map(|(record, _)| record)
+                .collect();
+            writeln!(stdout, "{}", serde_json::to_string_pretty(&records)?)?;
+        }
+        FrecencyCommand::Remove { channel, entries } => {
+            let removed = entries
+                .iter()
+                .filter(|entry| frecency.remove(channel, entry))
+                .count();
+            frecency.save_to_file()?;
+            writeln!(stdout, "Forgot {removed} record(s) of '{channel}'")?;
+        }
+        FrecencyCommand::Reset { channel } => {
+            let removed = frecency.reset(channel);
+            frecency.save_to_file()?;
+            writeln!(stdout, "Forgot {removed} record(s) of '{channel}'")?;
+        }
+        FrecencyCommand::Import {
+            channel,
+            file,
+            from,
+        } => {
+            let contents = if let Some(file) = file {
+                std::fs::read(expand_tilde(file))
+                    .with_context(|| format!("Failed to read {file}"))?
+            } else {
+                let mut contents = Vec::new();
+                stdin().read_to_end(&mut contents)?;
+                contents
+            };
+            let now = current_timestamp();
+            let records = match from {
+                FrecencyImportFormat::Tv => {
+                    serde_json::from_slice::<Vec<FrecencyRecord>>(&contents)
+                        .context("Failed to parse frecency records")?
+                }
+                FrecencyImportFormat::Zoxide => parse_zoxide(&contents, now)?,
+                FrecencyImportFormat::Fasd => {
+                    parse_fasd(&String::from_utf8_lossy(&contents))?
+                }
+                FrecencyImportFormat::Autojump => {
+                    parse_autojump(&String::from_utf8_lossy(&contents), now)?
+                }
+            };
+            let imported = frecency.import(channel, records);
+            frecency.save_to_file()?;
+            writeln!(
+                stdout,
+                "Imported {imported} record(s) into '{channel}'"
+            )?;
+        }
+        FrecencyCommand::Prune { channel } => {
+            let pruned = frecency.prune_missing(channel.as_deref());
+            frecency.save_to_file()?;
+            writeln!(stdout, "Forgot {pruned} record(s) of missing files")?;
+        }
     }
+    Ok(())
 }
 
 /// Creates an ad-hoc channel prototype from CLI arguments
@@ -0,0 +1,97 @@
+use super::tiebreak::Tiebreak;
+use serde::{Deserialize, Serialize};
+
+/// How the case of patterns is matched against entries.
+#[derive(
+    Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Hash, Default, Eq,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum CaseMatching {
+    /// Ignore case unless the term contains uppercase characters.
+    ///
+    /// This is decided for each term of the query, e.g. `foo Bar` matches
+    /// `FOO Bar` but not `foo bar`, rather than for the term under the
+    /// cursor, so that editing one term never changes how the others match.
+    #[default]
+    Smart,
+    /// Always match case.
+    Respect,
+    /// Never match case.
+    Ignore,
+}
+
+impl From<CaseMatching> for nucleo::pattern::CaseMatching {
+    fn from(case_matching: CaseMatching) -> Self {
+        match case_matching {
+            CaseMatching::Smart => nucleo::pattern::CaseMatching::Smart,
+            CaseMatching::Respect => nucleo::pattern::CaseMatching::Respect,
+            CaseMatching::Ignore => nucleo::pattern::CaseMatching::Ignore,
+        }
+    }
+}
+
+impl From<crate::cli::args::CaseMatching> for CaseMatching {
+    fn from(case_matching: crate::cli::args::CaseMatching) -> Self {
+        match case_matching {
+            crate::cli::args::CaseMatching::Smart => CaseMatching::Smart,
+            crate::cli::args::CaseMatching::Respect => CaseMatching::Respect,
+            crate::cli::args::CaseMatching::Ignore => CaseMatching::Ignore,
+        }
+    }
+}
+
+/// Whether latin characters with diacritics are matched by their ASCII
+/// counterpart.
+#[derive(
+    Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Hash, Default, Eq,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum Normalization {
+    /// Ignore diacritics unless the term contains some, e.g. `cafe` matches
+    /// `café` but `café` doesn't match `cafe`.
+    #[default]
+    Smart,
+    /// Always match diacritics.
+    Never,
+}
+
+impl From<Normalization> for nucleo::pattern::Normalization {
+    fn from(normalization: Normalization) -> Self {
+        match normalization {
+            Normalization::Smart => nucleo::pattern::Normalization::Smart,
+            Normalization::Never => nucleo::pattern::Normalization::Never,
+        }
+    }
+}
+
+impl From<crate::cli::args::Normalization> for Normalization {
+    fn from(normalization: crate::cli::args::Normalization) -> Self {
+        match normalization {
+            crate::cli::args::Normalization::Smart => Normalization::Smart,
+            crate::cli::args::Normalization::Never => Normalization::Never,
+        }
+    }
+}
+
+/// The configuration of a [`Matcher`](super::Matcher).
+#[derive(Clone, Debug, PartialEq, Hash, Default, Eq)]
+pub struct Config {
+    pub case_matching: CaseMatching,
+    pub normalization: Normalization,
+    /// Score matches as file paths, giving a bonus to characters following
+    /// path separators instead of whitespace.
+    pub match_paths: bool,
+    /// How matches are ordered, after the sort strategy of the matcher.
+    pub tiebreak: Vec<Tiebreak>,
+}
+
+impl Config {
+    /// The configuration of the underlying nucleo matcher.
+    pub fn nucleo_config(&self) -> nucleo::Config {
+        if self.match_paths {
+            nucleo::Config::DEFAULT.match_paths()
+        } else {
+            nucleo::Config::DEFAULT
+        }
+    }
+}
@@ -0,0 +1,88 @@
+//! Filtering of nucleo's matches with the groups of alternatives and the
+//! regular expressions of queries, which nucleo doesn't support.
+//!
+//! Large sets of matches are filtered on a background thread so that the
+//! filters never hold up rendering.
+use super::query::Query;
+use nucleo::{Injector, Match};
+use std::{cmp::Reverse, sync::Arc, sync::mpsc::Sender};
+
+/// Sets of matches larger than this are filtered on a background thread.
+pub const BACKGROUND_FILTER_THRESHOLD: usize = 10_000;
+
+/// The outcome of the filters for each item, by item index: the score of
+/// the alternatives if the item passed them, `None` when it wasn't checked
+/// yet.
+pub type Verdicts = Vec<Option<Option<u32>>>;
+
+/// Filters a snapshot of nucleo's matches.
+pub struct FilterJob<I>
+where
+    I: Sync + Send + 'static,
+{
+    /// Identifies the query and the items the matches were found with.
+    pub generation: u64,
+    pub query: Arc<Query>,
+    /// nucleo's matches, in order.
+    pub matches: Vec<Match>,
+    /// The verdicts of previous runs for the same query, so that only the
+    /// items matched since go through the filters.
+    pub verdicts: Verdicts,
+    pub items: Injector<I>,
+    pub config: nucleo::Config,
+    /// Order the matches by their score plus the score of the alternatives
+    /// they matched rather than keeping nucleo's order.
+    pub rank: bool,
+}
+
+/// The matches that passed the filters.
+pub struct Filtered {
+    pub generation: u64,
+    /// The indices of the items that passed the filters, in display order.
+    pub items: Vec<u32>,
+    pub verdicts: Verdicts,
+}
+
+impl<I> FilterJob<I>
+where
+    I: Sync + Send + 'static,
+{
+    pub fn run(mut self) -> Filtered {
+        let mut matcher = nucleo::Matcher::new(self.config);
+        self.verdicts
+            .resize(self.items.injected_items() as usize, None);
+        let mut passed: Vec<(u32, u32)> = self
+            .matches
+            .iter()
+            .filter_map(|m| {
+                let verdict = self.verdicts.get_mut(m.idx as usize)?;
+                let score = verdict.get_or_insert_with(|| {
+                    self.items.get(m.idx).and_then(|item| {
+                        self.query.score(
+                            item.matcher_columns[0].slice(..),
+                            &mut matcher,
+                        )
+                    })
+                });
+                score.map(|score| (m.idx, m.score.saturating_add(score)))
+            })
+            .collect();
+        if self.rank {
+            // the sort is stable, equal scores keep nucleo's tiebreaks
+            passed.sort_by_key(|(_, score)| Reverse(*score));
+        }
+        Filtered {
+            generation: self.generation,
+            items: passed.into_iter().map(|(idx, _)| idx).collect(),
+            verdicts: self.verdicts,
+        }
+    }
+
+    /// Runs the filters on a background thread and sends the outcome to
+    /// `tx`.
+    pub fn spawn(self, tx: Sender<Filtered>) {
+        rayon::spawn(move || {
+            let _ = tx.send(self.run());
+        });
+    }
+}
@@ -28,10 +28,10 @@ where
     ///
     /// # Example
     /// ```
-    /// use television::matcher::{Matcher};
+    /// use television::matcher::{Matcher, config::Config};
     /// use nucleo::SortStrategy;
     ///
-    /// let matcher = Matcher::new(SortStrategy::Score, 2);
+    /// let matcher = Matcher::new(&Config::default(), SortStrategy::Score, 2);
     ///
     /// let injector = matcher.injector();
     /// injector.push(
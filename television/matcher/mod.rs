@@ -1,9 +1,22 @@
+use config::Config;
+use filter::{BACKGROUND_FILTER_THRESHOLD, FilterJob, Filtered, Verdicts};
 use injector::Injector;
-use std::{sync::Arc, thread::available_parallelism};
+use nucleo::{Item, pattern::Pattern};
+use query::Query;
+use std::sync::{
+    Arc,
+    mpsc::{Receiver, Sender, channel},
+};
+use std::thread::available_parallelism;
+use tiebreak::{TiebreakPattern, Tiebreaker};
 
+pub mod config;
+pub mod filter;
 pub mod injector;
 pub mod lazy;
 pub mod matched_item;
+pub mod query;
+pub mod tiebreak;
 
 const MATCHER_TICK_TIMEOUT: u64 = 2;
 
@@ -35,6 +48,7 @@ impl From<nucleo::Status> for Status {
 ///
 /// The matcher can be used to find items that match a given pattern and to
 /// retrieve the matched items as well as the indices of the matched characters.
+#[allow(clippy::struct_excessive_bools)]
 pub struct Matcher<I>
 where
     I: Sync + Send + Clone + 'static,
@@ -49,11 +63,53 @@ where
     pub status: Status,
     /// The last pattern that was matched against.
     pub last_pattern: String,
+    /// How patterns are matched against items.
+    config: Config,
+    /// The last pattern, parsed.
+    query: Arc<Query>,
+    /// The pattern handed over to nucleo, shared with the tiebreaker when it
+    /// needs to locate matches.
+    tiebreak_pattern: Option<Arc<TiebreakPattern>>,
+    /// Whether patterns are regular expressions instead of using the
+    /// extended query syntax.
+    regex: bool,
+    /// The indices of the items matched by nucleo that also match the
+    /// filters of the query, in display order.
+    ///
+    /// These are the last filtered matches until the filters of the current
+    /// matches are done running.
+    filtered: Option<Vec<u32>>,
+    /// Whether each item matches the filters of the query, by item index.
+    ///
+    /// Item indices don't change while items are streamed in, so only newly
+    /// matched items need to go through the filters when nucleo's matches
+    /// change.
+    filter_verdicts: Verdicts,
+    /// Bumped whenever the query or the items change, so that the outcome
+    /// of filters started before is discarded.
+    filter_generation: u64,
+    /// Whether nucleo's matches changed since the filters last ran.
+    filters_outdated: bool,
+    /// Whether filters are running on a background thread.
+    filters_running: bool,
+    filter_tx: Sender<Filtered>,
+    filter_rx: Receiver<Filtered>,
+    /// Whether matches are sorted by score, in which case the score of the
+    /// alternatives of the query counts as well.
+    rank_alternatives: bool,
     /// A pre-allocated buffer used to collect match indices when fetching the results
     /// from the matcher. This avoids having to re-allocate on each pass.
     col_indices_buffer: Vec<u32>,
 }
 
+/// Borrow an item again, nucleo's items aren't `Clone`.
+fn reborrow<'a, I>(item: &Item<'a, I>) -> Item<'a, I> {
+    Item {
+        data: item.data,
+        matcher_columns: item.matcher_columns,
+    }
+}
+
 /// Get the number of threads to use for the matcher.
 ///
 /// This uses the number of available threads on the system, minus 3, to avoid
@@ -73,23 +129,69 @@ where
     I: Sync + Send + Clone + 'static,
 {
     /// Create a new fuzzy matcher with the given configuration and sort strategy.
+    ///
+    /// Matches a custom sort strategy considers equal are ordered with the
+    /// tiebreaks of the configuration, which also replace the order of
+    /// [`SortStrategy::Score`](nucleo::SortStrategy::Score) when set.
     pub fn new(
+        config: &Config,
         sort_strategy: nucleo::SortStrategy<I>,
         n_threads: usize,
     ) -> Self {
         let mut inner = nucleo::Nucleo::new(
-            nucleo::Config::DEFAULT,
+            config.nucleo_config(),
             Arc::new(|| {}),
             Some(n_threads),
             1,
         );
+        let tiebreak_pattern = config
+            .tiebreak
+            .iter()
+            .any(|tiebreak| tiebreak.needs_pattern())
+            .then(|| Arc::new(TiebreakPattern::default()));
+        let rank_alternatives =
+            matches!(sort_strategy, nucleo::SortStrategy::Score);
+        let tiebreaker = Tiebreaker::new(
+            config.tiebreak.clone(),
+            config.nucleo_config(),
+            tiebreak_pattern.clone().unwrap_or_default(),
+        );
+        let sort_strategy = match sort_strategy {
+            nucleo::SortStrategy::Score if !config.tiebreak.is_empty() => {
+                nucleo::SortStrategy::Custom(Box::new(
+                    move |m1, i1, m2, i2| tiebreaker.compare(m1, &i1, m2, &i2),
+                ))
+            }
+            nucleo::SortStrategy::Custom(compare) => {
+                nucleo::SortStrategy::Custom(Box::new(
+                    move |m1, i1, m2, i2| {
+                        compare(m1, reborrow(&i1), m2, reborrow(&i2))
+                            .then_with(|| tiebreaker.compare(m1, &i1, m2, &i2))
+                    },
+                ))
+            }
+            sort_strategy => sort_strategy,
+        };
         inner.set_sort_strategy(sort_strategy);
+        let (filter_tx, filter_rx) = channel();
         Self {
             inner,
             total_item_count: 0,
             matched_item_count: 0,
             status: Status::default(),
             last_pattern: String::new(),
+            config: config.clone(),
+            query: Arc::default(),
+            tiebreak_pattern,
+            regex: false,
+            filtered: None,
+            filter_verdicts: Vec::new(),
+            filter_generation: 0,
+            filters_outdated: false,
+            filters_running: false,
+            filter_tx,
+            filter_rx,
+            rank_alternatives,
             col_indices_buffer: Vec::with_capacity(128), // Pre-allocate for performance
         }
     }
@@ -98,7 +200,79 @@ where
     ///
     /// This should be called periodically to update the state of the matcher.
     pub fn tick(&mut self) {
-        self.status = self.inner.tick(MATCHER_TICK_TIMEOUT).into();
+        let status = self.inner.tick(MATCHER_TICK_TIMEOUT);
+        if status.changed {
+            self.filters_outdated = true;
+        }
+        self.update_filters();
+        self.status = Status {
+            running: status.running || self.filters_running,
+        };
+    }
+
+    /// Filter the items matched by nucleo with the filters of the query if
+    /// they changed since the filters last ran, and pick up the outcome of
+    /// filters running in the background.
+    ///
+    /// Items that went through the filters before keep their verdict, so
+    /// this only runs the filters on the items matched since.
+    fn update_filters(&mut self) {
+        while let Ok(filtered) = self.filter_rx.try_recv() {
+            self.filters_running = false;
+            self.set_filtered(filtered);
+        }
+        if !self.query.has_filters()
+            || !self.filters_outdated
+            || self.filters_running
+        {
+            return;
+        }
+        self.filters_outdated = false;
+        let job = FilterJob {
+            generation: self.filter_generation,
+            query: self.query.clone(),
+            matches: self.inner.snapshot().matches().to_vec(),
+            verdicts: std::mem::take(&mut self.filter_verdicts),
+            items: self.inner.injector(),
+            config: self.config.nucleo_config(),
+            rank: self.rank_alternatives,
+        };
+        if job.matches.len() > BACKGROUND_FILTER_THRESHOLD {
+            self.filters_running = true;
+            job.spawn(self.filter_tx.clone());
+        } else {
+            self.set_filtered(job.run());
+        }
+    }
+
+    fn set_filtered(&mut self, filtered: Filtered) {
+        // filters started with a previous query or previous items are stale
+        if filtered.generation == self.filter_generation {
+            self.filtered = Some(filtered.items);
+            self.filter_verdicts = filtered.verdicts;
+        }
+    }
+
+    /// The indices of the filtered items, if the query has filters.
+    fn filtered(&self) -> Option<&[u32]> {
+        self.filtered
+            .as_deref()
+            .filter(|_| self.query.has_filters())
+    }
+
+    /// Use regular expressions as patterns instead of the extended query
+    /// syntax.
+    pub fn set_regex(&mut self, regex: bool) {
+        if self.regex != regex {
+            self.regex = regex;
+            let pattern = std::mem::take(&mut self.last_pattern);
+            self.find(&pattern);
+        }
+    }
+
+    /// The error of the last pattern if it is invalid.
+    pub fn query_error(&self) -> Option<&str> {
+        self.query.error.as_deref()
     }
 
     /// Get an injector that can be used to push items into the fuzzy matcher.
@@ -129,19 +303,57 @@ where
     /// The `Matcher` will keep track of the last pattern and only reparse the
     /// pattern if it has changed, allowing for more efficient matching when
     /// `self.last_pattern` is a prefix of the new `pattern`.
+    ///
+    /// See [`query`] for the syntax of patterns.
     pub fn find(&mut self, pattern: &str) {
         if pattern != self.last_pattern {
-            self.inner.pattern.reparse(
-                0,
-                pattern,
-                nucleo::pattern::CaseMatching::Smart,
-                nucleo::pattern::Normalization::Smart,
-                pattern.starts_with(&self.last_pattern),
-            );
+            let query = if self.regex {
+                Query::regex(pattern, &self.config)
+            } else {
+                Query::parse(pattern, &self.config)
+            };
+            if query.pattern() != self.query.pattern() {
+                self.inner.pattern.reparse(
+                    0,
+                    query.pattern(),
+                    self.config.case_matching.into(),
+                    self.config.normalization.into(),
+                    query.pattern().starts_with(self.query.pattern()),
+                );
+                if let Some(tiebreak_pattern) = &self.tiebreak_pattern {
+                    tiebreak_pattern.set(Pattern::parse(
+                        query.pattern(),
+                        self.config.case_matching.into(),
+                        self.config.normalization.into(),
+                    ));
+                }
+            }
+            // previous filtered results are shown until the new filters are
+            // done, nucleo's unfiltered matches never are
+            if !self.query.has_filters() {
+                self.filtered = Some(Vec::new());
+            }
+            self.query = Arc::new(query);
+            self.filter_generation += 1;
+            self.filter_verdicts.clear();
+            self.filters_outdated = true;
             self.last_pattern = pattern.to_string();
         }
     }
 
+    /// Match and sort the items again with the current pattern, e.g. after
+    /// the data used by a custom sort strategy changed.
+    pub fn rescore(&mut self) {
+        self.inner.pattern.reparse(
+            0,
+            self.query.pattern(),
+            self.config.case_matching.into(),
+            self.config.normalization.into(),
+            false,
+        );
+        self.filters_outdated = true;
+    }
+
     /// Get the matched items.
     ///
     /// This should be called to retrieve the matched items after calling
@@ -177,9 +389,14 @@ where
         num_entries: u32,
         offset: u32,
     ) -> Vec<matched_item::MatchedItem<I>> {
+        self.update_filters();
         let snapshot = self.inner.snapshot();
         self.total_item_count = snapshot.item_count();
-        self.matched_item_count = snapshot.matched_item_count();
+        self.matched_item_count = self
+            .filtered()
+            .map_or(snapshot.matched_item_count(), |filtered| {
+                u32::try_from(filtered.len()).unwrap_or(u32::MAX)
+            });
 
         // If the offset is greater than the number of matched items, return an empty Vec
         if offset >= self.matched_item_count {
@@ -195,18 +412,32 @@ where
         // Clear the pre-allocated match indices buffer for safety
         self.col_indices_buffer.clear();
         let mut matcher = lazy::MATCHER.lock();
+        matcher.config = self.config.nucleo_config();
 
         // PERF: Pre-allocate the results Vec so we avoid repeated reallocations
         let mut results = Vec::with_capacity(num_entries as usize);
 
-        for item in snapshot.matched_items(
-            offset..(num_entries + offset).min(self.matched_item_count),
-        ) {
+        let range =
+            offset..(num_entries + offset).min(self.matched_item_count);
+        let items: Vec<_> = match self.filtered() {
+            Some(filtered) => filtered
+                [range.start as usize..range.end as usize]
+                .iter()
+                .filter_map(|&idx| snapshot.get_item(idx))
+                .collect(),
+            None => snapshot.matched_items(range).collect(),
+        };
+        for item in items {
             snapshot.pattern().column_pattern(0).indices(
                 item.matcher_columns[0].slice(..),
                 &mut matcher,
                 &mut self.col_indices_buffer,
             );
+            self.query.indices(
+                item.matcher_columns[0].slice(..),
+                &mut matcher,
+                &mut self.col_indices_buffer,
+            );
 
             // PERF: Avoid unnecessary sorting
             if self.col_indices_buffer.len() > 1 {
@@ -248,16 +479,30 @@ where
         &mut self,
         index: u32,
     ) -> Option<matched_item::MatchedItem<I>> {
+        self.update_filters();
+        let item = match self.filtered() {
+            Some(filtered) => self
+                .inner
+                .snapshot()
+                .get_item(*filtered.get(index as usize)?),
+            None => self.inner.snapshot().get_matched_item(index),
+        };
         let snapshot = self.inner.snapshot();
         let mut matcher = lazy::MATCHER.lock();
+        matcher.config = self.config.nucleo_config();
         self.col_indices_buffer.clear();
 
-        snapshot.get_matched_item(index).map(|item| {
+        item.map(|item| {
             snapshot.pattern().column_pattern(0).indices(
                 item.matcher_columns[0].slice(..),
                 &mut matcher,
                 &mut self.col_indices_buffer,
             );
+            self.query.indices(
+                item.matcher_columns[0].slice(..),
+                &mut matcher,
+                &mut self.col_indices_buffer,
+            );
             self.col_indices_buffer.sort_unstable();
             self.col_indices_buffer.dedup();
 
@@ -282,6 +527,13 @@ where
         self.matched_item_count = 0;
         self.status = Status::default();
         self.last_pattern.clear();
+        self.query = Arc::default();
+        if let Some(tiebreak_pattern) = &self.tiebreak_pattern {
+            tiebreak_pattern.set(Pattern::default());
+        }
+        self.filtered = None;
+        self.filter_verdicts.clear();
+        self.filter_generation += 1;
         self.col_indices_buffer.clear();
     }
 
@@ -296,3 +548,85 @@ where
         self.inner.reverse_items(reverse);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn push(matcher: &Matcher<String>, items: &[&str]) {
+        let injector = matcher.injector();
+        for item in items {
+            injector.push((*item).to_string(), |s, cols| {
+                cols[0] = s.as_str().into();
+            });
+        }
+    }
+
+    fn ranked(matcher: &mut Matcher<String>) -> Vec<String> {
+        matcher.tick();
+        while matcher.status.running {
+            matcher.tick();
+        }
+        matcher
+            .results(u32::MAX, 0)
+            .into_iter()
+            .map(|item| item.inner)
+            .collect()
+    }
+
+    fn results(matcher: &mut Matcher<String>) -> Vec<String> {
+        let mut results = ranked(matcher);
+        results.sort();
+        results
+    }
+
+    #[test]
+    fn test_filters_apply_to_streamed_items() {
+        let mut matcher =
+            Matcher::new(&Config::default(), nucleo::SortStrategy::Score, 1);
+        matcher.find("foo | bar");
+        push(&matcher, &["foo", "baz"]);
+        assert_eq!(results(&mut matcher), vec!["foo"]);
+
+        push(&matcher, &["bar", "qux"]);
+        assert_eq!(results(&mut matcher), vec!["bar", "foo"]);
+        assert_eq!(matcher.matched_item_count, 2);
+
+        matcher.find("qux | baz");
+        assert_eq!(results(&mut matcher), vec!["baz", "qux"]);
+    }
+
+    #[test]
+    fn test_alternatives_are_ranked() {
+        let mut matcher =
+            Matcher::new(&Config::default(), nucleo::SortStrategy::Score, 1);
+        push(&matcher, &["f_o_o", "b_a_r", "bar", "foo", "baz"]);
+        matcher.find("foo | bar");
+        let ranked = ranked(&mut matcher);
+        assert_eq!(ranked.len(), 4);
+        assert!(ranked[..2].contains(&"foo".to_string()));
+        assert!(ranked[..2].contains(&"bar".to_string()));
+    }
+
+    #[test]
+    fn test_large_match_sets_are_filtered_in_background() {
+        let mut matcher =
+            Matcher::new(&Config::default(), nucleo::SortStrategy::Score, 1);
+        let items: Vec<_> = (0..=BACKGROUND_FILTER_THRESHOLD)
+            .map(|i| format!("item {i}"))
+            .collect();
+        let items: Vec<_> = items.iter().map(String::as_str).collect();
+        push(&matcher, &items);
+        matcher.find("item | 42$");
+        assert_eq!(results(&mut matcher).len(), items.len());
+
+        matcher.find("42$ | 4242$");
+        let mut expected: Vec<_> = items
+            .iter()
+            .filter(|item| item.ends_with("42"))
+            .map(ToString::to_string)
+            .collect();
+        expected.sort();
+        assert_eq!(results(&mut matcher), expected);
+    }
+}
@@ -0,0 +1,512 @@
+//! The extended query syntax of the matcher.
+//!
+//! Queries are made of whitespace separated terms that entries must all
+//! match, on top of nucleo's pattern syntax:
+//! - `foo` fuzzy matches `foo`
+//! - `'foo` matches the substring `foo`
+//! - `^foo` / `foo$` match a prefix / suffix, `^foo$` the whole entry
+//! - `!foo` excludes entries containing `foo`
+//! - `"foo bar"` matches a phrase containing spaces, which can be combined
+//!   with the above (e.g. `!^"foo bar"`)
+//! - `foo | bar` matches entries matching either of the terms
+use super::config::{CaseMatching, Config};
+use nucleo::{
+    Matcher, Utf32Str,
+    pattern::{Atom, AtomKind},
+};
+use regex::{Regex, RegexBuilder};
+use std::borrow::Cow;
+
+const OR_OPERATOR: &str = "|";
+
+/// A single term of a query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Term {
+    text: String,
+    kind: AtomKind,
+    negated: bool,
+}
+
+impl Term {
+    /// Parse a raw term, returning whether it ended inside a quoted phrase.
+    ///
+    /// Like in nucleo's syntax, backslashes are only used to escape spaces
+    /// and leading or trailing operators, they are otherwise kept as is.
+    fn parse(raw: &str) -> (Self, bool) {
+        let mut rest = raw;
+        let negated = rest.starts_with('!');
+        if negated {
+            rest = &rest[1..];
+        }
+        let prefix = rest.starts_with('^');
+        let substring = rest.starts_with('\'');
+        if prefix
+            || substring
+            || [r"\!", r"\^", r"\'"].iter().any(|op| rest.starts_with(op))
+        {
+            rest = &rest[1..];
+        }
+
+        let mut text = String::with_capacity(rest.len());
+        let mut quoted = false;
+        let mut suffix = false;
+        let mut unterminated = false;
+        if let Some(phrase) = rest.strip_prefix('"') {
+            quoted = true;
+            unterminated = true;
+            let mut chars = phrase.chars();
+            while let Some(c) = chars.next() {
+                match c {
+                    '\\' if chars.as_str().starts_with('"') => {
+                        text.extend(chars.next());
+                    }
+                    '"' => {
+                        unterminated = false;
+                        suffix = chars.as_str() == "$";
+                        break;
+                    }
+                    c => text.push(c),
+                }
+            }
+        } else {
+            let literal_dollar = rest.ends_with(r"\$");
+            if literal_dollar {
+                rest = &rest[..rest.len() - 2];
+            } else if let Some(stripped) = rest.strip_suffix('$') {
+                suffix = true;
+                rest = stripped;
+            }
+            text.push_str(&rest.replace(r"\ ", " "));
+            if literal_dollar {
+                text.push('$');
+            }
+        }
+
+        let kind = match (prefix, suffix) {
+            (true, true) => AtomKind::Exact,
+            (true, false) => AtomKind::Prefix,
+            (false, true) => AtomKind::Postfix,
+            // negated fuzzy matching would exclude way too many entries
+            _ if substring || quoted || negated => AtomKind::Substring,
+            _ => AtomKind::Fuzzy,
+        };
+        (
+            Self {
+                text,
+                kind,
+                negated,
+            },
+            unterminated,
+        )
+    }
+
+    fn atom(&self, config: &Config) -> Atom {
+        let mut atom = Atom::new(
+            &self.text,
+            config.case_matching.into(),
+            config.normalization.into(),
+            self.kind,
+            false,
+        );
+        atom.negative = self.negated;
+        atom
+    }
+
+    /// Write the term using nucleo's pattern syntax.
+    fn write_pattern(&self, pattern: &mut String) {
+        if self.negated {
+            pattern.push('!');
+        }
+        match self.kind {
+            AtomKind::Prefix | AtomKind::Exact => pattern.push('^'),
+            AtomKind::Substring if !self.negated => pattern.push('\''),
+            _ => {
+                // escape characters nucleo would read as operators
+                if self.text.starts_with(['^', '\''])
+                    || !self.negated && self.text.starts_with('!')
+                {
+                    pattern.push('\\');
+                }
+            }
+        }
+        pattern.push_str(&self.text.replace(' ', r"\ "));
+        if matches!(self.kind, AtomKind::Postfix | AtomKind::Exact) {
+            pattern.push('$');
+        } else if self.text.ends_with('$') {
+            // a literal dollar sign is written `\$` by nucleo
+            pattern.pop();
+            pattern.push_str("\\$");
+        }
+    }
+}
+
+/// Split a query into its raw terms and `|` operators.
+///
+/// Terms are separated by whitespace, unless it is escaped with a backslash
+/// or part of a quoted phrase.
+///
+/// # Example
+/// ```
+/// use television::matcher::query::split_terms;
+///
+/// assert_eq!(
+///     split_terms(r#"foo !"bar baz" | qux\ quux"#),
+///     vec!["foo", r#"!"bar baz""#, "|", r"qux\ quux"]
+/// );
+/// ```
+pub fn split_terms(query: &str) -> Vec<&str> {
+    let mut terms = Vec::new();
+    let mut start = None;
+    let mut quoted = false;
+    let mut escaped = false;
+    for (i, c) in query.char_indices() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match c {
+            '\\' => escaped = true,
+            '"' => quoted = !quoted,
+            c if c.is_whitespace() && !quoted => {
+                if let Some(start) = start.take() {
+                    terms.push(&query[start..i]);
+                }
+                continue;
+            }
+            _ => {}
+        }
+        start.get_or_insert(i);
+    }
+    if let Some(start) = start {
+        terms.push(&query[start..]);
+    }
+    terms
+}
+
+/// A parsed query.
+///
+/// Terms that every entry must match are handed over to nucleo, while groups
+/// of alternatives and regular expressions, which nucleo doesn't support, are
+/// checked against nucleo's matches with [`Query::score`].
+///
+/// Entries matching alternatives are ranked by the score of the best
+/// alternative of each group, regular expressions don't rank entries.
+#[derive(Debug, Clone, Default)]
+pub struct Query {
+    /// The terms every entry must match, using nucleo's pattern syntax.
+    pattern: String,
+    /// Groups of alternatives, entries must match a term of each group.
+    alternatives: Vec<Vec<Atom>>,
+    regex: Option<Regex>,
+    /// Why the query is invalid, if it is.
+    pub error: Option<String>,
+}
+
+impl Query {
+    /// Parse a query using the extended syntax described in the module
+    /// documentation.
+    ///
+    /// Invalid queries are matched as well as possible, e.g. an unterminated
+    /// quoted phrase extends to the end of the query, and `error` is set.
+    pub fn parse(query: &str, config: &Config) -> Self {
+        let mut groups: Vec<Vec<Term>> = Vec::new();
+        let mut error = None;
+        let mut join_next = false;
+        for raw in split_terms(query) {
+            if raw == OR_OPERATOR {
+                join_next = !groups.is_empty();
+                continue;
+            }
+            let (term, unterminated) = Term::parse(raw);
+            if unterminated {
+                error = Some("unterminated quote".to_string());
+            }
+            if term.text.is_empty() {
+                continue;
+            }
+            match groups.last_mut() {
+                Some(group) if join_next => group.push(term),
+                _ => groups.push(vec![term]),
+            }
+            join_next = false;
+        }
+
+        let mut pattern = String::new();
+        let mut alternatives = Vec::new();
+        for mut group in groups {
+            if group.len() > 1 {
+                alternatives.push(
+                    group.iter().map(|term| term.atom(config)).collect(),
+                );
+                continue;
+            }
+            if !pattern.is_empty() {
+                pattern.push(' ');
+            }
+            group.pop().unwrap().write_pattern(&mut pattern);
+        }
+        Self {
+            pattern,
+            alternatives,
+            regex: None,
+            error,
+        }
+    }
+
+    /// Build a query matching entries against a regular expression.
+    ///
+    /// With smart case matching, the regular expression is case insensitive
+    /// unless it contains uppercase characters. Diacritics are always
+    /// matched.
+    pub fn regex(query: &str, config: &Config) -> Self {
+        if query.is_empty() {
+            return Self::default();
+        }
+        let case_insensitive = match config.case_matching {
+            CaseMatching::Smart => !query.chars().any(char::is_uppercase),
+            CaseMatching::Respect => false,
+            CaseMatching::Ignore => true,
+        };
+        match RegexBuilder::new(query)
+            .case_insensitive(case_insensitive)
+            .build()
+        {
+            Ok(regex) => Self {
+                regex: Some(regex),
+                ..Default::default()
+            },
+            Err(_) => Self {
+                error: Some("invalid regex".to_string()),
+                ..Default::default()
+            },
+        }
+    }
+
+    /// The part of the query handled by nucleo, using its pattern syntax.
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Whether entries matched by nucleo need to be filtered with
+    /// [`Query::matches`].
+    pub fn has_filters(&self) -> bool {
+        !self.alternatives.is_empty() || self.regex.is_some()
+    }
+
+    /// Whether `haystack` matches the alternatives and regular expression of
+    /// the query.
+    pub fn matches(
+        &self,
+        haystack: Utf32Str<'_>,
+        matcher: &mut Matcher,
+    ) -> bool {
+        self.score(haystack, matcher).is_some()
+    }
+
+    /// The score of `haystack` against the alternatives of the query, the
+    /// sum of the best score of each group, or `None` if it doesn't match
+    /// the alternatives and regular expression of the query.
+    pub fn score(
+        &self,
+        haystack: Utf32Str<'_>,
+        matcher: &mut Matcher,
+    ) -> Option<u32> {
+        if self
+            .regex
+            .as_ref()
+            .is_some_and(|regex| !regex.is_match(&haystack_str(haystack)))
+        {
+            return None;
+        }
+        self.alternatives.iter().try_fold(0u32, |total, group| {
+            let best = group
+                .iter()
+                .filter_map(|atom| atom.score(haystack, matcher))
+                .max()?;
+            Some(total.saturating_add(u32::from(best)))
+        })
+    }
+
+    /// Push the indices of the characters of `haystack` matched by the
+    /// alternatives and regular expression of the query to `indices`.
+    pub fn indices(
+        &self,
+        haystack: Utf32Str<'_>,
+        matcher: &mut Matcher,
+        indices: &mut Vec<u32>,
+    ) {
+        for group in &self.alternatives {
+            for atom in group {
+                if atom.indices(haystack, matcher, indices).is_some() {
+                    break;
+                }
+            }
+        }
+        if let Some(regex) = &self.regex {
+            let haystack = haystack_str(haystack);
+            for m in regex.find_iter(&haystack) {
+                let start = haystack[..m.start()].chars().count();
+                let len = m.as_str().chars().count();
+                indices.extend(
+                    (start..start + len).filter_map(|i| u32::try_from(i).ok()),
+                );
+            }
+        }
+    }
+}
+
+fn haystack_str(haystack: Utf32Str<'_>) -> Cow<'_, str> {
+    match haystack {
+        Utf32Str::Ascii(bytes) => String::from_utf8_lossy(bytes),
+        Utf32Str::Unicode(chars) => Cow::Owned(chars.iter().collect()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matcher::config::Normalization;
+    use nucleo::Utf32String;
+
+    fn matching<'a>(query: &Query, haystacks: &[&'a str]) -> Vec<&'a str> {
+        let mut matcher = Matcher::default();
+        let pattern = nucleo::pattern::Pattern::parse(
+            query.pattern(),
+            nucleo::pattern::CaseMatching::Smart,
+            nucleo::pattern::Normalization::Smart,
+        );
+        haystacks
+            .iter()
+            .filter(|haystack| {
+                let haystack = Utf32String::from(**haystack);
+                pattern.score(haystack.slice(..), &mut matcher).is_some()
+                    && query.matches(haystack.slice(..), &mut matcher)
+            })
+            .copied()
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_query_pattern() {
+        let query = Query::parse(
+            r#"foo 'bar ^baz qux$ !nope "a phrase" ^"b c"$"#,
+            &Config::default(),
+        );
+        assert_eq!(
+            query.pattern(),
+            r"foo 'bar ^baz qux$ !nope 'a\ phrase ^b\ c$"
+        );
+        assert!(!query.has_filters());
+        assert!(query.error.is_none());
+
+        assert_eq!(
+            Query::parse(r"\!foo bar\$", &Config::default()).pattern(),
+            r"\!foo bar\$"
+        );
+        assert_eq!(
+            Query::parse("! ^ foo", &Config::default()).pattern(),
+            "foo"
+        );
+    }
+
+    #[test]
+    fn test_parse_query_alternatives() {
+        let query = Query::parse("foo | bar baz | qux | ", &Config::default());
+        assert_eq!(query.pattern(), "");
+        assert_eq!(query.alternatives.len(), 2);
+        assert!(query.has_filters());
+
+        let haystacks = ["foo baz", "bar qux", "foo bar", "baz"];
+        assert_eq!(matching(&query, &haystacks), vec!["foo baz", "bar qux"]);
+    }
+
+    #[test]
+    fn test_query_score_alternatives() {
+        let mut matcher = Matcher::default();
+        let query = Query::parse("foo | bar", &Config::default());
+        let mut score = |haystack: &str| {
+            query.score(Utf32String::from(haystack).slice(..), &mut matcher)
+        };
+
+        assert!(score("f_o_o").unwrap() < score("foo").unwrap());
+        assert_eq!(score("foo"), score("bar"));
+        assert_eq!(score("baz"), None);
+        assert_eq!(
+            Query::regex("ba", &Config::default())
+                .score(Utf32String::from("bar").slice(..), &mut matcher),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn test_parse_query_unterminated_quote() {
+        let query = Query::parse(r#"foo "bar baz"#, &Config::default());
+        assert_eq!(query.error.as_deref(), Some("unterminated quote"));
+        assert_eq!(query.pattern(), r"foo 'bar\ baz");
+    }
+
+    #[test]
+    fn test_regex_query() {
+        let query = Query::regex(r"^ba[rz]\d", &Config::default());
+        assert!(query.has_filters());
+        let haystacks = ["bar1", "BAZ2", "foo bar3", "baz"];
+        assert_eq!(matching(&query, &haystacks), vec!["bar1", "BAZ2"]);
+
+        let query = Query::regex("Ba", &Config::default());
+        assert_eq!(matching(&query, &haystacks), Vec::<&str>::new());
+
+        let query = Query::regex("foo(", &Config::default());
+        assert_eq!(query.error.as_deref(), Some("invalid regex"));
+        assert!(!query.has_filters());
+    }
+
+    #[test]
+    fn test_query_case_matching_and_normalization() {
+        let haystacks = ["Café", "cafe", "CAFE"];
+        let config = |case_matching, normalization| Config {
+            case_matching,
+            normalization,
+            ..Config::default()
+        };
+
+        let smart = config(CaseMatching::Smart, Normalization::Smart);
+        let query = Query::parse("cafe | nope", &smart);
+        assert_eq!(matching(&query, &haystacks), vec!["Café", "cafe", "CAFE"]);
+        let query = Query::parse("Cafe | nope", &smart);
+        assert_eq!(matching(&query, &haystacks), vec!["Café"]);
+
+        let respect = config(CaseMatching::Respect, Normalization::Never);
+        let query = Query::parse("cafe | nope", &respect);
+        assert_eq!(matching(&query, &haystacks), vec!["cafe"]);
+        let query = Query::regex("caf", &respect);
+        assert_eq!(matching(&query, &haystacks), vec!["cafe"]);
+
+        let ignore = config(CaseMatching::Ignore, Normalization::Smart);
+        let query = Query::parse("Cafe | nope", &ignore);
+        assert_eq!(matching(&query, &haystacks), vec!["Café", "cafe", "CAFE"]);
+        let query = Query::regex("CAF", &ignore);
+        assert_eq!(matching(&query, &haystacks), vec!["Café", "cafe", "CAFE"]);
+    }
+
+    #[test]
+    fn test_query_indices() {
+        let mut matcher = Matcher::default();
+        let haystack = Utf32String::from("héllo wörld");
+        let mut indices = Vec::new();
+
+        Query::regex("ö.l", &Config::default()).indices(
+            haystack.slice(..),
+            &mut matcher,
+            &mut indices,
+        );
+        assert_eq!(indices, vec![7, 8, 9]);
+
+        indices.clear();
+        Query::parse("nope | 'wö", &Config::default()).indices(
+            haystack.slice(..),
+            &mut matcher,
+            &mut indices,
+        );
+        assert_eq!(indices, vec![6, 7]);
+    }
+}
@@ -0,0 +1,247 @@
+use nucleo::{Item, Match, pattern::Pattern};
+use parking_lot::RwLock;
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+use std::{cell::RefCell, cmp::Ordering, sync::Arc};
+
+/// A criterion used to order matches, similar to fzf's `--tiebreak`.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Hash, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Tiebreak {
+    /// Prefer matches with a higher score.
+    Score,
+    /// Prefer shorter entries.
+    Length,
+    /// Prefer entries matched closer to their beginning.
+    Begin,
+    /// Prefer entries matched closer to their end.
+    End,
+    /// Prefer entries coming first in the source.
+    Index,
+}
+
+impl Tiebreak {
+    /// Whether the criterion depends on the positions of the matched
+    /// characters.
+    pub fn needs_pattern(self) -> bool {
+        matches!(self, Tiebreak::Begin | Tiebreak::End)
+    }
+}
+
+impl From<crate::cli::args::Tiebreak> for Tiebreak {
+    fn from(tiebreak: crate::cli::args::Tiebreak) -> Self {
+        match tiebreak {
+            crate::cli::args::Tiebreak::Score => Tiebreak::Score,
+            crate::cli::args::Tiebreak::Length => Tiebreak::Length,
+            crate::cli::args::Tiebreak::Begin => Tiebreak::Begin,
+            crate::cli::args::Tiebreak::End => Tiebreak::End,
+            crate::cli::args::Tiebreak::Index => Tiebreak::Index,
+        }
+    }
+}
+
+/// The criteria used when none are configured, which is how nucleo sorts
+/// matches by default.
+const DEFAULT_TIEBREAK: &[Tiebreak] = &[Tiebreak::Score, Tiebreak::Length];
+
+thread_local! {
+    /// A matcher and buffer used to locate matches, sorting happens on the
+    /// worker threads of nucleo.
+    static MATCHER: RefCell<(nucleo::Matcher, Vec<u32>)> =
+        RefCell::new((nucleo::Matcher::default(), Vec::new()));
+}
+
+/// The pattern currently matched, along with the margins of the items that
+/// were located with it.
+#[derive(Default)]
+pub struct TiebreakPattern {
+    pattern: RwLock<Pattern>,
+    /// The margins of the items by item index, they only change with the
+    /// pattern.
+    margins: RwLock<FxHashMap<u32, (u32, u32)>>,
+}
+
+impl TiebreakPattern {
+    pub fn new(pattern: Pattern) -> Self {
+        Self {
+            pattern: RwLock::new(pattern),
+            margins: RwLock::default(),
+        }
+    }
+
+    /// Replace the pattern, forgetting the margins located with the
+    /// previous one.
+    pub fn set(&self, pattern: Pattern) {
+        // margins are only stored while holding a read lock on the pattern,
+        // so none of the previous pattern can be stored after clearing them
+        let mut current = self.pattern.write();
+        *current = pattern;
+        self.margins.write().clear();
+    }
+}
+
+/// Compares matches using a chain of [`Tiebreak`]s, falling back to the
+/// order of the source.
+pub struct Tiebreaker {
+    tiebreak: Vec<Tiebreak>,
+    nucleo_config: nucleo::Config,
+    /// The pattern currently matched, kept up to date by the
+    /// [`Matcher`](super::Matcher).
+    pattern: Arc<TiebreakPattern>,
+}
+
+impl Tiebreaker {
+    pub fn new(
+        tiebreak: Vec<Tiebreak>,
+        nucleo_config: nucleo::Config,
+        pattern: Arc<TiebreakPattern>,
+    ) -> Self {
+        Self {
+            tiebreak,
+            nucleo_config,
+            pattern,
+        }
+    }
+
+    pub fn compare<I>(
+        &self,
+        m1: &Match,
+        i1: &Item<'_, I>,
+        m2: &Match,
+        i2: &Item<'_, I>,
+    ) -> Ordering {
+        let tiebreak = if self.tiebreak.is_empty() {
+            DEFAULT_TIEBREAK
+        } else {
+            &self.tiebreak
+        };
+        tiebreak
+            .iter()
+            .map(|tiebreak| match tiebreak {
+                Tiebreak::Score => m2.score.cmp(&m1.score),
+                Tiebreak::Length => i1.matcher_columns[0]
+                    .len()
+                    .cmp(&i2.matcher_columns[0].len()),
+                Tiebreak::Begin => {
+                    self.margins(m1.idx, i1).0.cmp(&self.margins(m2.idx, i2).0)
+                }
+                Tiebreak::End => {
+                    self.margins(m1.idx, i1).1.cmp(&self.margins(m2.idx, i2).1)
+                }
+                Tiebreak::Index => m1.idx.cmp(&m2.idx),
+            })
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or_else(|| m1.idx.cmp(&m2.idx))
+    }
+
+    /// The number of characters before the first matched character of the
+    /// item and after the last one.
+    ///
+    /// Locating the matched characters is expensive, so they are only
+    /// computed once per item and pattern.
+    fn margins<I>(&self, idx: u32, item: &Item<'_, I>) -> (u32, u32) {
+        let pattern = self.pattern.pattern.read();
+        if let Some(margins) = self.pattern.margins.read().get(&idx) {
+            return *margins;
+        }
+        let haystack = item.matcher_columns[0].slice(..);
+        let margins = MATCHER.with_borrow_mut(|(matcher, indices)| {
+            matcher.config = self.nucleo_config.clone();
+            indices.clear();
+            pattern.indices(haystack, matcher, indices);
+            let len = u32::try_from(haystack.len()).unwrap_or(u32::MAX);
+            match (indices.iter().min(), indices.iter().max()) {
+                (Some(first), Some(last)) => (*first, len - 1 - last),
+                _ => (0, 0),
+            }
+        });
+        self.pattern.margins.write().insert(idx, margins);
+        margins
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nucleo::{
+        Utf32String,
+        pattern::{CaseMatching, Normalization},
+    };
+
+    fn sorted(
+        tiebreak: Vec<Tiebreak>,
+        pattern: &str,
+        items: &[&str],
+    ) -> Vec<String> {
+        let pattern =
+            Pattern::parse(pattern, CaseMatching::Smart, Normalization::Smart);
+        let tiebreaker = Tiebreaker::new(
+            tiebreak,
+            nucleo::Config::DEFAULT,
+            Arc::new(TiebreakPattern::new(pattern)),
+        );
+        let columns: Vec<[Utf32String; 1]> = items
+            .iter()
+            .map(|item| [Utf32String::from(*item)])
+            .collect();
+        let mut matches: Vec<Match> = (0..)
+            .zip(&columns)
+            .map(|(idx, _)| Match { score: 0, idx })
+            .collect();
+        matches.sort_by(|m1, m2| {
+            let item = |m: &Match| Item {
+                data: &(),
+                matcher_columns: &columns[m.idx as usize],
+            };
+            tiebreaker.compare(m1, &item(m1), m2, &item(m2))
+        });
+        matches
+            .iter()
+            .map(|m| columns[m.idx as usize][0].to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_tiebreak() {
+        let items = ["src/foo.rs", "foo.rs", "docs/foo.rs.md"];
+        assert_eq!(
+            sorted(vec![Tiebreak::Length], "foo", &items),
+            vec!["foo.rs", "src/foo.rs", "docs/foo.rs.md"]
+        );
+        assert_eq!(
+            sorted(vec![Tiebreak::Begin], "foo", &items),
+            vec!["foo.rs", "src/foo.rs", "docs/foo.rs.md"]
+        );
+        assert_eq!(
+            sorted(vec![Tiebreak::End], "rs", &items),
+            vec!["src/foo.rs", "foo.rs", "docs/foo.rs.md"]
+        );
+        assert_eq!(
+            sorted(vec![Tiebreak::Index], "foo", &items),
+            vec!["src/foo.rs", "foo.rs", "docs/foo.rs.md"]
+        );
+    }
+
+    #[test]
+    fn test_tiebreak_margins_follow_pattern() {
+        let parse = |pattern| {
+            Pattern::parse(pattern, CaseMatching::Smart, Normalization::Smart)
+        };
+        let pattern = Arc::new(TiebreakPattern::new(parse("foo")));
+        let tiebreaker = Tiebreaker::new(
+            vec![Tiebreak::Begin],
+            nucleo::Config::DEFAULT,
+            pattern.clone(),
+        );
+        let columns = [Utf32String::from("src/foo.rs")];
+        let item = Item {
+            data: &(),
+            matcher_columns: &columns,
+        };
+
+        assert_eq!(tiebreaker.margins(0, &item), (4, 3));
+        assert_eq!(tiebreaker.margins(0, &item), (4, 3));
+        pattern.set(parse("rs"));
+        assert_eq!(tiebreaker.margins(0, &item), (8, 0));
+    }
+}
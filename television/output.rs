@@ -0,0 +1,230 @@
+use crate::{app::AppOutput, channels::entry::Entry, cli::args::OutputFormat};
+use anyhow::Result;
+use serde::Serialize;
+use std::io::Write;
+
+#[derive(Debug, Serialize)]
+struct JsonEntry<'a> {
+    raw: &'a str,
+    display: &'a str,
+    output: String,
+}
+
+impl<'a> JsonEntry<'a> {
+    fn new(entry: &'a Entry) -> Result<Self> {
+        Ok(Self {
+            raw: &entry.raw,
+            display: entry.display(),
+            output: entry.output()?,
+        })
+    }
+}
+
+/// The document printed by `--output-format json`.
+#[derive(Debug, Serialize)]
+struct JsonOutput<'a> {
+    channel: &'a str,
+    query: &'a str,
+    source_index: usize,
+    expect_key: Option<&'a str>,
+    entries: Vec<JsonEntry<'a>>,
+}
+
+/// A single line printed by `--output-format jsonl`.
+#[derive(Debug, Serialize)]
+struct JsonLine<'a> {
+    channel: &'a str,
+    query: &'a str,
+    source_index: usize,
+    expect_key: Option<&'a str>,
+    #[serde(flatten)]
+    entry: JsonEntry<'a>,
+}
+
+/// Writes the selection contained in `output` using the given format.
+///
+/// `delimiter` terminates each entry of the `plain` format and defaults to a
+/// newline. When the application exited without a selection, `json` still
+/// writes a document with no entries and the other formats write nothing.
+pub fn write_output<W: Write>(
+    writer: &mut W,
+    output: &AppOutput,
+    format: OutputFormat,
+    delimiter: Option<char>,
+) -> Result<()> {
+    if output.selected_entries.is_none() && format != OutputFormat::Json {
+        return Ok(());
+    }
+    let entries = output.selected_entries.iter().flatten();
+    let expect_key = output.expect_key.as_ref().map(ToString::to_string);
+    let expect_key = expect_key.as_deref();
+
+    match format {
+        OutputFormat::Plain | OutputFormat::Nul => {
+            let terminator = if format == OutputFormat::Nul {
+                '\0'
+            } else {
+                delimiter.unwrap_or('\n')
+            };
+            if let Some(key) = expect_key {
+                write!(writer, "{}{}", key, terminator)?;
+            }
+            for entry in entries {
+                write!(writer, "{}{}", entry.output()?, terminator)?;
+            }
+        }
+        OutputFormat::Json => {
+            let document = JsonOutput {
+                channel: &output.channel,
+                query: &output.query,
+                source_index: output.source_index,
+                expect_key,
+                entries: entries.map(JsonEntry::new).collect::<Result<_>>()?,
+            };
+            serde_json::to_writer(&mut *writer, &document)?;
+            writeln!(writer)?;
+        }
+        OutputFormat::Jsonl => {
+            for entry in entries {
+                let line = JsonLine {
+                    channel: &output.channel,
+                    query: &output.query,
+                    source_index: output.source_index,
+                    expect_key,
+                    entry: JsonEntry::new(entry)?,
+                };
+                serde_json::to_writer(&mut *writer, &line)?;
+                writeln!(writer)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        app::ActionOutcome, channels::prototypes::Template, event::Key,
+        utils::hashmaps::FxIndexSet,
+    };
+
+    fn output(outcome: ActionOutcome) -> AppOutput {
+        AppOutput::new(outcome).with_state(
+            "foo".to_string(),
+            "files".to_string(),
+            1,
+        )
+    }
+
+    fn write(output: &AppOutput, format: OutputFormat) -> String {
+        let mut buffer = Vec::new();
+        write_output(&mut buffer, output, format, None).unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+
+    fn entry() -> Entry {
+        Entry::new("src/main.rs".to_string())
+            .with_display("main.rs".to_string())
+            .with_output(Template::parse("{split:/:-1}").unwrap())
+    }
+
+    #[test]
+    fn test_write_output_plain_with_expect_key() {
+        let output = output(ActionOutcome::EntriesWithExpect(
+            FxIndexSet::from_iter([entry()]),
+            Key::Ctrl('q'),
+        ));
+
+        assert_eq!(write(&output, OutputFormat::Plain), "ctrl-q\nmain.rs\n");
+        assert_eq!(write(&output, OutputFormat::Nul), "ctrl-q\0main.rs\0");
+    }
+
+    #[test]
+    fn test_write_output_custom_delimiter() {
+        let output = output(ActionOutcome::Entries(FxIndexSet::from_iter([
+            Entry::new("commit 1\n\n    message".to_string()),
+        ])));
+        let mut buffer = Vec::new();
+
+        write_output(&mut buffer, &output, OutputFormat::Plain, Some('\0'))
+            .unwrap();
+
+        assert_eq!(buffer, b"commit 1\n\n    message\0");
+    }
+
+    #[test]
+    fn test_write_output_json() {
+        let output = output(ActionOutcome::EntriesWithExpect(
+            FxIndexSet::from_iter([entry()]),
+            Key::Ctrl('q'),
+        ));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&write(&output, OutputFormat::Json)).unwrap();
+
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "channel": "files",
+                "query": "foo",
+                "source_index": 1,
+                "expect_key": "ctrl-q",
+                "entries": [
+                    {"raw": "src/main.rs", "display": "main.rs", "output": "main.rs"}
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn test_write_output_jsonl() {
+        let output = output(ActionOutcome::Entries(FxIndexSet::from_iter([
+            entry(),
+            Entry::new("README.md".to_string()),
+        ])));
+
+        let written = write(&output, OutputFormat::Jsonl);
+        let mut lines = written
+            .lines()
+            .map(|l| serde_json::from_str::<serde_json::Value>(l).unwrap())
+            .collect::<Vec<_>>();
+        lines.sort_by_key(|l| l["raw"].as_str().unwrap().to_string());
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(
+            lines[0],
+            serde_json::json!({
+                "channel": "files",
+                "query": "foo",
+                "source_index": 1,
+                "expect_key": null,
+                "raw": "README.md",
+                "display": "README.md",
+                "output": "README.md"
+            })
+        );
+        assert_eq!(lines[1]["output"], "main.rs");
+    }
+
+    #[test]
+    fn test_write_output_without_selection() {
+        let output = output(ActionOutcome::None);
+
+        let json: serde_json::Value =
+            serde_json::from_str(&write(&output, OutputFormat::Json)).unwrap();
+
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "channel": "files",
+                "query": "foo",
+                "source_index": 1,
+                "expect_key": null,
+                "entries": []
+            })
+        );
+        assert_eq!(write(&output, OutputFormat::Jsonl), "");
+        assert_eq!(write(&output, OutputFormat::Plain), "");
+    }
+}
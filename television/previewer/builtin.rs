@@ -0,0 +1,219 @@
+use std::{fs::File, path::Path, sync::LazyLock};
+
+use anyhow::Result;
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span, Text},
+};
+use syntect::{
+    easy::HighlightLines,
+    highlighting::{FontStyle, Theme, ThemeSet},
+    parsing::SyntaxSet,
+};
+
+use crate::{
+    channels::{
+        entry::Entry,
+        prototypes::{BuiltinPreview, Template},
+    },
+    utils::files::{FileType, ReadResult, read_into_lines_capped},
+};
+
+/// Files are only read up to this size for previews.
+const MAX_PREVIEW_BYTES: usize = 1024 * 1024;
+/// Lines longer than this (e.g. minified files) are not highlighted.
+const MAX_HIGHLIGHTED_LINE_LENGTH: usize = 2048;
+const DEFAULT_THEME: &str = "base16-ocean.dark";
+
+static SYNTAX_SET: LazyLock<SyntaxSet> =
+    LazyLock::new(SyntaxSet::load_defaults_nonewlines);
+static THEME: LazyLock<Theme> = LazyLock::new(|| {
+    ThemeSet::load_defaults()
+        .themes
+        .remove(DEFAULT_THEME)
+        .unwrap_or_default()
+});
+
+/// Renders previews in-process instead of spawning a preview command.
+#[derive(Debug, Clone)]
+pub struct BuiltinPreviewer {
+    pub kind: BuiltinPreview,
+    /// Extracts the path to preview from an entry, the raw entry is used
+    /// when not set.
+    pub path: Option<Template>,
+}
+
+impl BuiltinPreviewer {
+    pub fn new(kind: BuiltinPreview, path: Option<Template>) -> Self {
+        Self { kind, path }
+    }
+
+    /// The path of the file to preview for `entry`.
+    pub fn path(&self, entry: &Entry) -> Result<String> {
+        match &self.path {
+            Some(template) => template.format(&entry.raw),
+            None => Ok(entry.raw.clone()),
+        }
+    }
+
+    pub fn render(&self, path: &Path) -> Text<'static> {
+        match self.kind {
+            BuiltinPreview::Text => text_preview(path),
+        }
+    }
+}
+
+fn message(text: &str) -> Text<'static> {
+    Text::from(Line::styled(
+        text.to_string(),
+        Style::default().fg(Color::DarkGray).italic(),
+    ))
+}
+
+/// Reads `path` and highlights its syntax, prefixing every line with its
+/// number the same way `bat -n` does.
+pub fn text_preview(path: &Path) -> Text<'static> {
+    if path.is_dir() {
+        return message("Directory, no preview available");
+    }
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(e) => return message(&format!("Unable to open file: {e}")),
+    };
+    if file.metadata().is_ok_and(|m| m.len() == 0) {
+        return Text::default();
+    }
+    match FileType::from(path) {
+        FileType::Text => {}
+        FileType::Image => return message("Image file, no preview available"),
+        FileType::Other | FileType::Unknown => {
+            return message("Binary file, no preview available");
+        }
+    }
+    let (lines, truncated) =
+        match read_into_lines_capped(file, MAX_PREVIEW_BYTES) {
+            ReadResult::Full(lines) => (lines, false),
+            ReadResult::Partial(partial) => (partial.lines, true),
+            ReadResult::Error(e) => {
+                return message(&format!("Unable to read file: {e}"));
+            }
+        };
+
+    let syntax = SYNTAX_SET
+        .find_syntax_for_file(path)
+        .ok()
+        .flatten()
+        .or_else(|| {
+            lines
+                .first()
+                .and_then(|line| SYNTAX_SET.find_syntax_by_first_line(line))
+        })
+        .unwrap_or_else(|| SYNTAX_SET.find_syntax_plain_text());
+    let mut highlighter = HighlightLines::new(syntax, &THEME);
+    let line_number_style = Style::default().fg(Color::DarkGray);
+    let line_number_width = lines.len().to_string().len();
+
+    let mut text = Vec::with_capacity(lines.len() + 1);
+    for (i, line) in lines.into_iter().enumerate() {
+        let mut spans = vec![Span::styled(
+            format!("{:>line_number_width$} ", i + 1),
+            line_number_style,
+        )];
+        let regions = if line.len() > MAX_HIGHLIGHTED_LINE_LENGTH {
+            None
+        } else {
+            highlighter.highlight_line(&line, &SYNTAX_SET).ok()
+        };
+        match regions {
+            Some(regions) => {
+                spans.extend(regions.into_iter().map(|(style, content)| {
+                    Span::styled(content.to_string(), convert_style(style))
+                }));
+            }
+            None => spans.push(Span::raw(line)),
+        }
+        text.push(Line::from(spans));
+    }
+    if truncated {
+        text.extend(message("[file truncated]").lines);
+    }
+    Text::from(text)
+}
+
+/// Converts a syntect style to a ratatui one, leaving the background to the
+/// preview panel.
+fn convert_style(style: syntect::highlighting::Style) -> Style {
+    let mut modifier = Modifier::empty();
+    if style.font_style.contains(FontStyle::BOLD) {
+        modifier |= Modifier::BOLD;
+    }
+    if style.font_style.contains(FontStyle::ITALIC) {
+        modifier |= Modifier::ITALIC;
+    }
+    if style.font_style.contains(FontStyle::UNDERLINE) {
+        modifier |= Modifier::UNDERLINED;
+    }
+    Style::default()
+        .fg(Color::Rgb(
+            style.foreground.r,
+            style.foreground.g,
+            style.foreground.b,
+        ))
+        .add_modifier(modifier)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plain(line: &Line) -> String {
+        line.spans.iter().map(|s| s.content.as_ref()).collect()
+    }
+
+    #[test]
+    fn test_text_preview_numbers_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("main.rs");
+        std::fs::write(&path, "fn main() {\n    println!(\"hi\");\n}\n")
+            .unwrap();
+
+        let text = text_preview(&path);
+
+        assert_eq!(text.lines.len(), 3);
+        assert_eq!(plain(&text.lines[0]), "1 fn main() {");
+        assert_eq!(plain(&text.lines[2]), "3 }");
+        // highlighted lines are split into several spans
+        assert!(text.lines[0].spans.len() > 2);
+    }
+
+    #[test]
+    fn test_text_preview_binary_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("data");
+        std::fs::write(&path, [0u8, 159, 146, 150, 1, 2, 3, 0, 0, 255])
+            .unwrap();
+
+        let text = text_preview(&path);
+
+        assert_eq!(text.lines.len(), 1);
+        assert_eq!(plain(&text.lines[0]), "Binary file, no preview available");
+    }
+
+    #[test]
+    fn test_text_preview_missing_file() {
+        let text = text_preview(Path::new("does/not/exist.txt"));
+
+        assert!(plain(&text.lines[0]).starts_with("Unable to open file"));
+    }
+
+    #[test]
+    fn test_builtin_previewer_path_template() {
+        let previewer = BuiltinPreviewer::new(
+            BuiltinPreview::Text,
+            Some(Template::parse("{split:\\::0}").unwrap()),
+        );
+        let entry = Entry::new("src/main.rs:12:fn main".to_string());
+
+        assert_eq!(previewer.path(&entry).unwrap(), "src/main.rs");
+    }
+}
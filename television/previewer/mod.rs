@@ -1,5 +1,7 @@
 use std::{
     cmp::Ordering,
+    hash::{Hash, Hasher},
+    path::Path,
     sync::Arc,
     time::{Duration, Instant},
 };
@@ -8,10 +10,11 @@ use ansi_to_tui::IntoText;
 use anyhow::{Context, Result};
 use parking_lot::Mutex;
 use ratatui::text::Text;
+use rustc_hash::FxHasher;
 use tokio::process::Command as TokioCommand;
 use tokio::{
     sync::mpsc::{UnboundedReceiver, UnboundedSender},
-    task::spawn,
+    task::{JoinHandle, spawn},
     time::timeout,
 };
 use tracing::{debug, trace, warn};
@@ -21,9 +24,10 @@ use crate::{
         entry::Entry,
         prototypes::{CommandSpec, Template},
     },
-    previewer::cache::Cache,
+    previewer::{builtin::BuiltinPreviewer, cache::Cache},
     utils::{
-        command::shell_command,
+        command::{format_selection, shell_command},
+        hashmaps::FxIndexSet,
         strings::{
             EMPTY_STRING, ReplaceNonPrintableConfig,
             replace_non_printable_bulk,
@@ -31,6 +35,7 @@ use crate::{
     },
 };
 
+pub mod builtin;
 mod cache;
 pub mod state;
 
@@ -83,6 +88,8 @@ impl Ord for Request {
 #[derive(PartialEq, Eq)]
 pub struct Ticket {
     entry: Entry,
+    /// The selected entries, substituted for `{+}` in preview commands.
+    selection: FxIndexSet<Entry>,
     timestamp: Instant,
 }
 
@@ -102,10 +109,16 @@ impl Ticket {
     pub fn new(entry: Entry) -> Self {
         Self {
             entry,
+            selection: FxIndexSet::default(),
             timestamp: Instant::now(),
         }
     }
 
+    pub fn with_selection(mut self, selection: FxIndexSet<Entry>) -> Self {
+        self.selection = selection;
+        self
+    }
+
     fn age(&self) -> Duration {
         Instant::now().duration_since(self.timestamp)
     }
@@ -124,6 +137,19 @@ pub struct Preview {
     pub footer: Option<String>,
     pub preview_index: usize,
     pub preview_count: usize,
+    /// Identifies the selection the preview was generated for, see
+    /// `selection_id`.
+    pub selection_id: u64,
+}
+
+/// Identifies a set of selected entries (including their order) so that
+/// previews using `{+}` are refreshed when the selection changes.
+pub fn selection_id(selection: &FxIndexSet<Entry>) -> u64 {
+    let mut hasher = FxHasher::default();
+    for entry in selection {
+        entry.raw.hash(&mut hasher);
+    }
+    hasher.finish()
 }
 
 const DEFAULT_PREVIEW_TITLE: &str = "Select an entry to preview";
@@ -140,6 +166,7 @@ impl Default for Preview {
             footer: None,
             preview_index: 0,
             preview_count: 1,
+            selection_id: 0,
         }
     }
 }
@@ -167,6 +194,7 @@ impl Preview {
             footer,
             preview_index,
             preview_count,
+            selection_id: 0,
         }
     }
 }
@@ -176,12 +204,19 @@ pub struct Previewer {
     requests_tx: UnboundedSender<Request>,
     requests_rx: UnboundedReceiver<Request>,
     last_job_entry: Option<Entry>,
+    last_job_selection: FxIndexSet<Entry>,
     command: CommandSpec,
+    /// Renders previews natively instead of running `command`.
+    builtin: Option<BuiltinPreviewer>,
     /// The current cycle index for commands with multiple variants.
     cycle_index: usize,
     title_template: Option<Template>,
     footer_template: Option<Template>,
     offset_expr: Option<Template>,
+    /// Separator used to join the selected entries substituted for `{+}`.
+    selection_separator: String,
+    /// Run every command at once instead of the one being cycled to.
+    split: bool,
     results: UnboundedSender<Preview>,
     cache: Option<Arc<Mutex<Cache>>>,
 }
@@ -190,9 +225,12 @@ impl Previewer {
     #[allow(clippy::too_many_arguments)]
     pub fn new(
         command: &CommandSpec,
+        builtin: Option<BuiltinPreviewer>,
         offset_expr: Option<Template>,
         title_template: Option<Template>,
         footer_template: Option<Template>,
+        selection_separator: String,
+        split: bool,
         config: Config,
         requests_rx: UnboundedReceiver<Request>,
         requests_tx: UnboundedSender<Request>,
@@ -209,11 +247,15 @@ impl Previewer {
             requests_tx,
             requests_rx,
             last_job_entry: None,
+            last_job_selection: FxIndexSet::default(),
             command: command.clone(),
+            builtin,
             cycle_index: 0,
             title_template,
             footer_template,
             offset_expr,
+            selection_separator,
+            split,
             results: results_tx,
             cache,
         }
@@ -232,51 +274,42 @@ impl Previewer {
                             debug!("Preview request is stale, skipping");
                             continue;
                         }
-                        let results_handle = self.results.clone();
                         self.last_job_entry = Some(ticket.entry.clone());
-                        let preview_command = self.command.clone();
-                        let cache = self.cache.clone();
-                        let offset_expr = self.offset_expr.clone();
-                        let title_template = self.title_template.clone();
-                        let footer_template = self.footer_template.clone();
-                        let job = spawn(try_preview(
-                            preview_command,
-                            self.cycle_index,
-                            title_template,
-                            footer_template,
-                            offset_expr,
-                            ticket.entry,
-                            results_handle,
-                            cache,
-                        ));
-                        let abort_handle = job.abort_handle();
-                        match timeout(self.config.job_timeout, job).await {
-                            Ok(Ok(Ok(()))) => {
-                                trace!("Preview job completed successfully");
-                            }
-                            Ok(Ok(Err(e))) => warn!(
-                                "Failed to generate preview for entry '{}': {}",
-                                &self.last_job_entry.clone().unwrap().raw,
-                                e
-                            ),
-                            Ok(Err(join_err)) => {
-                                warn!(
-                                    "Preview join error for '{}': {}",
-                                    self.last_job_entry.clone().unwrap().raw,
-                                    join_err
-                                );
-                            }
-                            Err(_) => {
-                                warn!(
-                                    "Preview job for '{}' timed out after {:?}, aborting",
-                                    self.last_job_entry.clone().unwrap().raw,
-                                    self.config.job_timeout
-                                );
-                                // Cancel the detached task. Combined with
-                                // `kill_on_drop(true)` on the preview command,
-                                // this also kills the spawned child process.
-                                abort_handle.abort();
-                            }
+                        self.last_job_selection.clone_from(&ticket.selection);
+                        // a split preview renders every command in its own
+                        // pane, each of them being a separate job
+                        let indices = if self.split {
+                            0..self.command.inner.len()
+                        } else {
+                            self.cycle_index..self.cycle_index + 1
+                        };
+                        let jobs = indices
+                            .map(|index| {
+                                spawn(try_preview(
+                                    self.command.clone(),
+                                    self.builtin.clone(),
+                                    index,
+                                    self.title_template.clone(),
+                                    self.footer_template.clone(),
+                                    self.offset_expr.clone(),
+                                    ticket.entry.clone(),
+                                    ticket.selection.clone(),
+                                    self.selection_separator.clone(),
+                                    self.results.clone(),
+                                    self.cache.clone(),
+                                ))
+                            })
+                            .collect::<Vec<_>>();
+                        // jobs run concurrently and share the same timeout
+                        let started = Instant::now();
+                        for job in jobs {
+                            self.await_job(
+                                job,
+                                self.config
+                                    .job_timeout
+                                    .saturating_sub(started.elapsed()),
+                            )
+                            .await;
                         }
                     }
                     Request::CycleCommand => {
@@ -299,13 +332,51 @@ impl Previewer {
         }
     }
 
+    async fn await_job(
+        &self,
+        job: JoinHandle<Result<()>>,
+        job_timeout: Duration,
+    ) {
+        let abort_handle = job.abort_handle();
+        match timeout(job_timeout, job).await {
+            Ok(Ok(Ok(()))) => {
+                trace!("Preview job completed successfully");
+            }
+            Ok(Ok(Err(e))) => warn!(
+                "Failed to generate preview for entry '{}': {}",
+                &self.last_job_entry.clone().unwrap().raw,
+                e
+            ),
+            Ok(Err(join_err)) => {
+                warn!(
+                    "Preview join error for '{}': {}",
+                    self.last_job_entry.clone().unwrap().raw,
+                    join_err
+                );
+            }
+            Err(_) => {
+                warn!(
+                    "Preview job for '{}' timed out after {:?}, aborting",
+                    self.last_job_entry.clone().unwrap().raw,
+                    self.config.job_timeout
+                );
+                // Cancel the detached task. Combined with
+                // `kill_on_drop(true)` on the preview command,
+                // this also kills the spawned child process.
+                abort_handle.abort();
+            }
+        }
+    }
+
     pub fn cycle_command(&mut self) {
-        self.cycle_index = (self.cycle_index + 1) % self.command.inner.len();
+        self.cycle_index =
+            (self.cycle_index + 1) % self.command.inner.len().max(1);
         // re-request preview for the last entry if any
         if let Some(entry) = &self.last_job_entry {
-            let _ = self
-                .requests_tx
-                .send(Request::Preview(Ticket::new(entry.clone())));
+            let _ = self.requests_tx.send(Request::Preview(
+                Ticket::new(entry.clone())
+                    .with_selection(self.last_job_selection.clone()),
+            ));
         }
     }
 }
@@ -373,24 +444,55 @@ fn build_preview_from_text(
 #[allow(clippy::too_many_arguments)]
 pub async fn try_preview(
     command: CommandSpec,
+    builtin: Option<BuiltinPreviewer>,
     cycle_index: usize,
     title_template: Option<Template>,
     footer_template: Option<Template>,
     offset_expr: Option<Template>,
     entry: Entry,
+    selection: FxIndexSet<Entry>,
+    selection_separator: String,
     results_handle: UnboundedSender<Preview>,
     cache: Option<Arc<Mutex<Cache>>>,
 ) -> Result<()> {
-    let preview_count = command.inner.len();
-    let formatted_command =
-        command.get_nth(cycle_index).template().format(&entry.raw)?;
+    let selection_id = selection_id(&selection);
+    // builtin previews are keyed on the kind of preview and the file path
+    let (preview_count, formatted_command) = if let Some(builtin) = &builtin {
+        (
+            1,
+            format!("{} {}", builtin.kind.as_str(), builtin.path(&entry)?),
+        )
+    } else {
+        let template = command.get_nth(cycle_index).template();
+        // `{+}` falls back to the current entry when nothing is selected.
+        // The selection ends up in the formatted command, which makes it
+        // part of the cache key.
+        let formatted_command = if template.uses_selection() {
+            let selection = if selection.is_empty() {
+                FxIndexSet::from_iter([entry.clone()])
+            } else {
+                selection
+            };
+            template.format_with_selection(
+                &entry.raw,
+                &format_selection(
+                    &selection,
+                    &selection_separator,
+                    command.shell,
+                ),
+            )?
+        } else {
+            template.format(&entry.raw)?
+        };
+        (command.inner.len(), formatted_command)
+    };
 
     // Check if the entry is already cached
     if let Some(cache) = &cache
         && let Some(text) = cache.lock().get(&formatted_command)
     {
         trace!("Preview for command '{}' found in cache", formatted_command);
-        let preview = build_preview_from_text(
+        let mut preview = build_preview_from_text(
             &formatted_command,
             &entry,
             text,
@@ -400,40 +502,48 @@ pub async fn try_preview(
             cycle_index,
             preview_count,
         )?;
+        preview.selection_id = selection_id;
         results_handle.send(preview).with_context(
             || "Failed to send cached preview result to main thread.",
         )?;
         return Ok(());
     }
 
-    debug!("Executing preview command: {}", &formatted_command);
-    let shell_cmd = shell_command(
-        &formatted_command,
-        command.interactive,
-        &command.env,
-        command.shell,
-    );
-
-    let mut tokio_command = TokioCommand::from(shell_cmd);
-    // Ensure the child process is killed if this task is dropped/aborted
-    tokio_command.kill_on_drop(true);
-    let child = tokio_command.output().await?;
-
-    let mut text = if child.status.success() {
-        child
-            .stdout
-            .into_text()
-            .unwrap_or_else(|_| Text::from(EMPTY_STRING))
+    let mut text = if let Some(builtin) = builtin {
+        let path = builtin.path(&entry)?;
+        debug!("Rendering builtin preview for: {}", &path);
+        tokio::task::spawn_blocking(move || builtin.render(Path::new(&path)))
+            .await?
     } else {
-        child
-            .stderr
-            .into_text()
-            .unwrap_or_else(|_| Text::from(EMPTY_STRING))
+        debug!("Executing preview command: {}", &formatted_command);
+        let shell_cmd = shell_command(
+            &formatted_command,
+            command.interactive,
+            &command.env,
+            command.shell,
+        );
+
+        let mut tokio_command = TokioCommand::from(shell_cmd);
+        // Ensure the child process is killed if this task is dropped/aborted
+        tokio_command.kill_on_drop(true);
+        let child = tokio_command.output().await?;
+
+        if child.status.success() {
+            child
+                .stdout
+                .into_text()
+                .unwrap_or_else(|_| Text::from(EMPTY_STRING))
+        } else {
+            child
+                .stderr
+                .into_text()
+                .unwrap_or_else(|_| Text::from(EMPTY_STRING))
+        }
     };
 
     sanitize_text(&mut text);
 
-    let preview = if let Some(cache) = &cache {
+    let mut preview = if let Some(cache) = &cache {
         let preview = build_preview_from_text(
             &formatted_command,
             &entry,
@@ -458,6 +568,7 @@ pub async fn try_preview(
             preview_count,
         )?
     };
+    preview.selection_id = selection_id;
     // FIXME: ... and just send an Arc here as well
     results_handle
         .send(preview)
@@ -2,7 +2,9 @@ use crate::event::Key;
 use crate::utils::strings::SPACE;
 use crate::{
     action::{Action, Actions},
-    config::{Keybindings, layers::MergedConfig},
+    config::{
+        Keybindings, keybindings::key_sequence_to_string, layers::MergedConfig,
+    },
     screen::colors::Colorscheme,
     television::Mode,
     utils::strings::to_title_case,
@@ -73,6 +75,15 @@ fn is_action_relevant_for_mode(action: &Action, mode: Mode) -> bool {
                 | Action::GoToNextChar
                 | Action::GoToInputStart
                 | Action::GoToInputEnd
+                | Action::GoToPrevWord
+                | Action::GoToNextWord
+                | Action::DeleteNextWord
+                | Action::DeleteToInputEnd
+                | Action::TransposeChars
+                | Action::Yank
+                | Action::YankPop
+                | Action::Undo
+                | Action::Redo
                 // Navigation actions - available in both modes
                 | Action::SelectNextEntry
                 | Action::SelectPrevEntry
@@ -95,13 +106,18 @@ fn is_action_relevant_for_mode(action: &Action, mode: Mode) -> bool {
                 | Action::CyclePreviews
                 | Action::SelectPrevHistory
                 | Action::SelectNextHistory
+                | Action::ForgetEntry
                 // UI toggles - global
                 | Action::ToggleRemoteControl
                 | Action::ToggleActionPicker
+                | Action::ToggleHistoryPicker
                 | Action::ToggleHelp
                 | Action::ToggleStatusBar
                 // Channel-mode layout
                 | Action::ToggleOrientation
+                // Modal editing - global
+                | Action::EnterNormalMode
+                | Action::EnterInsertMode
                 // Application actions - global
                 | Action::Quit
                 // External actions
@@ -118,6 +134,8 @@ fn is_action_relevant_for_mode(action: &Action, mode: Mode) -> bool {
                 | Action::Error(_)
                 | Action::OpenEntry
                 | Action::SwitchToChannel(_)
+                | Action::SetQuery(_)
+                | Action::ChangePreview(_)
                 | Action::WatchTimer
                 | Action::SelectEntryAtPosition(_, _)
                 | Action::MouseClickAt(_, _)
@@ -125,8 +143,8 @@ fn is_action_relevant_for_mode(action: &Action, mode: Mode) -> bool {
                 | Action::SelectAndExit => false,
             }
         }
-        Mode::RemoteControl => {
-            // Remote control mode - limited set of actions
+        Mode::RemoteControl | Mode::HistoryPicker => {
+            // Remote control and history picker modes - limited set of actions
             match action {
                 // Input actions - available in both modes
                 Action::AddInputChar(_)
@@ -138,6 +156,15 @@ fn is_action_relevant_for_mode(action: &Action, mode: Mode) -> bool {
                 | Action::GoToNextChar
                 | Action::GoToInputStart
                 | Action::GoToInputEnd
+                | Action::GoToPrevWord
+                | Action::GoToNextWord
+                | Action::DeleteNextWord
+                | Action::DeleteToInputEnd
+                | Action::TransposeChars
+                | Action::Yank
+                | Action::YankPop
+                | Action::Undo
+                | Action::Redo
                 // Navigation actions - available in both modes
                 | Action::SelectNextEntry
                 | Action::SelectPrevEntry
@@ -147,8 +174,12 @@ fn is_action_relevant_for_mode(action: &Action, mode: Mode) -> bool {
                 | Action::ConfirmSelection
                 // UI toggles - global
                 | Action::ToggleRemoteControl
+                | Action::ToggleHistoryPicker
                 | Action::ToggleHelp
                 | Action::ToggleStatusBar
+                // Modal editing - global
+                | Action::EnterNormalMode
+                | Action::EnterInsertMode
                 // Application actions - global
                 | Action::Quit => true,
 
@@ -170,7 +201,14 @@ fn add_keybinding_lines_for_keys(
     // Collect all valid keybinding entries
     let mut entries: Vec<(String, String)> = Vec::new();
 
-    for (key, actions) in keybindings.iter() {
+    let bindings =
+        keybindings
+            .iter()
+            .map(|(key, actions)| (key.to_string(), actions))
+            .chain(keybindings.sequences.iter().map(|(keys, actions)| {
+                (key_sequence_to_string(keys), actions)
+            }));
+    for (key_string, actions) in bindings {
         for action in actions.as_slice() {
             // Filter out NoOp actions (unbound keys)
             // Filter out actions not relevant for current mode
@@ -181,7 +219,6 @@ fn add_keybinding_lines_for_keys(
             }
 
             let description = action.description();
-            let key_string = key.to_string();
             entries.push((description.to_string(), key_string.clone()));
             trace!(
                 "Added keybinding: {} -> {} ({})",
@@ -271,6 +308,7 @@ fn generate_help_content(
         Mode::Channel => "Channel Mode",
         Mode::RemoteControl => "Remote Control Mode",
         Mode::ActionPicker => "Action Picker Mode",
+        Mode::HistoryPicker => "History Picker Mode",
     };
 
     lines.push(Line::from(vec![Span::styled(
@@ -281,15 +319,22 @@ fn generate_help_content(
             .underlined(),
     )]));
 
+    let global_keybindings = &config.input_map.global_keybindings;
+    let mut keybindings: Keybindings = global_keybindings
+        .iter()
+        .map(|(key, actions)| (*key, actions.first().unwrap().clone()))
+        .collect::<Vec<_>>()
+        .into();
+    keybindings.sequences = global_keybindings
+        .sequences
+        .iter()
+        .map(|(keys, actions)| {
+            (keys.clone(), actions.first().unwrap().clone().into())
+        })
+        .collect();
     add_keybinding_lines_for_keys(
         &mut lines,
-        &config
-            .input_map
-            .global_keybindings
-            .iter()
-            .map(|(key, actions)| (*key, actions.first().unwrap().clone()))
-            .collect::<Vec<_>>()
-            .into(),
+        &keybindings,
         mode,
         colorscheme,
         mode_name,
@@ -339,7 +384,9 @@ fn create_compact_keybinding_line(
 ) -> Line<'static> {
     // Use the appropriate mode color
     let key_color = match mode {
-        Mode::Channel | Mode::ActionPicker => colorscheme.mode.channel,
+        Mode::Channel | Mode::ActionPicker | Mode::HistoryPicker => {
+            colorscheme.mode.channel
+        }
         Mode::RemoteControl => colorscheme.mode.remote_control,
     };
 
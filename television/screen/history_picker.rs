@@ -0,0 +1,125 @@
+use crate::{
+    channels::history_picker::HistoryPickerEntry,
+    screen::{colors::Colorscheme, result_item},
+    utils::input::Input,
+};
+use anyhow::Result;
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    prelude::{Line, Span, Style},
+    style::Stylize,
+    widgets::{
+        Block, BorderType, Borders, Clear, ListDirection, ListState, Padding,
+        Paragraph,
+    },
+};
+
+pub fn draw_history_picker(
+    f: &mut Frame,
+    rect: Rect,
+    entries: &[HistoryPickerEntry],
+    picker_state: &mut ListState,
+    input_state: &mut Input,
+    colorscheme: &Colorscheme,
+) -> Result<()> {
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Fill(1), Constraint::Length(3)].as_ref())
+        .split(rect);
+
+    f.render_widget(Clear, rect);
+
+    draw_history_list(f, layout[0], entries, picker_state, colorscheme);
+    draw_input(f, layout[1], input_state, colorscheme)
+}
+
+fn draw_history_list(
+    f: &mut Frame,
+    area: Rect,
+    entries: &[HistoryPickerEntry],
+    picker_state: &mut ListState,
+    colorscheme: &Colorscheme,
+) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(colorscheme.general.border_fg))
+        .title_top(
+            Line::from(" History ")
+                .alignment(Alignment::Center)
+                .italic(),
+        )
+        .style(
+            Style::default()
+                .bg(colorscheme.general.background.unwrap_or_default()),
+        )
+        .padding(Padding::right(1));
+
+    let history_list = result_item::build_results_list(
+        block,
+        entries,
+        picker_state,
+        ListDirection::TopToBottom,
+        &colorscheme.results,
+        area.width,
+        |_| None,
+    );
+
+    f.render_stateful_widget(history_list, area, picker_state);
+}
+
+fn draw_input(
+    f: &mut Frame,
+    area: Rect,
+    input: &mut Input,
+    colorscheme: &Colorscheme,
+) -> Result<()> {
+    let input_block = Block::default()
+        .title_top(
+            Line::from(" Search ").alignment(Alignment::Center).italic(),
+        )
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(colorscheme.general.border_fg))
+        .style(
+            Style::default()
+                .bg(colorscheme.general.background.unwrap_or_default()),
+        );
+
+    let input_block_inner = input_block.inner(area);
+
+    f.render_widget(input_block, area);
+
+    let inner_input_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Length(2), Constraint::Fill(1)])
+        .split(input_block_inner);
+
+    let arrow = Paragraph::new(Span::styled(
+        "> ",
+        Style::default().fg(colorscheme.input.input_fg).bold(),
+    ));
+    f.render_widget(arrow, inner_input_chunks[0]);
+
+    // keep 2 for borders and 1 for cursor
+    let width = inner_input_chunks[1].width.max(3) - 3;
+    let scroll = input.visual_scroll(width as usize);
+    let input_paragraph = Paragraph::new(input.value())
+        .scroll((0, u16::try_from(scroll)?))
+        .style(
+            Style::default()
+                .fg(colorscheme.input.input_fg)
+                .bold()
+                .italic(),
+        )
+        .alignment(Alignment::Left);
+    f.render_widget(input_paragraph, inner_input_chunks[1]);
+
+    f.set_cursor_position((
+        inner_input_chunks[1].x
+            + u16::try_from(input.visual_cursor().max(scroll) - scroll)?,
+        inner_input_chunks[1].y,
+    ));
+    Ok(())
+}
@@ -18,6 +18,7 @@ use ratatui::{
 };
 
 const LOADING_CHAR: &str = "●";
+const ERROR_CHAR: &str = "✗";
 
 #[allow(clippy::too_many_arguments)]
 pub fn draw_input_box(
@@ -28,6 +29,7 @@ pub fn draw_input_box(
     input_state: &Input,
     results_picker_state: &ListState,
     matcher_running: bool,
+    query_error: Option<&str>,
     channel_name: &str,
     colorscheme: &Colorscheme,
     position: InputPosition,
@@ -66,7 +68,8 @@ pub fn draw_input_box(
 
     f.render_widget(input_block, rect);
 
-    // split input block into 4 parts: prompt symbol, input, result count, indicator
+    // split input block into 5 parts: prompt symbol, input, query error,
+    // result count, indicator
     let indicator_len = if matcher_running { 2 } else { 0 };
     let constraints = [
         // prompt symbol + space
@@ -81,6 +84,10 @@ pub fn draw_input_box(
         ),
         // input field
         Constraint::Fill(1),
+        // query error
+        Constraint::Length(query_error.map_or(0, |e| {
+            u16::try_from(e.chars().count() + 4).unwrap_or(u16::MAX)
+        })),
         // result count
         Constraint::Length(
             3 * (u16::try_from(total_count.max(1).ilog10()).unwrap() + 1) + 3,
@@ -109,17 +116,24 @@ pub fn draw_input_box(
     let input = Paragraph::new(input_state.value())
         .scroll((0, u16::try_from(scroll)?))
         .block(interactive_input_block)
-        .style(
-            Style::default()
-                .fg(colorscheme.input.text_fg),
-        )
+        .style(Style::default().fg(colorscheme.input.text_fg))
         .alignment(Alignment::Left);
     f.render_widget(input, inner_input_chunks[1]);
 
+    if let Some(error) = query_error {
+        f.render_widget(
+            Span::styled(
+                format!(" {ERROR_CHAR} {error}"),
+                Style::default().fg(Color::Red).italic(),
+            ),
+            inner_input_chunks[2],
+        );
+    }
+
     if matcher_running {
         f.render_widget(
             Span::styled(LOADING_CHAR, Style::default().fg(Color::Green)),
-            inner_input_chunks[3],
+            inner_input_chunks[4],
         );
     }
 
@@ -132,7 +146,7 @@ pub fn draw_input_box(
     ))
     .block(result_count_block)
     .alignment(Alignment::Right);
-    f.render_widget(result_count_paragraph, inner_input_chunks[2]);
+    f.render_widget(result_count_paragraph, inner_input_chunks[3]);
 
     // Make the cursor visible and ask tui-rs to put it at the
     // specified coordinates after rendering
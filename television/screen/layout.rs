@@ -1,4 +1,5 @@
 use crate::{
+    channels::prototypes::PreviewSplit,
     config::{
         layers::MergedConfig,
         ui::{BorderType, Padding},
@@ -102,6 +103,7 @@ pub struct Layout {
     pub preview_window: Option<Rect>,
     pub remote_control: Option<Rect>,
     pub action_picker: Option<Rect>,
+    pub history_picker: Option<Rect>,
     pub help_panel: Option<Rect>,
     pub status_bar: Option<Rect>,
 }
@@ -125,6 +127,7 @@ impl Default for Layout {
             None,
             None,
             None,
+            None,
         )
     }
 }
@@ -137,6 +140,7 @@ impl Layout {
         preview_window: Option<Rect>,
         remote_control: Option<Rect>,
         action_picker: Option<Rect>,
+        history_picker: Option<Rect>,
         help_panel: Option<Rect>,
         status_bar: Option<Rect>,
     ) -> Self {
@@ -146,6 +150,7 @@ impl Layout {
             preview_window,
             remote_control,
             action_picker,
+            history_picker,
             help_panel,
             status_bar,
         }
@@ -441,6 +446,19 @@ impl Layout {
             None
         };
 
+        // the history picker is a centered popup, the same size as the action picker
+        let history_picker = if mode == Mode::HistoryPicker {
+            Some(centered_rect_with_dimensions(
+                &Dimensions::new(
+                    area.width * ACTION_PICKER_WIDTH_PERCENTAGE / 100,
+                    area.height * ACTION_PICKER_HEIGHT_PERCENTAGE / 100,
+                ),
+                area,
+            ))
+        } else {
+            None
+        };
+
         // the help panel is positioned at bottom-right, accounting for status bar
         let help_panel = if merged_config.help_panel_disabled
             || merged_config.help_panel_hidden
@@ -482,12 +500,33 @@ impl Layout {
             preview_window,
             remote_control,
             action_picker,
+            history_picker,
             help_panel,
             status_bar,
         )
     }
 }
 
+/// Splits the preview window into one pane per preview command when the
+/// channel's preview is split, returns the whole window otherwise.
+pub fn preview_panes(
+    preview_window: Rect,
+    merged_config: &MergedConfig,
+) -> Vec<Rect> {
+    let direction = match merged_config.channel_preview_split {
+        Some(PreviewSplit::Horizontal) => Direction::Horizontal,
+        Some(PreviewSplit::Vertical) | None => Direction::Vertical,
+    };
+    RatatuiLayout::default()
+        .direction(direction)
+        .constraints(vec![
+            Constraint::Fill(1);
+            merged_config.preview_pane_count()
+        ])
+        .split(preview_window)
+        .to_vec()
+}
+
 /// helper function to create a centered rect using up certain percentage of the available rect `r`
 fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
     let height = r.height.saturating_mul(percent_y) / 100;
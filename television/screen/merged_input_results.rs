@@ -8,8 +8,12 @@ use crate::{
     channels::entry::Entry,
     config::ui::{BorderType, DEFAULT_PROMPT, Padding},
     event::Key,
-    screen::{colors::Colorscheme, layout::InputPosition, result_item},
-    utils::input::Input,
+    screen::{
+        colors::Colorscheme,
+        layout::InputPosition,
+        result_item::{self, SelectionMarker},
+    },
+    utils::{hashmaps::FxIndexSet, input::Input},
 };
 use anyhow::Result;
 use ratatui::{
@@ -24,9 +28,9 @@ use ratatui::{
         TitlePosition,
     },
 };
-use rustc_hash::FxHashSet;
 
 const LOADING_CHAR: &str = "●";
+const ERROR_CHAR: &str = "✗";
 
 #[allow(clippy::too_many_arguments)]
 pub fn draw_merged_input_results(
@@ -38,10 +42,11 @@ pub fn draw_merged_input_results(
     input_state: &Input,
     results_picker_state: &mut ListState,
     matcher_running: bool,
+    query_error: Option<&str>,
     channel_name: &str,
     // results state
     entries: &[Entry],
-    selected_entries: &FxHashSet<Entry>,
+    selected_entries: &FxIndexSet<Entry>,
     _source_index: usize,
     _source_count: usize,
     _cycle_key: Option<Key>,
@@ -53,6 +58,7 @@ pub fn draw_merged_input_results(
     input_border_type: &BorderType,
     input_prompt: Option<&String>,
     results_padding: &Padding,
+    show_selection_index: bool,
 ) -> Result<()> {
     // ── outer block ─────────────────────────────────────────────
     let header_text =
@@ -153,12 +159,16 @@ pub fn draw_merged_input_results(
         u16::try_from(prompt_str.chars().count() + 1).unwrap_or(2);
     let count_digits = u16::try_from(total_count.max(1).ilog10()).unwrap() + 1;
     let count_len = 3 * count_digits + 3;
+    let error_len = query_error.map_or(0, |e| {
+        u16::try_from(e.chars().count() + 4).unwrap_or(u16::MAX)
+    });
 
     let input_chunks = RatatuiLayout::default()
         .direction(Direction::Horizontal)
         .constraints([
             Constraint::Length(prompt_len),
             Constraint::Fill(1),
+            Constraint::Length(error_len),
             Constraint::Length(count_len),
             Constraint::Length(indicator_len),
         ])
@@ -179,19 +189,27 @@ pub fn draw_merged_input_results(
     f.render_widget(
         Paragraph::new(input_state.value())
             .scroll((0, u16::try_from(scroll)?))
-            .style(
-                Style::default()
-                    .fg(colorscheme.input.text_fg),
-            )
+            .style(Style::default().fg(colorscheme.input.text_fg))
             .alignment(Alignment::Left),
         input_chunks[1],
     );
 
+    // query error
+    if let Some(error) = query_error {
+        f.render_widget(
+            Span::styled(
+                format!(" {ERROR_CHAR} {error}"),
+                Style::default().fg(Color::Red).italic(),
+            ),
+            input_chunks[2],
+        );
+    }
+
     // loading indicator
     if matcher_running {
         f.render_widget(
             Span::styled(LOADING_CHAR, Style::default().fg(Color::Green)),
-            input_chunks[3],
+            input_chunks[4],
         );
     }
 
@@ -204,7 +222,7 @@ pub fn draw_merged_input_results(
                 .italic(),
         ))
         .alignment(Alignment::Right),
-        input_chunks[2],
+        input_chunks[3],
     );
 
     // cursor
@@ -235,8 +253,6 @@ pub fn draw_merged_input_results(
         InputPosition::Top => ratatui::widgets::ListDirection::TopToBottom,
     };
 
-    let has_multi_select = !selected_entries.is_empty();
-
     // Build the list with no outer block (borders are handled by
     // the outer merged block).
     let results_block = Block::default().style(
@@ -252,11 +268,11 @@ pub fn draw_merged_input_results(
         &colorscheme.results,
         results_inner.width.saturating_sub(1),
         |entry| {
-            if has_multi_select {
-                Some(selected_entries.contains(entry))
-            } else {
-                None
-            }
+            SelectionMarker::for_item(
+                entry,
+                selected_entries,
+                show_selection_index,
+            )
         },
     );
 
@@ -137,7 +137,7 @@ fn calculate_popup_size(
     (required_width, required_height)
 }
 
-fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
+pub(crate) fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
     let vertical_layout = Layout::vertical([
         Constraint::Fill(1),
         Constraint::Length(height),
@@ -2,6 +2,7 @@ pub mod action_picker;
 pub mod colors;
 pub mod constants;
 pub mod help_panel;
+pub mod history_picker;
 pub mod input;
 pub mod keybindings;
 pub mod layout;
@@ -9,6 +10,7 @@ pub mod layout_ext;
 pub mod logo;
 pub mod merged_input_results;
 pub mod missing_requirements_popup;
+pub mod param_prompt;
 pub mod preview;
 pub mod remote_control;
 pub mod result_item;
@@ -0,0 +1,98 @@
+use crate::{
+    screen::{colors::Colorscheme, missing_requirements_popup::centered_rect},
+    television::ParamPrompt,
+};
+use ratatui::{
+    Frame,
+    layout::{Alignment, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Padding, Paragraph},
+};
+
+const POPUP_WIDTH: u16 = 50;
+const PROMPT: &str = "> ";
+
+/// Draws a centered popup prompting for the value of a channel parameter.
+///
+/// This popup is displayed when entering a channel declaring parameters
+/// that were neither given on the command line nor have a default.
+#[allow(clippy::cast_possible_truncation)]
+pub fn draw_param_prompt(
+    f: &mut Frame<'_>,
+    area: Rect,
+    prompt: &ParamPrompt,
+    colorscheme: &Colorscheme,
+) {
+    let Some((name, param)) = prompt.params.first() else {
+        return;
+    };
+
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            name.clone(),
+            Style::default().fg(colorscheme.help.metadata_field_name_fg),
+        )),
+    ];
+    if let Some(description) = &param.description {
+        lines.push(Line::from(Span::styled(
+            description.clone(),
+            Style::default().fg(colorscheme.general.border_fg).italic(),
+        )));
+    }
+    let input_line = lines.len() as u16;
+    lines.push(Line::from(vec![
+        Span::styled(
+            PROMPT,
+            Style::default().fg(colorscheme.input.input_fg).bold(),
+        ),
+        Span::styled(
+            prompt.input.value().to_string(),
+            Style::default().fg(colorscheme.input.input_fg),
+        ),
+    ]));
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        if prompt.is_cancellable() {
+            "Press Enter to confirm or Esc to cancel"
+        } else {
+            "Press Enter to confirm or Esc to quit"
+        },
+        Style::default().fg(colorscheme.general.border_fg).italic(),
+    )));
+
+    let popup_area = centered_rect(
+        POPUP_WIDTH.min(area.width),
+        (lines.len() as u16 + 2).min(area.height),
+        area,
+    );
+    f.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(colorscheme.general.border_fg))
+        .title_top(
+            Line::from(Span::styled(
+                format!(" {} ", prompt.channel_name),
+                Style::default().fg(colorscheme.mode.channel).bold(),
+            ))
+            .alignment(Alignment::Center),
+        )
+        .style(
+            Style::default()
+                .bg(colorscheme.general.background.unwrap_or_default()),
+        )
+        .padding(Padding::horizontal(1));
+    f.render_widget(Paragraph::new(lines).block(block), popup_area);
+
+    // borders and padding come first
+    f.set_cursor_position((
+        popup_area.x
+            + 2
+            + PROMPT.len() as u16
+            + prompt.input.visual_cursor() as u16,
+        popup_area.y + 1 + input_line,
+    ));
+}
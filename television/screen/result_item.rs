@@ -5,6 +5,7 @@ use crate::{
         constants::{DESELECTED_SYMBOL, POINTER_SYMBOL, SELECTED_SYMBOL},
     },
     utils::{
+        hashmaps::FxIndexSet,
         indices::truncate_highlighted_string,
         strings::{
             ReplaceNonPrintableConfig, make_result_item_printable,
@@ -16,9 +17,10 @@ use ansi_to_tui::IntoText;
 use anyhow::Result;
 use devicons::FileIcon;
 use ratatui::{
-    prelude::{Color, Line, Span, Style},
+    prelude::{Color, Line, Modifier, Span, Style},
     widgets::{Block, List, ListDirection, ListState},
 };
+use std::hash::Hash;
 use unicode_width::UnicodeWidthStr;
 
 /// Trait implemented by any item that can be displayed in the results or remote-control list.
@@ -49,12 +51,63 @@ pub trait ResultItem {
         None
     }
 
+    /// Optional details shown dimmed after the name (history picker entries).
+    fn details(&self) -> Option<&str> {
+        None
+    }
+
     /// Whether the item uses ANSI escape codes for styling.
     fn ansi(&self) -> bool {
         false
     }
 }
 
+/// The marker shown in front of results while entries are multi-selected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SelectionMarker {
+    Selected,
+    /// An entry that isn't selected, right-aligned to `width` columns so it
+    /// lines up with indexed rows.
+    Deselected {
+        width: usize,
+    },
+    /// A selected entry along with its 1-based position in the selection,
+    /// right-aligned to `width` digits.
+    Index {
+        index: usize,
+        width: usize,
+    },
+}
+
+impl SelectionMarker {
+    /// The marker of `item` given the current selection, `None` when nothing
+    /// is selected.
+    pub fn for_item<T: Hash + Eq>(
+        item: &T,
+        selected: &FxIndexSet<T>,
+        show_index: bool,
+    ) -> Option<Self> {
+        if selected.is_empty() {
+            return None;
+        }
+        // every row shares the width of the largest index so that entries
+        // don't shift once the selection reaches a new power of ten
+        let width = if show_index {
+            selected.len().ilog10() as usize + 1
+        } else {
+            1
+        };
+        Some(match selected.get_index_of(item) {
+            Some(i) if show_index => SelectionMarker::Index {
+                index: i + 1,
+                width,
+            },
+            Some(_) => SelectionMarker::Selected,
+            None => SelectionMarker::Deselected { width },
+        })
+    }
+}
+
 /// Build a single `Line` for a [`ResultItem`].
 #[allow(clippy::too_many_arguments)]
 #[allow(clippy::cast_possible_truncation)]
@@ -64,36 +117,54 @@ pub fn build_result_line<'a, T: ResultItem + ?Sized>(
     result_fg: Color,
     match_fg: Color,
     area_width: u16,
-    // Some(true)=selected ●, Some(false)=unselected, None=no prefix
-    prefix: Option<bool>,
+    // None = no prefix
+    prefix: Option<SelectionMarker>,
 ) -> Line<'a> {
     // PERF: Pre-allocate spans vector with estimated capacity
     let mut spans = Vec::<Span<'a>>::with_capacity(16);
 
     // Optional selection prefix
-    if let Some(selected) = prefix {
-        if selected {
+    let selection_prefix_width: u16 = match prefix {
+        Some(SelectionMarker::Selected) => {
             spans.push(Span::styled(
                 SELECTED_SYMBOL,
                 Style::default().fg(selection_fg),
             ));
-        } else {
-            spans.push(Span::raw(DESELECTED_SYMBOL));
+            2
         }
-    }
-
-    let selection_prefix_width: u16 = if prefix.is_some() { 2 } else { 0 };
+        Some(SelectionMarker::Deselected { width }) => {
+            spans.push(Span::raw(format!(
+                "{DESELECTED_SYMBOL:>0$}",
+                width + 1
+            )));
+            width as u16 + 1
+        }
+        Some(SelectionMarker::Index { index, width }) => {
+            spans.push(Span::styled(
+                format!("{index:>width$} "),
+                Style::default().fg(selection_fg),
+            ));
+            width as u16 + 1
+        }
+        None => 0,
+    };
 
     let shortcut_extra: u16 = item
         .shortcut()
         .map(|k| 2 + k.to_string().len() as u16) // space + key
         .unwrap_or(0);
 
+    let details_extra: u16 = item
+        .details()
+        .map(|d| 2 + d.width() as u16) // spaces + details
+        .unwrap_or(0);
+
     let item_max_width = area_width
         .saturating_sub(2) // pointer + space (kept for caller)
         .saturating_sub(2) // borders
         .saturating_sub(selection_prefix_width)
-        .saturating_sub(shortcut_extra);
+        .saturating_sub(shortcut_extra)
+        .saturating_sub(details_extra);
 
     if item.ansi() {
         spans.extend(build_entry_spans_ansi(
@@ -120,6 +191,15 @@ pub fn build_result_line<'a, T: ResultItem + ?Sized>(
         ));
     }
 
+    // Show details if present.
+    if let Some(details) = item.details() {
+        spans.push(Span::raw("  "));
+        spans.push(Span::styled(
+            details,
+            Style::default().fg(result_fg).add_modifier(Modifier::DIM),
+        ));
+    }
+
     Line::from(spans)
 }
 
@@ -325,7 +405,7 @@ pub fn build_results_list<'a, 'b, T, F>(
 where
     'b: 'a,
     T: ResultItem,
-    F: FnMut(&T) -> Option<bool>,
+    F: FnMut(&T) -> Option<SelectionMarker>,
 {
     List::new(entries.iter().enumerate().map(|(i, e)| {
         let prefix = prefix_fn(e);
@@ -344,9 +424,7 @@ where
         )
     }))
     .direction(list_direction)
-    .highlight_style(
-        Style::default().bg(colorscheme.result_selected_bg),
-    )
+    .highlight_style(Style::default().bg(colorscheme.result_selected_bg))
     .highlight_symbol(POINTER_SYMBOL)
     .block(block)
 }
@@ -383,6 +461,90 @@ mod tests {
         assert_eq!(line, expected);
     }
 
+    #[test]
+    fn test_selection_marker_for_item() {
+        let a = Entry::new("a".to_string());
+        let b = Entry::new("b".to_string());
+        let c = Entry::new("c".to_string());
+        let mut selected = FxIndexSet::default();
+
+        assert_eq!(SelectionMarker::for_item(&a, &selected, true), None);
+
+        selected.insert(b.clone());
+        selected.insert(a.clone());
+        assert_eq!(
+            SelectionMarker::for_item(&a, &selected, true),
+            Some(SelectionMarker::Index { index: 2, width: 1 })
+        );
+        assert_eq!(
+            SelectionMarker::for_item(&a, &selected, false),
+            Some(SelectionMarker::Selected)
+        );
+        assert_eq!(
+            SelectionMarker::for_item(&c, &selected, true),
+            Some(SelectionMarker::Deselected { width: 1 })
+        );
+
+        for i in 0..9 {
+            selected.insert(Entry::new(i.to_string()));
+        }
+        assert_eq!(
+            SelectionMarker::for_item(&a, &selected, true),
+            Some(SelectionMarker::Index { index: 2, width: 2 })
+        );
+        assert_eq!(
+            SelectionMarker::for_item(&c, &selected, true),
+            Some(SelectionMarker::Deselected { width: 2 })
+        );
+        assert_eq!(
+            SelectionMarker::for_item(&c, &selected, false),
+            Some(SelectionMarker::Deselected { width: 1 })
+        );
+    }
+
+    #[test]
+    fn test_build_result_line_selection_index() {
+        let entry = Entry::new("something".to_string());
+        let line = build_result_line(
+            &entry,
+            Color::Red,
+            Color::Reset,
+            Color::Reset,
+            200,
+            Some(SelectionMarker::Index { index: 3, width: 1 }),
+        );
+
+        assert_eq!(line.spans[0], Span::styled("3 ", Color::Red));
+    }
+
+    #[test]
+    fn test_build_result_line_selection_index_padding() {
+        let entry = Entry::new("something".to_string());
+        let index = build_result_line(
+            &entry,
+            Color::Red,
+            Color::Reset,
+            Color::Reset,
+            200,
+            Some(SelectionMarker::Index { index: 3, width: 2 }),
+        );
+        let deselected = build_result_line(
+            &entry,
+            Color::Red,
+            Color::Reset,
+            Color::Reset,
+            200,
+            Some(SelectionMarker::Deselected { width: 2 }),
+        );
+
+        assert_eq!(index.spans[0], Span::styled(" 3 ", Color::Red));
+        assert_eq!(
+            deselected.spans[0],
+            Span::raw(format!(" {DESELECTED_SYMBOL}"))
+        );
+        assert_eq!(index.width(), deselected.width());
+    }
+
     #[test]
     fn test_build_result_line_truncate_multibyte() {
         let entry = Entry::new("ジェイムス下地 - REDLINE Original Soundtrack - 06 - ROBOWORLD TV.mp3".to_string())
@@ -2,7 +2,12 @@ use crate::{
     channels::entry::Entry,
     config::ui::{BorderType, Padding},
     event::Key,
-    screen::{colors::Colorscheme, layout::InputPosition, result_item},
+    screen::{
+        colors::Colorscheme,
+        layout::InputPosition,
+        result_item::{self, SelectionMarker},
+    },
+    utils::hashmaps::FxIndexSet,
 };
 use anyhow::Result;
 use ratatui::{
@@ -12,20 +17,20 @@ use ratatui::{
     text::Line,
     widgets::{Block, Borders, ListState, Padding as RatatuiPadding},
 };
-use rustc_hash::FxHashSet;
 
 #[allow(clippy::too_many_arguments)]
 pub fn draw_results_list(
     f: &mut Frame,
     rect: Rect,
     entries: &[Entry],
-    selected_entries: &FxHashSet<Entry>,
+    selected_entries: &FxIndexSet<Entry>,
     relative_picker_state: &mut ListState,
     input_bar_position: InputPosition,
     colorscheme: &Colorscheme,
     results_panel_padding: &Padding,
     results_panel_border_type: &BorderType,
     results_panel_header: &Option<String>,
+    show_selection_index: bool,
     source_index: usize,
     source_count: usize,
     current_source_name: Option<&str>,
@@ -93,8 +98,6 @@ pub fn draw_results_list(
         InputPosition::Top => ratatui::widgets::ListDirection::TopToBottom,
     };
 
-    let has_multi_select = !selected_entries.is_empty();
-
     let results_list = result_item::build_results_list(
         results_block,
         entries,
@@ -103,11 +106,11 @@ pub fn draw_results_list(
         &colorscheme.results,
         rect.width - 1, // right padding
         |entry| {
-            if has_multi_select {
-                Some(selected_entries.contains(entry))
-            } else {
-                None
-            }
+            SelectionMarker::for_item(
+                entry,
+                selected_entries,
+                show_selection_index,
+            )
         },
     );
 
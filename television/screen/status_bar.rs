@@ -1,5 +1,6 @@
 use crate::{
-    action::Action, draw::Ctx, television::Mode, utils::strings::SPACE,
+    action::Action, config::keybindings::key_sequence_to_string, draw::Ctx,
+    keymap::InputMode, television::Mode, utils::strings::SPACE,
 };
 use ratatui::{
     Frame,
@@ -43,6 +44,11 @@ pub fn draw_status_bar(f: &mut Frame<'_>, area: Rect, ctx: &Ctx) {
             ctx.colorscheme.mode.action_picker_fg,
             ctx.colorscheme.mode.action_picker,
         ),
+        Mode::HistoryPicker => (
+            "HISTORY",
+            ctx.colorscheme.mode.action_picker_fg,
+            ctx.colorscheme.mode.action_picker,
+        ),
     };
 
     // Create mode bubble with separators
@@ -71,6 +77,17 @@ pub fn draw_status_bar(f: &mut Frame<'_>, area: Rect, ctx: &Ctx) {
         ));
     }
 
+    // Input mode when modal keybindings are enabled
+    if let Some(input_mode) = ctx.tv_state.input_mode {
+        left_spans.push(Span::styled(
+            match input_mode {
+                InputMode::Normal => " NORMAL",
+                InputMode::Insert => " INSERT",
+            },
+            Style::default().fg(mode_bg).add_modifier(Modifier::BOLD),
+        ));
+    }
+
     // Add channel-specific info in Channel mode
     if ctx.tv_state.mode == Mode::Channel {
         let name_style = Style::default()
@@ -108,7 +125,9 @@ pub fn draw_status_bar(f: &mut Frame<'_>, area: Rect, ctx: &Ctx) {
     // Use mode color for keybinding hints
     let key_color = match ctx.tv_state.mode {
         Mode::Channel => ctx.colorscheme.mode.channel,
-        Mode::ActionPicker => ctx.colorscheme.mode.action_picker,
+        Mode::ActionPicker | Mode::HistoryPicker => {
+            ctx.colorscheme.mode.action_picker
+        }
         Mode::RemoteControl => ctx.colorscheme.mode.remote_control,
     };
 
@@ -139,7 +158,9 @@ pub fn draw_status_bar(f: &mut Frame<'_>, area: Rect, ctx: &Ctx) {
             .get_key_for_action(&Action::ToggleRemoteControl);
         if let Some(k) = key {
             let hint_text = match ctx.tv_state.mode {
-                Mode::Channel | Mode::ActionPicker => "Remote Control",
+                Mode::Channel | Mode::ActionPicker | Mode::HistoryPicker => {
+                    "Remote Control"
+                }
                 Mode::RemoteControl => "Back to Channel",
             };
             add_hint(hint_text, &k.to_string());
@@ -170,13 +191,20 @@ pub fn draw_status_bar(f: &mut Frame<'_>, area: Rect, ctx: &Ctx) {
         middle_spans.extend(hint_spans);
     }
 
-    // === RIGHT SECTION: Version ===
-    let right_spans = vec![Span::styled(
+    // === RIGHT SECTION: Pending keys and version ===
+    let mut right_spans = Vec::new();
+    if !ctx.tv_state.pending_keys.is_empty() {
+        right_spans.push(Span::styled(
+            format!("{} ", key_sequence_to_string(&ctx.tv_state.pending_keys)),
+            Style::default().fg(key_color).add_modifier(Modifier::BOLD),
+        ));
+    }
+    right_spans.push(Span::styled(
         format!("v{} ", ctx.app_metadata.version),
         Style::default()
             .fg(ctx.colorscheme.results.result_fg)
             .add_modifier(Modifier::ITALIC),
-    )];
+    ));
 
     // Render all sections
     f.render_widget(
@@ -0,0 +1,126 @@
+//! Per-channel session state, restored when reopening channels that opt in
+//! with `resume = true`.
+
+use anyhow::{Context, Result};
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+use crate::screen::layout::Orientation;
+
+const SESSIONS_FILE_NAME: &str = "sessions.json";
+
+/// The state of a channel when it was last used.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionState {
+    /// The query typed in the input bar.
+    pub query: String,
+    /// The index of the source command in use.
+    pub source_index: usize,
+    /// The index of the preview command in use.
+    pub preview_index: usize,
+    /// The preview pane scrolled by the preview actions when the preview is
+    /// split.
+    #[serde(default)]
+    pub focused_preview_pane: usize,
+    pub orientation: Orientation,
+    pub preview_hidden: bool,
+}
+
+/// The session state of every channel, persisted in the data directory.
+#[derive(Debug, Clone)]
+pub struct Sessions {
+    /// Session states keyed by channel name.
+    states: FxHashMap<String, SessionState>,
+    file_path: PathBuf,
+    /// Whether a state changed since the sessions were loaded.
+    dirty: bool,
+}
+
+impl Sessions {
+    pub fn new(data_dir: &Path) -> Self {
+        Self {
+            states: FxHashMap::default(),
+            file_path: data_dir.join(SESSIONS_FILE_NAME),
+            dirty: false,
+        }
+    }
+
+    /// Initialize the sessions by loading previously persisted states from
+    /// disk.
+    pub fn init(&mut self) -> Result<()> {
+        if !self.file_path.exists() {
+            debug!("Sessions file not found: {}", self.file_path.display());
+            return Ok(());
+        }
+
+        let contents = std::fs::read_to_string(&self.file_path)
+            .context("Failed to read sessions file")?;
+        self.states = serde_json::from_str(&contents)
+            .context("Failed to parse sessions file")?;
+        Ok(())
+    }
+
+    /// Save the sessions to disk if any of them changed.
+    pub fn save_to_file(&self) -> Result<()> {
+        if !self.dirty {
+            return Ok(());
+        }
+        if let Some(parent) = self.file_path.parent() {
+            std::fs::create_dir_all(parent)
+                .context("Failed to create sessions directory")?;
+        }
+
+        let contents = serde_json::to_string_pretty(&self.states)
+            .context("Failed to serialize sessions")?;
+        std::fs::write(&self.file_path, contents)
+            .context("Failed to write sessions file")?;
+        debug!("Saved sessions to {}", self.file_path.display());
+        Ok(())
+    }
+
+    pub fn get(&self, channel_name: &str) -> Option<&SessionState> {
+        self.states.get(channel_name)
+    }
+
+    /// Record the current state of a channel.
+    pub fn update(&mut self, channel_name: &str, state: SessionState) {
+        if self.states.get(channel_name) != Some(&state) {
+            self.states.insert(channel_name.to_string(), state);
+            self.dirty = true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sessions_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let state = SessionState {
+            query: "foo".to_string(),
+            source_index: 1,
+            preview_index: 2,
+            focused_preview_pane: 1,
+            orientation: Orientation::Portrait,
+            preview_hidden: true,
+        };
+
+        let mut sessions = Sessions::new(dir.path());
+        sessions.init().unwrap();
+        assert!(sessions.get("files").is_none());
+        sessions.save_to_file().unwrap();
+        // nothing is written until a state is recorded
+        assert!(!dir.path().join(SESSIONS_FILE_NAME).exists());
+
+        sessions.update("files", state.clone());
+        sessions.save_to_file().unwrap();
+
+        let mut sessions = Sessions::new(dir.path());
+        sessions.init().unwrap();
+        assert_eq!(sessions.get("files"), Some(&state));
+    }
+}
@@ -5,7 +5,8 @@ use crate::{
         action_picker::{ActionEntry, ActionPicker},
         channel::ChannelKind as CableChannel,
         entry::Entry,
-        prototypes::{ChannelPrototype, CommandSpec, Template},
+        history_picker::{HistoryPicker, HistoryPickerEntry},
+        prototypes::{ChannelParam, ChannelPrototype, CommandSpec, Template},
         remote_control::{CableEntry, RemoteControl},
     },
     config::{
@@ -14,39 +15,47 @@ use crate::{
     },
     draw::{ChannelState, Ctx, TvState},
     errors::os_error_exit,
-    frecency::FrecencyHandle,
+    event::Key,
+    frecency::{ChannelFrecency, FrecencyHandle},
+    history::HistoryEntry,
     input::convert_action_to_input_request,
+    keymap::InputMode,
+    matcher::query::split_terms,
     picker::{Movement, Picker},
     previewer::{
         Config as PreviewerConfig, Preview, Previewer,
-        Request as PreviewRequest, Ticket, state::PreviewState,
+        Request as PreviewRequest, Ticket, builtin::BuiltinPreviewer,
+        selection_id, state::PreviewState,
     },
     render::UiState,
     screen::{
         colors::Colorscheme,
-        layout::{InputPosition, Orientation},
+        layout::{InputPosition, Orientation, preview_panes},
     },
+    session::SessionState,
     utils::{
         clipboard::CLIPBOARD,
+        hashmaps::FxIndexSet,
+        input::Input,
         metadata::AppMetadata,
         strings::{EMPTY_STRING, SPACE},
     },
 };
 use anyhow::Result;
-use ratatui::layout::Rect;
-use rustc_hash::FxHashSet;
+use rustc_hash::FxHashMap;
 use serde::{Deserialize, Serialize};
 use std::{fmt::Display, sync::Arc};
 use tokio::sync::mpsc::{
     UnboundedReceiver, UnboundedSender, unbounded_channel,
 };
-use tracing::{debug, error};
+use tracing::{debug, error, warn};
 
 #[derive(PartialEq, Copy, Clone, Hash, Eq, Debug, Serialize, Deserialize)]
 pub enum Mode {
     Channel,
     RemoteControl,
     ActionPicker,
+    HistoryPicker,
 }
 
 impl Display for Mode {
@@ -55,6 +64,7 @@ impl Display for Mode {
             Mode::Channel => write!(f, "Channel"),
             Mode::RemoteControl => write!(f, "Remote Control"),
             Mode::ActionPicker => write!(f, "Action Picker"),
+            Mode::HistoryPicker => write!(f, "History Picker"),
         }
     }
 }
@@ -69,10 +79,58 @@ pub struct MissingRequirementsPopup {
     pub missing_requirements: Vec<String>,
 }
 
+/// State of the prompt asking for the channel parameters that have neither
+/// a value nor a default.
+///
+/// The channel only starts once every parameter has a value.
+#[derive(Debug, Clone)]
+pub struct ParamPrompt {
+    pub channel_name: String,
+    /// The parameters left to prompt for, starting with the current one.
+    pub params: Vec<(String, ChannelParam)>,
+    /// The value being typed for the current parameter.
+    pub input: Input,
+    /// The values entered so far.
+    values: FxHashMap<String, String>,
+    /// The channel to switch to, or `None` when prompting for the channel
+    /// tv was started with.
+    prototype: Option<ChannelPrototype>,
+}
+
+impl ParamPrompt {
+    fn new(
+        channel_name: String,
+        params: Vec<(String, ChannelParam)>,
+        prototype: Option<ChannelPrototype>,
+    ) -> Self {
+        Self {
+            channel_name,
+            params,
+            input: Input::new(EMPTY_STRING.to_string()),
+            values: FxHashMap::default(),
+            prototype,
+        }
+    }
+
+    /// Whether the prompt can be dismissed, going back to the current
+    /// channel, rather than quitting tv.
+    pub fn is_cancellable(&self) -> bool {
+        self.prototype.is_some()
+    }
+
+    /// Whether `action` is handled while the prompt is shown.
+    pub fn accepts(action: &Action) -> bool {
+        convert_action_to_input_request(action).is_some()
+            || matches!(action, Action::ConfirmSelection | Action::Quit)
+    }
+}
+
 #[derive(PartialEq, Copy, Clone, Hash, Eq, Debug, Serialize, Deserialize)]
 pub enum MatchingMode {
     Substring,
     Fuzzy,
+    /// Entries are matched against a regular expression.
+    Regex,
 }
 
 pub struct Television {
@@ -82,14 +140,24 @@ pub struct Television {
     pub channel: CableChannel,
     pub remote_control: Option<RemoteControl>,
     pub action_picker: Option<ActionPicker>,
+    pub history_picker: Option<HistoryPicker>,
     pub mode: Mode,
+    /// The current mode when modal keybindings are enabled.
+    pub input_mode: InputMode,
+    /// Keys pressed so far of a key sequence.
+    pub pending_keys: Vec<Key>,
     pub currently_selected: Option<Entry>,
     pub current_pattern: String,
     pub matching_mode: MatchingMode,
     pub results_picker: Picker<Entry>,
     pub rc_picker: Picker<CableEntry>,
     pub ap_picker: Picker<ActionEntry>,
-    pub preview_state: PreviewState,
+    pub hp_picker: Picker<HistoryPickerEntry>,
+    /// The state of each preview pane, a single one unless the preview is
+    /// split.
+    pub preview_panes: Vec<PreviewState>,
+    /// The pane scrolled by the preview actions.
+    pub focused_preview_pane: usize,
     pub preview_handles:
         Option<(UnboundedSender<PreviewRequest>, UnboundedReceiver<Preview>)>,
     pub app_metadata: Arc<AppMetadata>,
@@ -102,6 +170,8 @@ pub struct Television {
     was_running: bool,
     /// Popup shown when attempting to switch to a channel with missing requirements
     pub missing_requirements_popup: Option<MissingRequirementsPopup>,
+    /// Prompt shown when entering a channel with parameters lacking a value
+    pub param_prompt: Option<ParamPrompt>,
 }
 
 impl Television {
@@ -130,6 +200,8 @@ impl Television {
 
         let matching_mode = if merged_config.exact_match {
             MatchingMode::Substring
+        } else if merged_config.regex_match {
+            MatchingMode::Regex
         } else {
             MatchingMode::Fuzzy
         };
@@ -141,31 +213,39 @@ impl Television {
             .map(|command| {
                 Self::setup_previewer(
                     command,
+                    merged_config.channel_preview_builtin.clone(),
                     merged_config.channel_preview_cached,
                     merged_config.channel_preview_offset.clone(),
                     merged_config.preview_panel_header.clone(),
                     merged_config.preview_panel_footer.clone(),
+                    merged_config.channel_preview_separator.clone(),
+                    merged_config.channel_preview_split.is_some(),
                 )
             });
 
         let frecency_config =
             if merged_config.channel_frecency && !merged_config.no_sort {
-                Some((frecency.clone(), merged_config.channel_name.clone()))
+                Some((frecency.clone(), channel_frecency(&merged_config)))
             } else {
                 None
             };
 
         let mut channel = CableChannel::new(
             merged_config.channel_source_command.clone(),
+            merged_config.channel_source_walker.clone(),
             merged_config.channel_source_entry_delimiter,
             merged_config.channel_source_ansi,
             merged_config.channel_source_display.clone(),
             merged_config.channel_source_output.clone(),
+            merged_config.channel_source_fields.clone(),
             merged_config.channel_preview_command.is_some(),
             merged_config.no_sort,
+            &merged_config.matcher_config,
             frecency_config,
             merged_config.is_stdin,
+            merged_config.channel_source_reload_on_query,
         );
+        channel.set_regex(matching_mode == MatchingMode::Regex);
 
         let app_metadata = AppMetadata::new(
             env!("CARGO_PKG_VERSION").to_string(),
@@ -183,21 +263,23 @@ impl Television {
             });
         let colorscheme = (&theme).into();
 
-        let pattern = Television::preprocess_pattern(
-            matching_mode,
-            &merged_config
-                .input
-                .clone()
-                .unwrap_or(EMPTY_STRING.to_string()),
-        );
+        let input = merged_config
+            .input
+            .clone()
+            .unwrap_or(EMPTY_STRING.to_string());
+        let pattern = Television::preprocess_pattern(matching_mode, &input);
 
         channel.find(&pattern);
+        channel.set_source_query(&input);
 
-        let preview_state = PreviewState::new(
-            channel.supports_preview(),
-            Preview::default(),
-            0,
-        );
+        let preview_panes = vec![
+            PreviewState::new(
+                channel.supports_preview(),
+                Preview::default(),
+                0,
+            );
+            merged_config.preview_pane_count()
+        ];
 
         let remote_control = if merged_config.remote_disabled {
             None
@@ -211,6 +293,15 @@ impl Television {
         // Action picker is lazily initialized when toggled
         let action_picker = None;
 
+        let missing_params = layered_config.missing_channel_params();
+        let param_prompt = (!missing_params.is_empty()).then(|| {
+            ParamPrompt::new(
+                merged_config.channel_name.clone(),
+                missing_params,
+                None,
+            )
+        });
+
         Self {
             action_tx,
             merged_config: Arc::new(layered_config.merge()),
@@ -218,14 +309,19 @@ impl Television {
             channel,
             remote_control,
             action_picker,
+            history_picker: None,
             mode: Mode::Channel,
+            input_mode: InputMode::default(),
+            pending_keys: Vec::new(),
             currently_selected: None,
             current_pattern: EMPTY_STRING.to_string(),
             results_picker,
             matching_mode,
             rc_picker: Picker::default(),
             ap_picker: Picker::default(),
-            preview_state,
+            hp_picker: Picker::default(),
+            preview_panes,
+            focused_preview_pane: 0,
             preview_handles,
             app_metadata: Arc::new(app_metadata),
             colorscheme: Arc::new(colorscheme),
@@ -234,23 +330,31 @@ impl Television {
             ui_state: UiState::default(),
             frecency,
             missing_requirements_popup: None,
+            param_prompt,
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn setup_previewer(
         command: &CommandSpec,
+        builtin: Option<BuiltinPreviewer>,
         cached: bool,
         offset_expr: Option<Template>,
         title_template: Option<Template>,
         footer_template: Option<Template>,
+        selection_separator: String,
+        split: bool,
     ) -> (UnboundedSender<PreviewRequest>, UnboundedReceiver<Preview>) {
         let (preview_requests_tx, preview_requests_rx) = unbounded_channel();
         let (preview_results_tx, preview_results_rx) = unbounded_channel();
         let previewer = Previewer::new(
             command,
+            builtin,
             offset_expr,
             title_template,
             footer_template,
+            selection_separator,
+            split,
             // NOTE: this could be a per-channel configuration option in the future
             PreviewerConfig::default(),
             preview_requests_rx,
@@ -276,6 +380,7 @@ impl Television {
             self.channel.current_source_name().map(str::to_string),
             self.channel.source_index(),
             self.channel.source_count(),
+            self.channel.query_error().map(str::to_string),
         );
         let tv_state = TvState::new(
             self.mode,
@@ -283,15 +388,21 @@ impl Television {
             self.results_picker.clone(),
             self.rc_picker.clone(),
             self.ap_picker.clone(),
+            self.hp_picker.clone(),
             channel_state,
-            self.preview_state.for_render_context(
-                self.ui_state
-                    .layout
-                    .preview_window
-                    .as_ref()
-                    .map_or(0, |r| r.height as usize),
-            ),
+            self.preview_panes
+                .iter()
+                .zip(self.preview_pane_heights())
+                .map(|(state, height)| state.for_render_context(height))
+                .collect(),
+            self.focused_preview_pane,
+            self.merged_config
+                .input_map
+                .is_modal()
+                .then_some(self.input_mode),
+            self.pending_keys.clone(),
             self.missing_requirements_popup.clone(),
+            self.param_prompt.clone(),
         );
 
         Ctx::new(
@@ -308,9 +419,62 @@ impl Television {
         self.merged_config.channel_name.clone()
     }
 
+    /// Switch to the given channel, prompting for the values of its
+    /// parameters first if needed.
     pub fn change_channel(&mut self, channel_prototype: &ChannelPrototype) {
+        let missing_params = self
+            .layered_config
+            .missing_params_on_switch(channel_prototype);
+        if missing_params.is_empty() {
+            self.layered_config
+                .update_channel(channel_prototype.clone());
+            self.setup_channel();
+        } else {
+            self.param_prompt = Some(ParamPrompt::new(
+                channel_prototype.metadata.name.clone(),
+                missing_params,
+                Some(channel_prototype.clone()),
+            ));
+        }
+    }
+
+    /// Handle an action while the parameter prompt is shown.
+    fn handle_param_prompt_action(&mut self, action: &Action) {
+        let Some(prompt) = &mut self.param_prompt else {
+            return;
+        };
+        match action {
+            Action::ConfirmSelection => {
+                let (name, _) = prompt.params.remove(0);
+                prompt.values.insert(name, prompt.input.value().to_string());
+                prompt.input.reset();
+                if !prompt.params.is_empty() {
+                    return;
+                }
+                let prompt = self.param_prompt.take().unwrap();
+                if let Some(prototype) = prompt.prototype {
+                    self.layered_config.update_channel(prototype);
+                }
+                self.layered_config.set_channel_params(prompt.values);
+                self.setup_channel();
+            }
+            Action::Quit => {
+                if prompt.is_cancellable() {
+                    self.param_prompt = None;
+                }
+            }
+            action => {
+                if let Some(request) = convert_action_to_input_request(action)
+                {
+                    prompt.input.handle(request);
+                }
+            }
+        }
+    }
+
+    /// Replace the running channel with the one currently configured.
+    fn setup_channel(&mut self) {
         // shutdown the current channel and reset state
-        self.preview_state.reset();
         self.reset_picker_selection();
         self.reset_picker_input();
         self.current_pattern = EMPTY_STRING.to_string();
@@ -321,10 +485,8 @@ impl Television {
                 .expect("Failed to send shutdown signal to previewer");
         }
         // setup the new channel
-        debug!("Changing channel to {:?}", channel_prototype);
-        self.layered_config
-            .update_channel(channel_prototype.clone());
         self.merged_config = Arc::new(self.layered_config.merge());
+        debug!("Changing channel to {}", self.merged_config.channel_name);
         // merge channel shortcuts if remote control is enabled
         if let Some(rc) = &mut self.remote_control {
             Arc::make_mut(&mut self.merged_config)
@@ -339,16 +501,21 @@ impl Television {
                 |command| {
                     Self::setup_previewer(
                         command,
+                        self.merged_config.channel_preview_builtin.clone(),
                         self.merged_config.channel_preview_cached,
                         self.merged_config.channel_preview_offset.clone(),
                         self.merged_config.preview_panel_header.clone(),
                         self.merged_config.preview_panel_footer.clone(),
+                        self.merged_config.channel_preview_separator.clone(),
+                        self.merged_config.channel_preview_split.is_some(),
                     )
                 },
             );
         // Set preview state enabled based on both channel capability and UI configuration
-        self.preview_state.enabled = channel_prototype.preview.is_some()
-            && !self.merged_config.preview_panel_hidden;
+        self.reset_preview_panes(
+            self.merged_config.channel_preview_command.is_some()
+                && !self.merged_config.preview_panel_hidden,
+        );
 
         // Build frecency config if enabled for this channel and sorting is enabled
         let frecency_config = if self.merged_config.channel_frecency
@@ -356,7 +523,7 @@ impl Television {
         {
             Some((
                 self.frecency.clone(),
-                self.merged_config.channel_name.clone(),
+                channel_frecency(&self.merged_config),
             ))
         } else {
             None
@@ -364,15 +531,21 @@ impl Television {
 
         self.channel = CableChannel::new(
             self.merged_config.channel_source_command.clone(),
+            self.merged_config.channel_source_walker.clone(),
             self.merged_config.channel_source_entry_delimiter,
             self.merged_config.channel_source_ansi,
             self.merged_config.channel_source_display.clone(),
             self.merged_config.channel_source_output.clone(),
+            self.merged_config.channel_source_fields.clone(),
             self.merged_config.channel_preview_command.is_some(),
             self.merged_config.no_sort,
+            &self.merged_config.matcher_config,
             frecency_config,
             false, // stdin only applies to the initial channel
+            self.merged_config.channel_source_reload_on_query,
         );
+        self.channel
+            .set_regex(self.matching_mode == MatchingMode::Regex);
         self.was_running = true;
         self.channel.load();
     }
@@ -383,6 +556,7 @@ impl Television {
                 let processed_pattern =
                     Self::preprocess_pattern(self.matching_mode, pattern);
                 self.channel.find(&processed_pattern);
+                self.channel.set_source_query(pattern);
             }
             Mode::RemoteControl => {
                 if let Some(rc) = self.remote_control.as_mut() {
@@ -394,12 +568,17 @@ impl Television {
                     ap.find(pattern);
                 }
             }
+            Mode::HistoryPicker => {
+                if let Some(hp) = self.history_picker.as_mut() {
+                    hp.find(pattern);
+                }
+            }
         }
     }
 
     fn preprocess_pattern(mode: MatchingMode, pattern: &str) -> String {
         if mode == MatchingMode::Substring {
-            let parts: Vec<&str> = pattern.split_ascii_whitespace().collect();
+            let parts = split_terms(pattern);
             if parts.is_empty() {
                 return pattern.to_string();
             }
@@ -413,7 +592,13 @@ impl Television {
                 if i > 0 {
                     result.push(' ');
                 }
-                result.push('\'');
+                // only fuzzy terms are turned into substring ones
+                if !(*part == "|"
+                    || part.starts_with(['\'', '!', '^', '"', '\\'])
+                    || part.ends_with('$'))
+                {
+                    result.push('\'');
+                }
                 result.push_str(part);
             }
             result
@@ -454,16 +639,17 @@ impl Television {
             Mode::Channel => self.results_picker.selected().map(|i| i as u32),
             Mode::RemoteControl => self.rc_picker.selected().map(|i| i as u32),
             Mode::ActionPicker => self.ap_picker.selected().map(|i| i as u32),
+            Mode::HistoryPicker => self.hp_picker.selected().map(|i| i as u32),
         }
     }
 
     #[must_use]
-    pub fn get_selected_entries(&mut self) -> Option<FxHashSet<Entry>> {
+    pub fn get_selected_entries(&mut self) -> Option<FxIndexSet<Entry>> {
         // if nothing is selected, return the currently hovered entry
         if self.channel.selected_entries().is_empty() {
             return self
                 .get_selected_entry()
-                .map(|e| FxHashSet::from_iter([e]));
+                .map(|e| FxIndexSet::from_iter([e]));
         }
         Some(self.channel.selected_entries().clone())
     }
@@ -513,6 +699,23 @@ impl Television {
                         as usize,
                 );
             }
+            Mode::HistoryPicker => {
+                let total_results = self
+                    .history_picker
+                    .as_ref()
+                    .expect("history picker should be Some when in HP mode")
+                    .result_count()
+                    as usize;
+                self.hp_picker.move_cursor(
+                    movement,
+                    step,
+                    total_results,
+                    self.ui_state.layout.history_picker.expect(
+                        "history picker UI panel should be contained in the layout when in HP mode"
+                    ).height.saturating_sub(5) // accounting for borders (2) and input box (3)
+                        as usize,
+                );
+            }
         }
     }
 
@@ -525,6 +728,9 @@ impl Television {
             Mode::ActionPicker => {
                 self.ap_picker.reset_selection();
             }
+            Mode::HistoryPicker => {
+                self.hp_picker.reset_selection();
+            }
         }
     }
 
@@ -537,6 +743,9 @@ impl Television {
             Mode::ActionPicker => {
                 self.ap_picker.reset_input();
             }
+            Mode::HistoryPicker => {
+                self.hp_picker.reset_input();
+            }
         }
     }
 
@@ -593,6 +802,15 @@ impl Television {
                     | Action::GoToNextChar
                     | Action::GoToInputStart
                     | Action::GoToInputEnd
+                    | Action::GoToPrevWord
+                    | Action::GoToNextWord
+                    | Action::DeleteNextWord
+                    | Action::DeleteToInputEnd
+                    | Action::TransposeChars
+                    | Action::Yank
+                    | Action::YankPop
+                    | Action::Undo
+                    | Action::Redo
                     | Action::ToggleSelectionDown
                     | Action::ToggleSelectionUp
                     | Action::ConfirmSelection
@@ -609,11 +827,15 @@ impl Television {
                     | Action::ToggleStatusBar
                     | Action::ToggleRemoteControl
                     | Action::ToggleActionPicker
+                    | Action::ToggleHistoryPicker
                     | Action::ToggleOrientation
+                    | Action::EnterNormalMode
+                    | Action::EnterInsertMode
                     | Action::CopyEntryToClipboard
                     | Action::CycleSources
                     | Action::CyclePreviews
                     | Action::ReloadSource
+                    | Action::ForgetEntry
             ))
             // We want to avoid too much rendering while the channel is reloading
             // to prevent UI flickering.
@@ -627,47 +849,99 @@ impl Television {
         selected_entry: &Option<Entry>,
     ) -> Result<()> {
         if let Some(selected_entry) = selected_entry {
+            let heights = self.preview_pane_heights();
             if let Some((sender, receiver)) = &mut self.preview_handles {
                 // send a preview request if the preview state is out of sync
                 // with the currently selected entry
                 // FIXME: this can't only rely on raw (ex: lines numbers may change for text
                 // but we don't want to regenerate the preview if the file is the same)
                 // NOTE: this is fine for now since we'll get a cache hit if cache is enabled
-                if selected_entry.raw != self.preview_state.preview.entry_raw {
-                    sender.send(PreviewRequest::Preview(Ticket::new(
-                        selected_entry.clone(),
-                    )))?;
+                let selection = self
+                    .merged_config
+                    .channel_preview_command
+                    .as_ref()
+                    .filter(|command| command.uses_selection())
+                    .map(|_| self.channel.selected_entries());
+                // all panes are refreshed together, so the first one is
+                // enough to tell whether they are out of sync
+                let current = &self.preview_panes[0].preview;
+                if selected_entry.raw != current.entry_raw
+                    || selection.is_some_and(|selection| {
+                        selection_id(selection) != current.selection_id
+                    })
+                {
+                    let mut ticket = Ticket::new(selected_entry.clone());
+                    if let Some(selection) = selection {
+                        ticket = ticket.with_selection(selection.clone());
+                    }
+                    sender.send(PreviewRequest::Preview(ticket))?;
                 }
-                // try to receive a preview update
-                if let Ok(preview) = receiver.try_recv() {
+                // try to receive preview updates, split previews being
+                // routed to the pane of the command that produced them
+                while let Ok(preview) = receiver.try_recv() {
+                    let pane = if self.preview_panes.len() > 1 {
+                        preview.preview_index
+                    } else {
+                        0
+                    };
+                    let Some(state) = self.preview_panes.get_mut(pane) else {
+                        continue;
+                    };
                     let initial_scroll = Self::calculate_scroll(
                         &preview,
-                        self.ui_state.layout.preview_window.as_ref(),
+                        heights.get(pane).copied(),
                     );
-                    self.preview_state.update(preview, initial_scroll);
+                    state.update(preview, initial_scroll);
                     self.action_tx.send(Action::Render)?;
                 }
             }
         } else {
-            self.preview_state.reset();
+            self.preview_panes.iter_mut().for_each(PreviewState::reset);
         }
         Ok(())
     }
 
-    fn calculate_scroll(
-        preview: &Preview,
-        preview_window: Option<&Rect>,
-    ) -> u16 {
-        if let Some(window) = preview_window
+    fn calculate_scroll(preview: &Preview, pane_height: Option<usize>) -> u16 {
+        if let Some(height) = pane_height
             && let Some(target_line) = preview.target_line
         {
-            // this places the target line 3 lines above the center of the preview window
-            return target_line
-                .saturating_sub((window.height / 2).saturating_sub(3));
+            // this places the target line 3 lines above the center of the preview pane
+            let height = u16::try_from(height).unwrap_or(u16::MAX);
+            return target_line.saturating_sub((height / 2).saturating_sub(3));
         }
         0
     }
 
+    /// The height of each preview pane in the last rendered layout.
+    fn preview_pane_heights(&self) -> Vec<usize> {
+        self.ui_state
+            .layout
+            .preview_window
+            .map(|window| {
+                preview_panes(window, &self.merged_config)
+                    .iter()
+                    .map(|pane| pane.height as usize)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Replaces the preview panes with empty ones, one for each pane of the
+    /// current preview.
+    fn reset_preview_panes(&mut self, enabled: bool) {
+        self.preview_panes =
+            vec![
+                PreviewState::new(enabled, Preview::default(), 0);
+                self.merged_config.preview_pane_count()
+            ];
+        self.focused_preview_pane = 0;
+    }
+
+    /// The preview pane scrolled by the preview actions.
+    fn focused_preview(&mut self) -> &mut PreviewState {
+        &mut self.preview_panes[self.focused_preview_pane]
+    }
+
     pub fn update_results_picker_state(&mut self) {
         {
             let offset = u32::try_from(self.results_picker.offset()).unwrap();
@@ -749,6 +1023,75 @@ impl Television {
             self.action_picker.as_ref().unwrap().total_count();
     }
 
+    pub fn update_hp_picker_state(&mut self) {
+        let Some(hp) = self.history_picker.as_ref() else {
+            return;
+        };
+
+        if self.hp_picker.selected().is_none() && hp.result_count() > 0 {
+            self.hp_picker.select(Some(0));
+            self.hp_picker.relative_select(Some(0));
+        }
+
+        {
+            let offset = u32::try_from(self.hp_picker.offset()).unwrap();
+            let height = self
+                .ui_state
+                .layout
+                .history_picker
+                .unwrap_or_default()
+                .height
+                .saturating_sub(5)
+                .into();
+            let new_entries = self
+                .history_picker
+                .as_mut()
+                .unwrap()
+                .results(height, offset);
+
+            self.hp_picker.entries = Arc::new(new_entries);
+        }
+        self.hp_picker.total_items =
+            self.history_picker.as_ref().unwrap().total_count();
+    }
+
+    /// Initialize the history picker with the given history entries, from
+    /// the most recent one.
+    ///
+    /// Entries of channels that can't be switched to are left out.
+    pub fn init_history_picker(&mut self, entries: &[&HistoryEntry]) {
+        let current_channel = self.current_channel();
+        let entries: Vec<&HistoryEntry> = entries
+            .iter()
+            .filter(|entry| {
+                entry.channel == current_channel
+                    || self.can_switch_to(&entry.channel)
+            })
+            .copied()
+            .collect();
+        self.history_picker = Some(HistoryPicker::new(&entries));
+    }
+
+    /// Whether [`Action::SwitchToChannel`] can switch to the given channel,
+    /// which needs the remote control.
+    fn can_switch_to(&self, channel_name: &str) -> bool {
+        self.remote_control
+            .as_ref()
+            .is_some_and(|rc| rc.cable_channels.has_channel(channel_name))
+    }
+
+    /// Close the history picker and go back to the channel, whose query was
+    /// left untouched.
+    fn close_history_picker(&mut self) {
+        self.reset_picker_input();
+        if let Some(hp) = self.history_picker.as_mut() {
+            hp.find(EMPTY_STRING);
+        }
+        self.reset_picker_selection();
+        self.mode = Mode::Channel;
+        self.current_pattern = self.results_picker.input.value().to_string();
+    }
+
     /// Initialize the action picker with the current channel's actions.
     fn init_action_picker(&mut self) {
         // Build a map from action strings to keybindings
@@ -786,22 +1129,17 @@ impl Television {
             Mode::Channel => &mut self.results_picker.input,
             Mode::RemoteControl => &mut self.rc_picker.input,
             Mode::ActionPicker => &mut self.ap_picker.input,
+            Mode::HistoryPicker => &mut self.hp_picker.input,
         };
-        input.handle(convert_action_to_input_request(action).unwrap());
-        match action {
-            Action::AddInputChar(_)
-            | Action::DeletePrevChar
-            | Action::DeletePrevWord
-            | Action::DeleteLine
-            | Action::DeleteNextChar => {
-                let new_pattern = input.value().to_string();
-                if new_pattern != self.current_pattern {
-                    self.current_pattern.clone_from(&new_pattern);
-                    self.find(&new_pattern);
-                    self.reset_picker_selection();
-                }
+        let response =
+            input.handle(convert_action_to_input_request(action).unwrap());
+        if response.is_some_and(|changed| changed.value) {
+            let new_pattern = input.value().to_string();
+            if new_pattern != self.current_pattern {
+                self.current_pattern.clone_from(&new_pattern);
+                self.find(&new_pattern);
+                self.reset_picker_selection();
             }
-            _ => {}
         }
     }
 
@@ -869,6 +1207,24 @@ impl Television {
                         .send(Action::ExternalAction(entry.action_string))?;
                 }
             }
+            Mode::HistoryPicker => {
+                if let Some(entry) = self.get_selected_history_entry() {
+                    self.close_history_picker();
+                    if entry.channel == self.current_channel() {
+                        self.set_pattern(&entry.query);
+                    } else if self.can_switch_to(&entry.channel) {
+                        // queries of other channels are run in their channel
+                        self.action_tx
+                            .send(Action::SwitchToChannel(entry.channel))?;
+                        self.action_tx.send(Action::SetQuery(entry.query))?;
+                    } else {
+                        warn!(
+                            "Can't switch to channel {} to run its query",
+                            entry.channel
+                        );
+                    }
+                }
+            }
         }
         Ok(())
     }
@@ -886,6 +1242,21 @@ impl Television {
         })
     }
 
+    pub fn get_selected_history_entry(
+        &mut self,
+    ) -> Option<HistoryPickerEntry> {
+        if self
+            .history_picker
+            .as_ref()
+            .is_none_or(|hp| hp.result_count() == 0)
+        {
+            return None;
+        }
+        self.selected_index().and_then(|idx| {
+            self.history_picker.as_mut().map(|hp| hp.get_result(idx))
+        })
+    }
+
     pub fn handle_copy_entry_to_clipboard(&mut self) {
         if self.mode == Mode::Channel
             && let Some(entries) = self.get_selected_entries()
@@ -900,6 +1271,20 @@ impl Television {
         }
     }
 
+    /// Forget the frecency of the selected entry, which then loses its
+    /// ranking boost.
+    pub fn forget_selected_entry(&mut self) {
+        if self.mode == Mode::Channel
+            && let Some(entry) = &self.currently_selected
+            && self.frecency.remove(
+                &self.current_channel(),
+                &self.channel.frecency_key(&entry.raw),
+            )
+        {
+            self.channel.refresh_frecency(&self.frecency);
+        }
+    }
+
     pub fn cycle_sources(&mut self) {
         if self.mode == Mode::Channel {
             self.channel.cycle_sources();
@@ -908,7 +1293,12 @@ impl Television {
     }
 
     pub fn cycle_previews(&mut self) {
-        if self.mode == Mode::Channel
+        // every command is already shown when the preview is split, cycling
+        // moves the focus to the next pane instead
+        if self.mode == Mode::Channel && self.preview_panes.len() > 1 {
+            self.focused_preview_pane =
+                (self.focused_preview_pane + 1) % self.preview_panes.len();
+        } else if self.mode == Mode::Channel
             && let Some((sender, _)) = &self.preview_handles
         {
             sender.send(PreviewRequest::CycleCommand).expect(
@@ -917,6 +1307,85 @@ impl Television {
         }
     }
 
+    /// The state of the current channel, saved for channels that resume
+    /// their session.
+    pub fn session_state(&self) -> SessionState {
+        SessionState {
+            query: self.results_picker.input.value().to_string(),
+            source_index: self.channel.source_index(),
+            preview_index: self.preview_panes[0].preview.preview_index,
+            focused_preview_pane: self.focused_preview_pane,
+            orientation: self.merged_config.layout,
+            preview_hidden: self.merged_config.preview_panel_hidden,
+        }
+    }
+
+    /// Restores the saved state of the current channel.
+    ///
+    /// Options explicitly passed on the command line take precedence.
+    pub fn restore_session(&mut self, state: &SessionState) {
+        let cli = self.layered_config.channel_cli();
+        let restore_query = cli.input.is_none();
+        let restore_layout = cli.layout.is_none();
+        let restore_preview_hidden = !cli.hide_preview && !cli.show_preview;
+
+        self.channel.set_source_index(state.source_index);
+        if self.preview_panes.len() > 1 {
+            // split previews show every command, only the focus is restored
+            self.focused_preview_pane =
+                state.focused_preview_pane % self.preview_panes.len();
+        } else if let Some(command) =
+            &self.merged_config.channel_preview_command
+        {
+            for _ in 0..state.preview_index % command.inner.len().max(1) {
+                self.cycle_previews();
+            }
+        }
+        let config = Arc::make_mut(&mut self.merged_config);
+        if restore_layout {
+            config.layout = state.orientation;
+        }
+        if restore_preview_hidden && !config.preview_panel_disabled {
+            config.preview_panel_hidden = state.preview_hidden;
+        }
+        if restore_query {
+            self.set_pattern(&state.query);
+        }
+    }
+
+    /// Replaces the preview command of the current channel.
+    pub fn change_preview(&mut self, command: &str) {
+        let template = match Template::parse(command) {
+            Ok(template) => template,
+            Err(e) => {
+                error!("Invalid preview command '{}': {}", command, e);
+                return;
+            }
+        };
+        let mut command = CommandSpec::from(template);
+        command.shell = self.merged_config.shell;
+        if let Some((sender, _)) = &self.preview_handles {
+            sender
+                .send(PreviewRequest::Shutdown)
+                .expect("Failed to send shutdown signal to previewer");
+        }
+        let merged_config = Arc::make_mut(&mut self.merged_config);
+        merged_config.channel_preview_command = Some(command.clone());
+        merged_config.channel_preview_builtin = None;
+        merged_config.channel_preview_split = None;
+        self.preview_handles = Some(Self::setup_previewer(
+            &command,
+            None,
+            self.merged_config.channel_preview_cached,
+            self.merged_config.channel_preview_offset.clone(),
+            self.merged_config.preview_panel_header.clone(),
+            self.merged_config.preview_panel_footer.clone(),
+            self.merged_config.channel_preview_separator.clone(),
+            false,
+        ));
+        self.reset_preview_panes(!self.merged_config.preview_panel_hidden);
+    }
+
     pub fn handle_reload_source(&mut self) {
         if self.mode == Mode::Channel {
             let current_pattern = self.current_pattern.clone();
@@ -927,6 +1396,11 @@ impl Television {
     }
 
     pub fn handle_action(&mut self, action: &Action) -> Result<()> {
+        if self.param_prompt.is_some() {
+            self.handle_param_prompt_action(action);
+            return Ok(());
+        }
+
         // If popup is showing, only allow certain actions to dismiss it
         if self.missing_requirements_popup.is_some() {
             match action {
@@ -951,7 +1425,16 @@ impl Television {
             | Action::GoToInputEnd
             | Action::GoToInputStart
             | Action::GoToNextChar
-            | Action::GoToPrevChar => {
+            | Action::GoToPrevChar
+            | Action::GoToPrevWord
+            | Action::GoToNextWord
+            | Action::DeleteNextWord
+            | Action::DeleteToInputEnd
+            | Action::TransposeChars
+            | Action::Yank
+            | Action::YankPop
+            | Action::Undo
+            | Action::Redo => {
                 self.handle_input_action(action);
             }
             Action::SelectNextEntry => {
@@ -986,13 +1469,13 @@ impl Television {
                     );
                 }
             }
-            Action::ScrollPreviewDown => self.preview_state.scroll_down(1),
-            Action::ScrollPreviewUp => self.preview_state.scroll_up(1),
+            Action::ScrollPreviewDown => self.focused_preview().scroll_down(1),
+            Action::ScrollPreviewUp => self.focused_preview().scroll_up(1),
             Action::ScrollPreviewHalfPageDown => {
-                self.preview_state.scroll_down(20);
+                self.focused_preview().scroll_down(20);
             }
             Action::ScrollPreviewHalfPageUp => {
-                self.preview_state.scroll_up(20);
+                self.focused_preview().scroll_up(20);
             }
 
             Action::ToggleSelectionDown | Action::ToggleSelectionUp => {
@@ -1013,12 +1496,25 @@ impl Television {
             Action::ReloadSource | Action::WatchTimer => {
                 self.handle_reload_source();
             }
+            Action::ForgetEntry => {
+                self.forget_selected_entry();
+            }
             Action::SwitchToChannel(channel_name) => {
                 if let Some(rc) = &self.remote_control {
-                    let prototype = rc.zap(channel_name);
-                    self.change_channel(&prototype);
+                    if rc.cable_channels.has_channel(channel_name) {
+                        let prototype = rc.zap(channel_name);
+                        self.change_channel(&prototype);
+                    } else {
+                        warn!("Unknown channel: {}", channel_name);
+                    }
                 }
             }
+            Action::SetQuery(pattern) => {
+                self.set_pattern(pattern);
+            }
+            Action::ChangePreview(command) => {
+                self.change_preview(command);
+            }
             Action::ToggleRemoteControl => {
                 if self.remote_control.is_none()
                     || self.merged_config.remote_disabled
@@ -1060,6 +1556,16 @@ impl Television {
                             .find(EMPTY_STRING);
                         self.ticks = 0;
                     }
+                    Mode::HistoryPicker => {
+                        // Close history picker and open remote control
+                        self.close_history_picker();
+                        self.mode = Mode::RemoteControl;
+                        self.remote_control
+                            .as_mut()
+                            .unwrap()
+                            .find(EMPTY_STRING);
+                        self.ticks = 0;
+                    }
                 }
             }
             Action::ToggleActionPicker => {
@@ -1099,6 +1605,61 @@ impl Television {
                         }
                         self.ticks = 0;
                     }
+                    Mode::HistoryPicker => {
+                        // Close history picker and open action picker
+                        self.close_history_picker();
+                        self.init_action_picker();
+                        self.mode = Mode::ActionPicker;
+                        if let Some(ap) = self.action_picker.as_mut() {
+                            ap.find(EMPTY_STRING);
+                        }
+                        self.ticks = 0;
+                    }
+                }
+            }
+            Action::ToggleHistoryPicker => {
+                // The picker is initialized by the app, which owns the history
+                if self.history_picker.is_none() {
+                    return Ok(());
+                }
+                match self.mode {
+                    Mode::Channel => {
+                        self.mode = Mode::HistoryPicker;
+                        if let Some(hp) = self.history_picker.as_mut() {
+                            hp.find(EMPTY_STRING);
+                        }
+                        self.ticks = 0;
+                    }
+                    Mode::HistoryPicker => {
+                        self.close_history_picker();
+                    }
+                    Mode::RemoteControl => {
+                        // Close remote control and open history picker
+                        self.reset_picker_input();
+                        self.remote_control
+                            .as_mut()
+                            .unwrap()
+                            .find(EMPTY_STRING);
+                        self.reset_picker_selection();
+                        self.mode = Mode::HistoryPicker;
+                        if let Some(hp) = self.history_picker.as_mut() {
+                            hp.find(EMPTY_STRING);
+                        }
+                        self.ticks = 0;
+                    }
+                    Mode::ActionPicker => {
+                        // Close action picker and open history picker
+                        self.reset_picker_input();
+                        if let Some(ap) = self.action_picker.as_mut() {
+                            ap.find(EMPTY_STRING);
+                        }
+                        self.reset_picker_selection();
+                        self.mode = Mode::HistoryPicker;
+                        if let Some(hp) = self.history_picker.as_mut() {
+                            hp.find(EMPTY_STRING);
+                        }
+                        self.ticks = 0;
+                    }
                 }
             }
             Action::ToggleHelp => {
@@ -1124,6 +1685,14 @@ impl Television {
                     config.status_bar_hidden = !config.status_bar_hidden;
                 }
             }
+            Action::EnterNormalMode => {
+                if self.merged_config.input_map.is_modal() {
+                    self.input_mode = InputMode::Normal;
+                }
+            }
+            Action::EnterInsertMode => {
+                self.input_mode = InputMode::Insert;
+            }
             Action::ToggleOrientation => {
                 let config = Arc::make_mut(&mut self.merged_config);
                 match config.layout {
@@ -1174,6 +1743,10 @@ impl Television {
             self.update_ap_picker_state();
         }
 
+        if self.history_picker.is_some() && self.mode == Mode::HistoryPicker {
+            self.update_hp_picker_state();
+        }
+
         if self.mode == Mode::Channel {
             let selected_entry = self.get_selected_entry();
             self.update_preview_state(&selected_entry)?;
@@ -1189,15 +1762,30 @@ impl Television {
     }
 }
 
+/// How the entries of the current channel are recorded and ranked, with
+/// projects resolved from the working directory.
+fn channel_frecency(merged_config: &MergedConfig) -> ChannelFrecency {
+    let cwd = std::env::current_dir().unwrap_or_default();
+    ChannelFrecency {
+        channel_name: merged_config.channel_name.clone(),
+        key: merged_config.channel_frecency_key,
+        scope: merged_config.channel_frecency_scope.resolve(&cwd),
+        global_weight: merged_config.channel_frecency_global_weight,
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::{
         action::{Action, Actions},
         cable::Cable,
+        channels::prototypes::{ChannelParam, Template},
         cli::{ChannelCli, GlobalCli},
         config::layers::ConfigLayers,
         event::Key,
         frecency::Frecency,
+        screen::layout::Orientation,
+        session::SessionState,
         television::{MatchingMode, Mode, Television},
     };
     use std::sync::Arc;
@@ -1217,6 +1805,14 @@ mod test {
             Television::preprocess_pattern(MatchingMode::Substring, mult_word),
             expect_mult
         );
+        // operators and quoted phrases are left as is
+        assert_eq!(
+            Television::preprocess_pattern(
+                MatchingMode::Substring,
+                r#"foo | !bar "baz qux" ^a b$"#
+            ),
+            r#"'foo | !bar "baz qux" ^a b$"#
+        );
     }
 
     #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
@@ -1252,6 +1848,54 @@ mod test {
         assert!(tv.remote_control.is_none());
     }
 
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_history_picker_without_remote_control() {
+        use crate::cli::PostProcessedCli;
+        use crate::history::HistoryEntry;
+
+        let prototype = crate::channels::prototypes::ChannelPrototype::new(
+            "test", "echo 1",
+        );
+        let cli_args = PostProcessedCli {
+            global: GlobalCli {
+                no_remote: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let layered_config = ConfigLayers::new(
+            crate::config::Config::default(),
+            prototype.clone(),
+            cli_args,
+        );
+        let dir = tempdir().unwrap();
+        let mut tv = Television::new(
+            tokio::sync::mpsc::unbounded_channel().0,
+            layered_config,
+            Cable::from_prototypes(vec![
+                prototype,
+                crate::channels::prototypes::ChannelPrototype::new(
+                    "other", "echo 2",
+                ),
+            ]),
+            Arc::new(Frecency::new(100, dir.path())),
+        );
+        let here = HistoryEntry::new("here".into(), "test".into());
+        let other = HistoryEntry::new("other".into(), "other".into());
+
+        // other channels' queries can't be run without switching channels
+        tv.init_history_picker(&[&other, &here]);
+        let hp = tv.history_picker.as_mut().unwrap();
+        let mut results = hp.results(10, 0);
+        while hp.running() {
+            results = hp.results(10, 0);
+        }
+        assert_eq!(
+            results.iter().map(|e| e.query.as_str()).collect::<Vec<_>>(),
+            vec!["here"]
+        );
+    }
+
     #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
     async fn test_channel_keybindings_take_precedence() {
         use crate::cli::PostProcessedCli;
@@ -1298,4 +1942,197 @@ mod test {
             Some(&Actions::single(Action::SelectNextEntry)),
         );
     }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_restore_session_respects_cli() {
+        use crate::cli::PostProcessedCli;
+
+        let mut prototype = crate::channels::prototypes::ChannelPrototype::new(
+            "test", "echo 1",
+        );
+        prototype
+            .source
+            .command
+            .inner
+            .push(Template::parse("echo 2").unwrap().into());
+        let cli_args = PostProcessedCli {
+            channel: ChannelCli {
+                layout: Some(Orientation::Landscape),
+                ..Default::default()
+            },
+            global: GlobalCli::default(),
+        };
+        let layered_config = ConfigLayers::new(
+            crate::config::Config::default(),
+            prototype,
+            cli_args,
+        );
+        let dir = tempdir().unwrap();
+        let frecency = Arc::new(Frecency::new(100, dir.path()));
+        let mut tv = Television::new(
+            tokio::sync::mpsc::unbounded_channel().0,
+            layered_config,
+            Cable::from_prototypes(vec![]),
+            frecency,
+        );
+
+        tv.restore_session(&SessionState {
+            query: "foo".to_string(),
+            source_index: 1,
+            preview_index: 0,
+            focused_preview_pane: 0,
+            orientation: Orientation::Portrait,
+            preview_hidden: true,
+        });
+
+        assert_eq!(tv.current_pattern, "foo");
+        assert_eq!(tv.channel.source_index(), 1);
+        assert!(tv.merged_config.preview_panel_hidden);
+        // `--layout` was passed explicitly
+        assert_eq!(tv.merged_config.layout, Orientation::Landscape);
+        assert_eq!(tv.session_state().query, "foo");
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn test_param_prompt() {
+        let mut prototype = crate::channels::prototypes::ChannelPrototype::new(
+            "test",
+            "echo {param:greeting}",
+        );
+        prototype.source.command.shell =
+            Some(crate::utils::shell::Shell::Bash);
+        prototype
+            .metadata
+            .params
+            .insert("greeting".to_string(), ChannelParam::default());
+        let layered_config = ConfigLayers::new(
+            crate::config::Config::default(),
+            prototype,
+            crate::cli::PostProcessedCli::default(),
+        );
+        let dir = tempdir().unwrap();
+        let mut tv = Television::new(
+            tokio::sync::mpsc::unbounded_channel().0,
+            layered_config,
+            Cable::from_prototypes(vec![]),
+            Arc::new(Frecency::new(100, dir.path())),
+        );
+
+        let prompt = tv.param_prompt.as_ref().unwrap();
+        assert_eq!(prompt.params[0].0, "greeting");
+        // the initial channel can't be left without its parameters
+        tv.handle_action(&Action::Quit).unwrap();
+        assert!(tv.param_prompt.is_some());
+
+        for c in "hi".chars() {
+            tv.handle_action(&Action::AddInputChar(c)).unwrap();
+        }
+        tv.handle_action(&Action::ConfirmSelection).unwrap();
+
+        assert!(tv.param_prompt.is_none());
+        assert_eq!(tv.channel.current_command(), "echo 'hi'");
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn test_cli_params_only_apply_to_first_channel() {
+        let new_prototype = |name: &str, default: Option<&str>| {
+            let mut prototype =
+                crate::channels::prototypes::ChannelPrototype::new(
+                    name,
+                    "echo {param:greeting}",
+                );
+            prototype.source.command.shell =
+                Some(crate::utils::shell::Shell::Bash);
+            prototype.metadata.params.insert(
+                "greeting".to_string(),
+                ChannelParam {
+                    default: default.map(str::to_string),
+                    ..Default::default()
+                },
+            );
+            prototype
+        };
+        let first = new_prototype("first", None);
+        let mut cli = crate::cli::PostProcessedCli::default();
+        cli.channel
+            .params
+            .insert("greeting".to_string(), "hi".to_string());
+        let layered_config = ConfigLayers::new(
+            crate::config::Config::default(),
+            first.clone(),
+            cli,
+        );
+        let dir = tempdir().unwrap();
+        let mut tv = Television::new(
+            tokio::sync::mpsc::unbounded_channel().0,
+            layered_config,
+            Cable::from_prototypes(vec![]),
+            Arc::new(Frecency::new(100, dir.path())),
+        );
+        assert!(tv.param_prompt.is_none());
+        assert_eq!(tv.channel.current_command(), "echo 'hi'");
+
+        // other channels keep their own defaults
+        tv.change_channel(&new_prototype("second", Some("hello")));
+        assert!(tv.param_prompt.is_none());
+        assert_eq!(tv.channel.current_command(), "echo 'hello'");
+
+        tv.change_channel(&new_prototype("third", None));
+        assert!(tv.param_prompt.is_some());
+        tv.handle_action(&Action::Quit).unwrap();
+        assert!(tv.param_prompt.is_none());
+
+        // the first channel still gets the values it was given
+        tv.change_channel(&first);
+        assert!(tv.param_prompt.is_none());
+        assert_eq!(tv.channel.current_command(), "echo 'hi'");
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn test_split_preview() {
+        use crate::channels::prototypes::{
+            ChannelPrototype, PreviewSpec, PreviewSplit,
+        };
+
+        let mut prototype = ChannelPrototype::new("test", "echo foo");
+        let mut preview = PreviewSpec::from_str_command("echo {}");
+        preview
+            .command
+            .inner
+            .push(Template::parse("cat {}").unwrap().into());
+        preview.split = Some(PreviewSplit::Vertical);
+        prototype.preview = Some(preview);
+        let layered_config = ConfigLayers::new(
+            crate::config::Config::default(),
+            prototype,
+            crate::cli::PostProcessedCli::default(),
+        );
+        let dir = tempdir().unwrap();
+        let mut tv = Television::new(
+            tokio::sync::mpsc::unbounded_channel().0,
+            layered_config,
+            Cable::from_prototypes(vec![]),
+            Arc::new(Frecency::new(100, dir.path())),
+        );
+
+        assert_eq!(tv.preview_panes.len(), 2);
+        // cycling moves the focus across panes, and back to the first one
+        tv.handle_action(&Action::CyclePreviews).unwrap();
+        assert_eq!(tv.focused_preview_pane, 1);
+        let state = tv.session_state();
+        assert_eq!(state.focused_preview_pane, 1);
+        assert_eq!(state.preview_index, 0);
+        tv.handle_action(&Action::CyclePreviews).unwrap();
+        assert_eq!(tv.focused_preview_pane, 0);
+
+        // restoring the session focuses the same pane without cycling
+        tv.restore_session(&state);
+        assert_eq!(tv.focused_preview_pane, 1);
+        tv.handle_action(&Action::CyclePreviews).unwrap();
+
+        // a preview command given at runtime isn't split
+        tv.change_preview("echo {}");
+        assert_eq!(tv.preview_panes.len(), 1);
+        assert_eq!(tv.focused_preview_pane, 0);
+    }
 }
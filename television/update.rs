@@ -0,0 +1,733 @@
+//! Updating the local cable directory with `tv update-channels`.
+//!
+//! Channels can be fetched from the television repository (the default), a
+//! local directory such as a git checkout, or an index file listing channel
+//! files and URLs. Files written by the updater are recorded in a manifest
+//! kept in the cable directory so that later updates can tell them apart
+//! from channels edited or created locally, which are left untouched unless
+//! `--force` is given.
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    path::{Component, Path, PathBuf},
+};
+
+use anyhow::{Context, Result, bail};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::{
+    cable::{CHANNEL_FILE_FORMAT, get_cable_files},
+    channels::prototypes::{BinaryRequirement, Metadata},
+    gh::{DEFAULT_CABLE_DIR_PATH, GithubSource},
+    utils::paths::expand_tilde,
+};
+
+const MANIFEST_FILE_NAME: &str = ".installed-channels.json";
+
+/// A channel prototype fetched from a [`ChannelSource`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DownloadedPrototype {
+    pub name: String,
+    pub content: String,
+}
+
+impl DownloadedPrototype {
+    pub fn new(name: String, content: String) -> Self {
+        Self { name, content }
+    }
+
+    /// Parses `content` to find the name of the channel, printing a warning
+    /// and returning `None` if it isn't a valid channel.
+    pub(crate) fn parse(origin: &str, content: String) -> Option<Self> {
+        match toml::from_str::<ChannelFile>(&content) {
+            Ok(file) => Some(Self::new(file.metadata.name, content)),
+            Err(e) => {
+                println!(
+                    "  {} {}: {}",
+                    "Ignoring invalid channel".red(),
+                    origin.yellow(),
+                    e.message()
+                );
+                None
+            }
+        }
+    }
+}
+
+/// The part of a channel file the updater reads, which doesn't need the
+/// channel it extends to be resolved.
+#[derive(Debug, Deserialize)]
+struct ChannelFile {
+    metadata: Metadata,
+}
+
+/// A place channel prototypes can be fetched from.
+pub trait ChannelSource {
+    /// A stable identifier of the source, recorded in the manifest next to
+    /// the channels it installed.
+    fn id(&self) -> String;
+
+    /// A human readable description of the source.
+    fn describe(&self) -> String {
+        self.id()
+    }
+
+    fn fetch(&self) -> Result<Vec<DownloadedPrototype>>;
+}
+
+/// Resolves the value of `--from` into a channel source.
+///
+/// URLs and files are read as indexes, directories are scanned for channel
+/// files and no value at all means the television repository.
+pub fn source_from(from: Option<&str>) -> Result<Box<dyn ChannelSource>> {
+    let Some(from) = from else {
+        return Ok(Box::new(GithubSource::new(env!("CARGO_PKG_VERSION"))));
+    };
+    if is_url(from) {
+        return Ok(Box::new(IndexSource::Url(from.to_string())));
+    }
+    let path = expand_tilde(from);
+    if path.is_dir() {
+        Ok(Box::new(DirectorySource::new(path)))
+    } else if path.is_file() {
+        Ok(Box::new(IndexSource::File(path)))
+    } else {
+        bail!("Channel source not found: {}", path.display())
+    }
+}
+
+fn is_url(s: &str) -> bool {
+    s.starts_with("https://") || s.starts_with("http://")
+}
+
+/// Fetches a plain URL.
+///
+/// Unlike requests to the GitHub API, these never carry the user's token
+/// since the URL may point anywhere.
+fn fetch_url(url: &str) -> Result<String> {
+    let response = ureq::get(url)
+        .header("User-Agent", "television-client")
+        .call()
+        .with_context(|| format!("Failed to fetch {url}"))?;
+    Ok(response.into_body().read_to_string()?)
+}
+
+/// A directory of channel files, e.g. a checkout of the television
+/// repository or of a personal channel collection.
+///
+/// When the directory contains the repository's `cable/<os>` layout, only
+/// the channels for the current platform are picked up.
+pub struct DirectorySource {
+    root: PathBuf,
+}
+
+impl DirectorySource {
+    pub fn new(root: PathBuf) -> Self {
+        Self {
+            root: root.canonicalize().unwrap_or(root),
+        }
+    }
+
+    fn channels_dir(&self) -> PathBuf {
+        let platform_dir = self.root.join(DEFAULT_CABLE_DIR_PATH);
+        if platform_dir.is_dir() {
+            platform_dir
+        } else {
+            self.root.clone()
+        }
+    }
+}
+
+impl ChannelSource for DirectorySource {
+    fn id(&self) -> String {
+        format!("dir:{}", self.root.display())
+    }
+
+    fn describe(&self) -> String {
+        self.channels_dir().display().to_string()
+    }
+
+    fn fetch(&self) -> Result<Vec<DownloadedPrototype>> {
+        let mut files = get_cable_files(self.channels_dir());
+        files.sort();
+        let mut prototypes = Vec::new();
+        for file in files {
+            let content =
+                std::fs::read_to_string(&file).with_context(|| {
+                    format!("Failed to read {}", file.display())
+                })?;
+            prototypes.extend(DownloadedPrototype::parse(
+                &file.display().to_string(),
+                content,
+            ));
+        }
+        Ok(prototypes)
+    }
+}
+
+/// The contents of an index file.
+///
+/// ```toml
+/// channels = [
+///     "channels/files.toml",
+///     "https://example.com/channels/git-log.toml",
+/// ]
+/// ```
+///
+/// Relative entries are resolved against the location of the index.
+#[derive(Debug, Deserialize)]
+struct Index {
+    channels: Vec<String>,
+}
+
+/// An index file, either local or served over HTTP.
+pub enum IndexSource {
+    File(PathBuf),
+    Url(String),
+}
+
+impl IndexSource {
+    fn read(&self, location: &str) -> Result<String> {
+        if is_url(location) {
+            return fetch_url(location);
+        }
+        match self {
+            IndexSource::File(path) => {
+                let path =
+                    path.parent().unwrap_or(Path::new("")).join(location);
+                std::fs::read_to_string(&path).with_context(|| {
+                    format!("Failed to read {}", path.display())
+                })
+            }
+            IndexSource::Url(url) => {
+                let base = &url[..=url.rfind('/').unwrap_or(url.len() - 1)];
+                fetch_url(&format!("{base}{location}"))
+            }
+        }
+    }
+}
+
+impl ChannelSource for IndexSource {
+    fn id(&self) -> String {
+        match self {
+            IndexSource::File(path) => format!(
+                "index:{}",
+                path.canonicalize().unwrap_or(path.clone()).display()
+            ),
+            IndexSource::Url(url) => format!("index:{url}"),
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            IndexSource::File(path) => path.display().to_string(),
+            IndexSource::Url(url) => url.clone(),
+        }
+    }
+
+    fn fetch(&self) -> Result<Vec<DownloadedPrototype>> {
+        let index = match self {
+            IndexSource::File(path) => std::fs::read_to_string(path)
+                .with_context(|| {
+                    format!("Failed to read {}", path.display())
+                })?,
+            IndexSource::Url(url) => fetch_url(url)?,
+        };
+        let index: Index = toml::from_str(&index)
+            .with_context(|| format!("Invalid index {}", self.describe()))?;
+        let mut prototypes = Vec::new();
+        for location in &index.channels {
+            let content = self.read(location)?;
+            prototypes.extend(DownloadedPrototype::parse(location, content));
+        }
+        Ok(prototypes)
+    }
+}
+
+/// A channel installed by the updater.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct InstalledChannel {
+    /// The id of the source the channel was installed from.
+    source: String,
+    /// The hash of the file content as it was written.
+    hash: String,
+}
+
+/// The channels installed by the updater, keyed by channel name.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Manifest {
+    channels: BTreeMap<String, InstalledChannel>,
+}
+
+impl Manifest {
+    fn load(cable_dir: &Path) -> Result<Self> {
+        let path = cable_dir.join(MANIFEST_FILE_NAME);
+        if !path.exists() {
+            debug!("Channel manifest not found: {}", path.display());
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(&path)
+            .context("Failed to read channel manifest")?;
+        serde_json::from_str(&content)
+            .context("Failed to parse channel manifest")
+    }
+
+    fn save(&self, cable_dir: &Path) -> Result<()> {
+        let content = serde_json::to_string_pretty(self)
+            .context("Failed to serialize channel manifest")?;
+        std::fs::write(cable_dir.join(MANIFEST_FILE_NAME), content)
+            .context("Failed to write channel manifest")
+    }
+
+    /// Whether the local `content` of a channel is the one the updater
+    /// wrote.
+    fn is_pristine(&self, name: &str, content: &str) -> bool {
+        self.channels
+            .get(name)
+            .is_some_and(|c| c.hash == content_hash(content))
+    }
+}
+
+/// A 64-bit FNV-1a hash of `content`, stable across versions and platforms.
+fn content_hash(content: &str) -> String {
+    let hash = content.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, b| {
+        (hash ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3)
+    });
+    format!("{hash:016x}")
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change {
+    Added,
+    Changed,
+    Removed,
+    Unchanged,
+    Skipped(String),
+}
+
+/// What an update does to a single channel.
+#[derive(Debug, Clone)]
+pub struct PlannedChange {
+    pub name: String,
+    pub change: Change,
+    /// The new content of the channel, if it is written.
+    content: Option<String>,
+}
+
+impl Display for PlannedChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = self.name.bold();
+        match &self.change {
+            Change::Added => write!(f, "  {} {}", "+".green().bold(), name),
+            Change::Changed => write!(f, "  {} {}", "~".yellow().bold(), name),
+            Change::Removed => write!(f, "  {} {}", "-".red().bold(), name),
+            Change::Unchanged => write!(f, "  {} {}", "=".dimmed(), name),
+            Change::Skipped(reason) => {
+                write!(f, "  {} {} ({})", "!".dimmed(), name, reason.dimmed())
+            }
+        }
+    }
+}
+
+fn missing_requirements(content: &str) -> Result<Vec<String>> {
+    let mut requirements = toml::from_str::<ChannelFile>(content)?
+        .metadata
+        .requirements;
+    requirements.iter_mut().for_each(BinaryRequirement::init);
+    Ok(requirements
+        .into_iter()
+        .filter(|r| !r.is_met())
+        .map(|r| r.bin_name)
+        .collect())
+}
+
+/// Gets the path of the file of the channel `name` in `cable_dir`.
+///
+/// Channel names come from fetched files, so names that aren't a plain file
+/// name, e.g. `../.bashrc`, are rejected rather than pointing outside of
+/// `cable_dir`.
+fn channel_path(cable_dir: &Path, name: &str) -> Result<PathBuf> {
+    let mut components = Path::new(name).components();
+    if name.contains(['/', '\\'])
+        || !matches!(
+            (components.next(), components.next()),
+            (Some(Component::Normal(_)), None)
+        )
+    {
+        bail!("invalid channel name '{name}'");
+    }
+    Ok(cable_dir.join(format!("{name}.{CHANNEL_FILE_FORMAT}")))
+}
+
+/// Computes the changes needed to bring `cable_dir` up to date with the
+/// channels fetched from the source identified by `source_id`.
+///
+/// Channels that weren't installed by the updater or were modified since
+/// are skipped unless `force` is set, and so are new channels whose
+/// requirements aren't met. Channels installed from the same source that
+/// disappeared upstream are removed.
+fn plan(
+    cable_dir: &Path,
+    manifest: &Manifest,
+    source_id: &str,
+    mut prototypes: Vec<DownloadedPrototype>,
+    force: bool,
+) -> Result<Vec<PlannedChange>> {
+    prototypes.sort_by(|a, b| a.name.cmp(&b.name));
+    prototypes.dedup_by(|a, b| a.name == b.name);
+
+    let mut changes = Vec::new();
+    for p in &prototypes {
+        let path = match channel_path(cable_dir, &p.name) {
+            Ok(path) => path,
+            Err(e) => {
+                changes.push(PlannedChange {
+                    name: p.name.clone(),
+                    change: Change::Skipped(e.to_string()),
+                    content: None,
+                });
+                continue;
+            }
+        };
+        let change = match std::fs::read_to_string(&path).ok() {
+            None => {
+                let missing = missing_requirements(&p.content)?;
+                if missing.is_empty() || force {
+                    Change::Added
+                } else {
+                    Change::Skipped(format!(
+                        "requirement {} is unavailable on this system",
+                        missing.join(", ")
+                    ))
+                }
+            }
+            Some(local) if local == p.content => Change::Unchanged,
+            Some(local) => {
+                if force || manifest.is_pristine(&p.name, &local) {
+                    Change::Changed
+                } else if manifest.channels.contains_key(&p.name) {
+                    Change::Skipped("modified locally".to_string())
+                } else {
+                    Change::Skipped(
+                        "not installed by update-channels".to_string(),
+                    )
+                }
+            }
+        };
+        changes.push(PlannedChange {
+            name: p.name.clone(),
+            change,
+            content: Some(p.content.clone()),
+        });
+    }
+
+    for (name, installed) in &manifest.channels {
+        if installed.source != source_id
+            || prototypes.iter().any(|p| &p.name == name)
+        {
+            continue;
+        }
+        let path = match channel_path(cable_dir, name) {
+            Ok(path) => path,
+            Err(e) => {
+                changes.push(PlannedChange {
+                    name: name.clone(),
+                    change: Change::Skipped(e.to_string()),
+                    content: None,
+                });
+                continue;
+            }
+        };
+        let change = match std::fs::read_to_string(&path).ok() {
+            Some(local) if !force && !manifest.is_pristine(name, &local) => {
+                Change::Skipped(
+                    "removed upstream but modified locally".to_string(),
+                )
+            }
+            _ => Change::Removed,
+        };
+        changes.push(PlannedChange {
+            name: name.clone(),
+            change,
+            content: None,
+        });
+    }
+    Ok(changes)
+}
+
+/// Applies the planned `changes` to `cable_dir` and records them in the
+/// manifest.
+fn apply(
+    cable_dir: &Path,
+    manifest: &mut Manifest,
+    source_id: &str,
+    changes: &[PlannedChange],
+) -> Result<()> {
+    for change in changes {
+        if matches!(change.change, Change::Skipped(_)) {
+            continue;
+        }
+        let path = channel_path(cable_dir, &change.name)?;
+        match (&change.change, &change.content) {
+            (Change::Added | Change::Changed, Some(content)) => {
+                std::fs::write(&path, content).with_context(|| {
+                    format!("Failed to write {}", path.display())
+                })?;
+                manifest.channels.insert(
+                    change.name.clone(),
+                    InstalledChannel {
+                        source: source_id.to_string(),
+                        hash: content_hash(content),
+                    },
+                );
+            }
+            // start tracking identical channels so they get future updates
+            (Change::Unchanged, Some(content)) => {
+                manifest.channels.entry(change.name.clone()).or_insert_with(
+                    || InstalledChannel {
+                        source: source_id.to_string(),
+                        hash: content_hash(content),
+                    },
+                );
+            }
+            (Change::Removed, _) => {
+                if path.exists() {
+                    std::fs::remove_file(&path).with_context(|| {
+                        format!("Failed to remove {}", path.display())
+                    })?;
+                }
+                manifest.channels.remove(&change.name);
+            }
+            _ => {}
+        }
+    }
+    manifest.save(cable_dir)
+}
+
+/// Updates the channels in `cable_dir` from the source given with `--from`.
+///
+/// With `dry_run`, the changes are only printed.
+pub fn update_local_channels(
+    cable_dir: &Path,
+    from: Option<&str>,
+    force: bool,
+    dry_run: bool,
+) -> Result<()> {
+    let source = source_from(from)?;
+    println!(
+        "{}{}",
+        "Fetching cable channels from ".bold(),
+        source.describe().green().bold()
+    );
+    let prototypes = source.fetch()?;
+    if prototypes.is_empty() {
+        bail!("No channels found in {}", source.describe());
+    }
+
+    let mut manifest = Manifest::load(cable_dir)?;
+    let changes = plan(cable_dir, &manifest, &source.id(), prototypes, force)?;
+    println!();
+    for change in changes.iter().filter(|c| c.change != Change::Unchanged) {
+        println!("{change}");
+    }
+    let count = |f: fn(&Change) -> bool| {
+        changes.iter().filter(|c| f(&c.change)).count()
+    };
+    println!(
+        "\n{} added, {} changed, {} removed, {} unchanged, {} skipped",
+        count(|c| *c == Change::Added),
+        count(|c| *c == Change::Changed),
+        count(|c| *c == Change::Removed),
+        count(|c| *c == Change::Unchanged),
+        count(|c| matches!(c, Change::Skipped(_))),
+    );
+    if changes
+        .iter()
+        .any(|c| matches!(c.change, Change::Skipped(_)))
+        && !force
+    {
+        println!("Use --force to overwrite skipped channels.");
+    }
+
+    if dry_run {
+        println!("{}", "\nDry run, no changes were made.".bold());
+        return Ok(());
+    }
+    if !cable_dir.exists() {
+        println!("  Creating cable directory at {}", cable_dir.display());
+        std::fs::create_dir_all(cable_dir)?;
+    }
+    apply(cable_dir, &mut manifest, &source.id(), &changes)?;
+    println!(
+        "{}",
+        "\nCable channels updated successfully.".green().bold()
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn channel(name: &str, command: &str) -> String {
+        format!(
+            "[metadata]\nname = \"{name}\"\n\n[source]\ncommand = \"{command}\"\n"
+        )
+    }
+
+    fn update(
+        cable_dir: &Path,
+        source: &dyn ChannelSource,
+        force: bool,
+    ) -> Vec<PlannedChange> {
+        let mut manifest = Manifest::load(cable_dir).unwrap();
+        let changes = plan(
+            cable_dir,
+            &manifest,
+            &source.id(),
+            source.fetch().unwrap(),
+            force,
+        )
+        .unwrap();
+        apply(cable_dir, &mut manifest, &source.id(), &changes).unwrap();
+        changes
+    }
+
+    fn change_of(changes: &[PlannedChange], name: &str) -> Change {
+        changes
+            .iter()
+            .find(|c| c.name == name)
+            .map(|c| c.change.clone())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_update_from_directory() {
+        let upstream = tempfile::tempdir().unwrap();
+        let cable = tempfile::tempdir().unwrap();
+        let write = |dir: &Path, name: &str, command: &str| {
+            std::fs::write(
+                dir.join(format!("{name}.toml")),
+                channel(name, command),
+            )
+            .unwrap();
+        };
+        write(upstream.path(), "files", "fd");
+        write(upstream.path(), "dirs", "fd -t d");
+        write(upstream.path(), "env", "env");
+        write(cable.path(), "env", "printenv");
+        let source = DirectorySource::new(upstream.path().to_path_buf());
+
+        let changes = update(cable.path(), &source, false);
+        assert_eq!(change_of(&changes, "files"), Change::Added);
+        assert_eq!(change_of(&changes, "dirs"), Change::Added);
+        assert!(matches!(change_of(&changes, "env"), Change::Skipped(_)));
+
+        // installed channels follow upstream, edited ones are left alone
+        write(upstream.path(), "files", "fd -t f");
+        write(cable.path(), "dirs", "fd -t d -H");
+        std::fs::remove_file(upstream.path().join("dirs.toml")).unwrap();
+        let changes = update(cable.path(), &source, false);
+        assert_eq!(change_of(&changes, "files"), Change::Changed);
+        assert!(matches!(change_of(&changes, "dirs"), Change::Skipped(_)));
+        assert_eq!(
+            std::fs::read_to_string(cable.path().join("files.toml")).unwrap(),
+            channel("files", "fd -t f")
+        );
+        assert!(cable.path().join("dirs.toml").exists());
+
+        let changes = update(cable.path(), &source, true);
+        assert_eq!(change_of(&changes, "dirs"), Change::Removed);
+        assert_eq!(change_of(&changes, "env"), Change::Changed);
+        assert!(!cable.path().join("dirs.toml").exists());
+        assert_eq!(
+            std::fs::read_to_string(cable.path().join("env.toml")).unwrap(),
+            channel("env", "env")
+        );
+    }
+
+    #[test]
+    fn test_update_rejects_invalid_names() {
+        let upstream = tempfile::tempdir().unwrap();
+        let root = tempfile::tempdir().unwrap();
+        let cable = root.path().join("cable");
+        std::fs::create_dir(&cable).unwrap();
+        let names = ["../escaped", "/tmp/absolute", "a/b", "..", "a\\b"];
+        for (i, name) in names.iter().enumerate() {
+            std::fs::write(
+                upstream.path().join(format!("{i}.toml")),
+                channel(&name.replace('\\', "\\\\"), "ls"),
+            )
+            .unwrap();
+        }
+        std::fs::write(
+            upstream.path().join("dotted.toml"),
+            channel("a.b", "ls"),
+        )
+        .unwrap();
+        let source = DirectorySource::new(upstream.path().to_path_buf());
+
+        let changes = update(&cable, &source, true);
+        for name in names {
+            assert!(
+                matches!(change_of(&changes, name), Change::Skipped(_)),
+                "{name} wasn't rejected"
+            );
+        }
+        assert!(!root.path().join("escaped.toml").exists());
+        assert_eq!(change_of(&changes, "a.b"), Change::Added);
+        assert!(cable.join("a.b.toml").exists());
+
+        // names recorded in the manifest aren't trusted either
+        let mut manifest = Manifest::load(&cable).unwrap();
+        manifest.channels.insert(
+            "../escaped".to_string(),
+            InstalledChannel {
+                source: source.id(),
+                hash: String::new(),
+            },
+        );
+        std::fs::write(root.path().join("escaped.toml"), "").unwrap();
+        let changes =
+            plan(&cable, &manifest, &source.id(), Vec::new(), true).unwrap();
+        apply(&cable, &mut manifest, &source.id(), &changes).unwrap();
+        assert!(matches!(
+            change_of(&changes, "../escaped"),
+            Change::Skipped(_)
+        ));
+        assert!(root.path().join("escaped.toml").exists());
+    }
+
+    #[test]
+    fn test_index_source() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("channels")).unwrap();
+        std::fs::write(
+            dir.path().join("channels/files.toml"),
+            channel("files", "fd"),
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("index.toml"),
+            "channels = [\"channels/files.toml\"]\n",
+        )
+        .unwrap();
+
+        let source = source_from(Some(
+            &dir.path().join("index.toml").to_string_lossy(),
+        ))
+        .unwrap();
+        let prototypes = source.fetch().unwrap();
+
+        assert!(source.id().starts_with("index:"));
+        assert_eq!(
+            prototypes,
+            vec![DownloadedPrototype::new(
+                "files".to_string(),
+                channel("files", "fd")
+            )]
+        );
+    }
+}
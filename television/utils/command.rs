@@ -3,11 +3,10 @@ use crate::{
         entry::Entry,
         prototypes::{ActionSpec, ExecutionMode, Template},
     },
-    utils::{shell::Shell, strings::SPACE},
+    utils::{hashmaps::FxIndexSet, shell::Shell, strings::SPACE},
 };
 use anyhow::Result;
 use lazy_regex::{Lazy, Regex, regex};
-use rustc_hash::FxHashSet;
 use std::{
     collections::HashMap,
     process::{Command, ExitStatus, Stdio},
@@ -94,8 +93,8 @@ pub fn shell_command<S>(
 ///     channels::{entry::Entry, prototypes::Template},
 ///     utils::command::format_command
 /// };
-/// # use rustc_hash::FxHashSet;
-/// let mut entries = FxHashSet::default();
+/// # use television::utils::hashmaps::FxIndexSet;
+/// let mut entries = FxIndexSet::default();
 /// entries.insert(Entry::new("file1.txt".to_string()));
 /// entries.insert(Entry::new("file 2.txt".to_string()));
 /// let template = Template::parse("nvim {split:\\n:..|map:{append:'|prepend:'}|join: }").unwrap();
@@ -106,7 +105,7 @@ pub fn shell_command<S>(
 /// assert!(result.contains("'file 2.txt'"));
 /// ```
 pub fn format_command(
-    entries: &FxHashSet<Entry>,
+    entries: &FxIndexSet<Entry>,
     template: &Template,
     separator: &str,
 ) -> Result<String> {
@@ -154,6 +153,23 @@ pub fn format_command(
     }
 }
 
+/// Format the selected entries substituted for `{+}` in preview commands.
+///
+/// Each entry is quoted for `shell` and the entries are joined with `separator`, mirroring
+/// how `format_command` passes multiple entries to actions.
+pub fn format_selection(
+    entries: &FxIndexSet<Entry>,
+    separator: &str,
+    shell: Option<Shell>,
+) -> String {
+    let shell = shell.unwrap_or_else(|| Shell::from_env().unwrap_or_default());
+    entries
+        .iter()
+        .map(|entry| shell.quote(&entry.raw))
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
 /// Execute an external action with the appropriate execution mode and output handling
 ///
 /// Takes an `ActionSpec` and a set of entries, creates a command using the action's template,
@@ -171,7 +187,7 @@ pub fn format_command(
 /// - `ExecutionMode::Fork` - spawns the command as a child process
 pub fn execute_action(
     action_spec: &ActionSpec,
-    entries: &FxHashSet<Entry>,
+    entries: &FxIndexSet<Entry>,
 ) -> Result<ExitStatus> {
     debug!("Executing external action with {} entries", entries.len());
 
@@ -254,7 +270,7 @@ mod tests {
 
     #[test]
     fn test_simple_braces_syntactic_sugar() {
-        let mut entries = FxHashSet::default();
+        let mut entries = FxIndexSet::default();
         entries.insert(Entry::new("file1.txt".to_string()));
 
         // Simple braces should use syntactic sugar with quotes
@@ -265,7 +281,7 @@ mod tests {
 
     #[test]
     fn test_simple_braces_multiple_entries() {
-        let mut entries = FxHashSet::default();
+        let mut entries = FxIndexSet::default();
         entries.insert(Entry::new("file1.txt".to_string()));
         entries.insert(Entry::new("file2.txt".to_string()));
 
@@ -280,9 +296,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_entries_keep_selection_order() {
+        let mut entries = FxIndexSet::default();
+        entries.insert(Entry::new("b.txt".to_string()));
+        entries.insert(Entry::new("c.txt".to_string()));
+        entries.insert(Entry::new("a.txt".to_string()));
+
+        let template = Template::parse("cat {}").unwrap();
+        let result = format_command(&entries, &template, "\n").unwrap();
+        assert_eq!(result, "cat 'b.txt' 'c.txt' 'a.txt'");
+
+        let template = Template::parse("cat {split:\\n:..|join:,}").unwrap();
+        let result = format_command(&entries, &template, "\n").unwrap();
+        assert_eq!(result, "cat b.txt,c.txt,a.txt");
+    }
+
     #[test]
     fn test_simple_braces_with_quotes_in_filename() {
-        let mut entries = FxHashSet::default();
+        let mut entries = FxIndexSet::default();
         entries.insert(Entry::new("file's name.txt".to_string()));
 
         // Simple braces should escape single quotes in filenames
@@ -293,7 +325,7 @@ mod tests {
 
     #[test]
     fn test_complex_braces_use_template_system() {
-        let mut entries = FxHashSet::default();
+        let mut entries = FxIndexSet::default();
         entries.insert(Entry::new("file1.txt".to_string()));
         entries.insert(Entry::new("file2.txt".to_string()));
 
@@ -313,7 +345,7 @@ mod tests {
 
     #[test]
     fn test_complex_braces_use_template_system_with_quotes_in_filename() {
-        let mut entries = FxHashSet::default();
+        let mut entries = FxIndexSet::default();
         entries.insert(Entry::new("file1's.txt".to_string()));
         entries.insert(Entry::new("file2.txt".to_string()));
 
@@ -367,4 +399,21 @@ mod tests {
 
         assert_eq!(args, vec!["-i", "-c", "echo hi"]);
     }
+
+    #[test]
+    fn test_format_selection() {
+        let entries = FxIndexSet::from_iter([
+            Entry::new("b.txt".to_string()),
+            Entry::new("it's.txt".to_string()),
+        ]);
+
+        assert_eq!(
+            format_selection(&entries, " ", Some(Shell::Bash)),
+            r"'b.txt' 'it'\''s.txt'"
+        );
+        assert_eq!(
+            format_selection(&entries, ",", Some(Shell::Cmd)),
+            r#""b.txt","it's.txt""#
+        );
+    }
 }
@@ -1,6 +1,13 @@
 use std::hash::Hash;
 
-use rustc_hash::FxHashMap;
+use indexmap::{IndexMap, IndexSet};
+use rustc_hash::{FxBuildHasher, FxHashMap};
+
+/// A set that iterates in insertion order, hashed like `FxHashSet`.
+pub type FxIndexSet<T> = IndexSet<T, FxBuildHasher>;
+
+/// A map that iterates in insertion order, hashed like `FxHashMap`.
+pub type FxIndexMap<K, V> = IndexMap<K, V, FxBuildHasher>;
 
 pub fn invert_hashmap<K, V>(hashmap: &FxHashMap<K, V>) -> FxHashMap<V, K>
 where
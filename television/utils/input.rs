@@ -42,6 +42,11 @@ pub enum InputRequest {
     DeleteNextWord,
     DeleteLine,
     DeleteTillEnd,
+    Yank,
+    YankPop,
+    TransposeChars,
+    Undo,
+    Redo,
 }
 
 #[derive(Debug, PartialOrd, PartialEq, Eq, Clone, Copy, Hash)]
@@ -53,11 +58,28 @@ pub struct StateChanged {
 #[allow(clippy::module_name_repetitions)]
 pub type InputResponse = Option<StateChanged>;
 
+/// Maximum number of edits that can be undone.
+const MAX_UNDO_STEPS: usize = 100;
+/// Maximum number of killed texts kept around for yanking.
+const MAX_KILL_RING_SIZE: usize = 16;
+
 /// An input buffer with cursor support.
+///
+/// Text removed by word and line deletions is pushed onto a kill ring from
+/// which it can be yanked back, and every edit can be undone and redone.
 #[derive(Default, Debug, Clone, PartialEq, Hash)]
 pub struct Input {
     value: String,
     cursor: usize,
+    /// Previous `(value, cursor)` states, most recent last.
+    undo_stack: Vec<(String, usize)>,
+    /// States that were undone, most recent last.
+    redo_stack: Vec<(String, usize)>,
+    /// Killed texts, most recent last.
+    kill_ring: Vec<String>,
+    /// Char range `(start, len)` of the text inserted by the last yank.
+    last_yank: Option<(usize, usize)>,
+    last_request: Option<InputRequest>,
 }
 
 impl Input {
@@ -65,7 +87,11 @@ impl Input {
     /// Cursor will be set to the given value's length.
     pub fn new(value: String) -> Self {
         let len = value.chars().count();
-        Self { value, cursor: len }
+        Self {
+            value,
+            cursor: len,
+            ..Default::default()
+        }
     }
 
     /// Set the value manually.
@@ -83,20 +109,110 @@ impl Input {
         self
     }
 
-    // Reset the cursor and value to default
+    // Reset the cursor and value to default, along with the undo history
     pub fn reset(&mut self) {
         self.cursor = Default::default();
         self.value = String::default();
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+        self.last_yank = None;
+        self.last_request = None;
     }
 
     /// Handle request and emit response.
-    #[allow(clippy::too_many_lines)]
+    ///
+    /// Edits are recorded in the undo history, consecutive character
+    /// insertions being grouped into a single step, and text removed by
+    /// word and line deletions is pushed onto the kill ring.
     pub fn handle(&mut self, req: InputRequest) -> InputResponse {
+        let response = match req {
+            InputRequest::Undo => self.undo(),
+            InputRequest::Redo => self.redo(),
+            _ => {
+                let previous = (self.value.clone(), self.cursor);
+                let response = self.apply(req);
+                if previous.0 != self.value {
+                    self.record_edit(req, previous);
+                }
+                response
+            }
+        };
+        self.last_request = Some(req);
+        response
+    }
+
+    fn record_edit(&mut self, req: InputRequest, previous: (String, usize)) {
+        use InputRequest::{
+            DeleteLine, DeleteNextWord, DeletePrevWord, DeleteTillEnd,
+            InsertChar,
+        };
+        if matches!(
+            req,
+            DeletePrevWord | DeleteNextWord | DeleteLine | DeleteTillEnd
+        ) {
+            // deletions only remove a contiguous run of characters, which
+            // starts at the new cursor position
+            let removed =
+                previous.0.chars().count() - self.value.chars().count();
+            let killed = previous.0.chars().skip(self.cursor).take(removed);
+            self.kill_ring.push(killed.collect());
+            if self.kill_ring.len() > MAX_KILL_RING_SIZE {
+                self.kill_ring.remove(0);
+            }
+        }
+        let typing = matches!(req, InsertChar(_))
+            && matches!(self.last_request, Some(InsertChar(_)));
+        if !typing {
+            self.undo_stack.push(previous);
+            if self.undo_stack.len() > MAX_UNDO_STEPS {
+                self.undo_stack.remove(0);
+            }
+        }
+        self.redo_stack.clear();
+    }
+
+    fn undo(&mut self) -> InputResponse {
+        let (value, cursor) = self.undo_stack.pop()?;
+        let current = std::mem::replace(&mut self.value, value);
+        self.redo_stack.push((current, self.cursor));
+        self.cursor = cursor;
+        Some(StateChanged {
+            value: true,
+            cursor: true,
+        })
+    }
+
+    fn redo(&mut self) -> InputResponse {
+        let (value, cursor) = self.redo_stack.pop()?;
+        let current = std::mem::replace(&mut self.value, value);
+        self.undo_stack.push((current, self.cursor));
+        self.cursor = cursor;
+        Some(StateChanged {
+            value: true,
+            cursor: true,
+        })
+    }
+
+    /// Insert `text` at the cursor, returning the number of chars inserted.
+    fn insert_str(&mut self, text: &str) -> usize {
+        let len = text.chars().count();
+        let byte_index = self
+            .value
+            .char_indices()
+            .nth(self.cursor)
+            .map_or(self.value.len(), |(i, _)| i);
+        self.value.insert_str(byte_index, text);
+        self.cursor += len;
+        len
+    }
+
+    #[allow(clippy::too_many_lines)]
+    fn apply(&mut self, req: InputRequest) -> InputResponse {
         use InputRequest::{
             DeleteLine, DeleteNextChar, DeleteNextWord, DeletePrevChar,
             DeletePrevWord, DeleteTillEnd, GoToEnd, GoToNextChar,
             GoToNextWord, GoToPrevChar, GoToPrevWord, GoToStart, InsertChar,
-            SetCursor,
+            Redo, SetCursor, TransposeChars, Undo, Yank, YankPop,
         };
         match req {
             SetCursor(pos) => {
@@ -334,6 +450,60 @@ impl Input {
                     cursor: false,
                 })
             }
+
+            Yank => {
+                let text = self.kill_ring.last()?.clone();
+                let start = self.cursor;
+                let len = self.insert_str(&text);
+                self.last_yank = Some((start, len));
+                Some(StateChanged {
+                    value: true,
+                    cursor: true,
+                })
+            }
+
+            YankPop => {
+                // only valid right after a yank, replaces the yanked text
+                // with the previous entry of the kill ring
+                if !matches!(self.last_request, Some(Yank | YankPop)) {
+                    return None;
+                }
+                let (start, len) = self.last_yank?;
+                self.kill_ring.rotate_right(1);
+                self.value = self
+                    .value
+                    .chars()
+                    .take(start)
+                    .chain(self.value.chars().skip(start + len))
+                    .collect();
+                self.cursor = start;
+                let text = self.kill_ring.last()?.clone();
+                let len = self.insert_str(&text);
+                self.last_yank = Some((start, len));
+                Some(StateChanged {
+                    value: true,
+                    cursor: true,
+                })
+            }
+
+            TransposeChars => {
+                // swap the characters around the cursor, or the last two
+                // when the cursor is at the end of the input
+                let mut chars = self.value.chars().collect::<Vec<_>>();
+                if self.cursor == 0 || chars.len() < 2 {
+                    return None;
+                }
+                let index = self.cursor.min(chars.len() - 1);
+                chars.swap(index - 1, index);
+                self.value = chars.into_iter().collect();
+                self.cursor = index + 1;
+                Some(StateChanged {
+                    value: true,
+                    cursor: true,
+                })
+            }
+
+            Undo | Redo => None,
         }
     }
 
@@ -588,4 +758,91 @@ mod tests {
         assert_eq!(input.visual_cursor(), 23);
         assert_eq!(input.visual_scroll(6), 18);
     }
+
+    #[test]
+    fn kill_and_yank() {
+        let mut input: Input = TEXT.into();
+
+        input.handle(InputRequest::DeletePrevWord);
+        assert_eq!(input.value(), "first second, ");
+        input.handle(InputRequest::GoToStart);
+        input.handle(InputRequest::DeleteNextWord);
+        assert_eq!(input.value(), "second, ");
+
+        let resp = input.handle(InputRequest::Yank);
+        assert_eq!(
+            resp,
+            Some(StateChanged {
+                value: true,
+                cursor: true,
+            })
+        );
+        assert_eq!(input.value(), "first second, ");
+        assert_eq!(input.cursor(), 6);
+
+        input.handle(InputRequest::YankPop);
+        assert_eq!(input.value(), "third.second, ");
+        assert_eq!(input.cursor(), 6);
+
+        // yank pop only follows a yank
+        input.handle(InputRequest::GoToEnd);
+        assert_eq!(input.handle(InputRequest::YankPop), None);
+
+        input.handle(InputRequest::GoToStart);
+        input.handle(InputRequest::DeleteTillEnd);
+        assert_eq!(input.value(), "");
+        input.handle(InputRequest::Yank);
+        assert_eq!(input.value(), "third.second, ");
+    }
+
+    #[test]
+    fn transpose_chars() {
+        let mut input: Input = "abc".into();
+
+        input.handle(InputRequest::TransposeChars);
+        assert_eq!(input.value(), "acb");
+        assert_eq!(input.cursor(), 3);
+
+        input.handle(InputRequest::SetCursor(1));
+        input.handle(InputRequest::TransposeChars);
+        assert_eq!(input.value(), "cab");
+        assert_eq!(input.cursor(), 2);
+
+        input.handle(InputRequest::GoToStart);
+        assert_eq!(input.handle(InputRequest::TransposeChars), None);
+    }
+
+    #[test]
+    fn undo_redo() {
+        let mut input = Input::default();
+        assert_eq!(input.handle(InputRequest::Undo), None);
+
+        for c in "foo".chars() {
+            input.handle(InputRequest::InsertChar(c));
+        }
+        input.handle(InputRequest::InsertChar(' '));
+        input.handle(InputRequest::GoToPrevChar);
+        input.handle(InputRequest::DeletePrevChar);
+        assert_eq!(input.value(), "fo ");
+
+        input.handle(InputRequest::Undo);
+        assert_eq!(input.value(), "foo ");
+        assert_eq!(input.cursor(), 3);
+        // consecutive insertions are undone at once
+        input.handle(InputRequest::Undo);
+        assert_eq!(input.value(), "");
+        assert_eq!(input.handle(InputRequest::Undo), None);
+
+        input.handle(InputRequest::Redo);
+        assert_eq!(input.value(), "foo ");
+        input.handle(InputRequest::Redo);
+        assert_eq!(input.value(), "fo ");
+        assert_eq!(input.handle(InputRequest::Redo), None);
+
+        // a new edit discards the undone ones
+        input.handle(InputRequest::Undo);
+        input.handle(InputRequest::InsertChar('!'));
+        assert_eq!(input.handle(InputRequest::Redo), None);
+        assert_eq!(input.value(), "foo! ");
+    }
 }
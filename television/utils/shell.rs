@@ -142,6 +142,34 @@ impl Shell {
             Shell::Nu => "nu",
         }
     }
+
+    /// Quotes `arg` so that this shell passes it as a single literal
+    /// argument.
+    pub fn quote(&self, arg: &str) -> String {
+        match self {
+            Shell::Bash | Shell::Zsh => {
+                format!("'{}'", arg.replace('\'', r"'\''"))
+            }
+            Shell::Fish => {
+                format!("'{}'", arg.replace('\\', r"\\").replace('\'', r"\'"))
+            }
+            Shell::Psh => format!("'{}'", arg.replace('\'', "''")),
+            // `%` can't be escaped within quotes, so it is left out of them
+            // and escaped with `^` to prevent variables from being expanded
+            Shell::Cmd => format!(
+                "\"{}\"",
+                arg.replace('"', "\"\"").replace('%', "\"^%\"")
+            ),
+            Shell::Nu => {
+                // a raw string ends with `'` followed by as many `#` as it
+                // started with, so use more than any run of `#` in `arg`
+                let longest_run =
+                    arg.split(|c| c != '#').map(str::len).max().unwrap_or(0);
+                let hashes = "#".repeat(longest_run + 1);
+                format!("r{hashes}'{arg}'{hashes}")
+            }
+        }
+    }
 }
 
 impl From<CliShell> for Shell {
@@ -369,4 +397,19 @@ mod tests {
         assert!(script.contains("Invoke-TvShellHistory"));
         assert!(script.contains("Set-PSReadLineKeyHandler"));
     }
+
+    #[test]
+    fn test_quote() {
+        assert_eq!(Shell::Bash.quote("it's $HOME"), r"'it'\''s $HOME'");
+        assert_eq!(Shell::Fish.quote(r"it's \n"), r"'it\'s \\n'");
+        assert_eq!(Shell::Psh.quote("it's"), "'it''s'");
+        assert_eq!(Shell::Cmd.quote(r#"say "hi""#), r#""say ""hi""""#);
+        assert_eq!(
+            Shell::Cmd.quote("%PATH% 100%"),
+            r#"""^%"PATH"^%" 100"^%"""#
+        );
+        assert_eq!(Shell::Nu.quote("it's"), "r#'it's'#");
+        assert_eq!(Shell::Nu.quote("a'#; rm -rf ~"), "r##'a'#; rm -rf ~'##");
+        assert_eq!(Shell::Nu.quote("'###"), "r####''###'####");
+    }
 }
@@ -0,0 +1,603 @@
+//! Checks cable channel files for mistakes before they are used, see
+//! `tv validate`.
+//!
+//! Unlike `load_cable`, which skips channels that fail to load, this reports
+//! every problem found along with the line it was found at.
+use std::{
+    fmt::{self, Display, Formatter},
+    ops::Range,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use rustc_hash::FxHashMap;
+use string_pipeline::MultiTemplate;
+use toml::de::{DeTable, DeValue};
+
+use crate::{
+    action::{Action, CUSTOM_ACTION_PREFIX},
+    cable::{
+        CHANNEL_FILE_FORMAT, Cable, ChannelTables, channel_tables,
+        get_cable_files, parse_prototype, read_cable_files,
+    },
+    channels::prototypes::{
+        ChannelPrototype, CommandSpec, SELECTION_PLACEHOLDER, Template,
+    },
+    event::Key,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+/// A problem found in a channel file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub path: PathBuf,
+    /// The 1-based line the problem was found at, if it could be located.
+    pub line: Option<usize>,
+    pub severity: Severity,
+    pub message: String,
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.path.display())?;
+        if let Some(line) = self.line {
+            write!(f, ":{}", line)?;
+        }
+        write!(f, ": {}: {}", self.severity, self.message)
+    }
+}
+
+/// Collects the channel files found at `paths`, walking directories for
+/// `.toml` files.
+pub fn collect_channel_files(paths: &[PathBuf]) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    for path in paths {
+        if path.is_dir() {
+            let mut found = get_cable_files(path);
+            found.sort();
+            files.extend(found);
+        } else {
+            files.push(path.clone());
+        }
+    }
+    files
+}
+
+/// Validates the channel files at `paths`.
+///
+/// Shortcuts are checked for conflicts between the validated files as well
+/// as with the channels of `cable` that aren't being validated. Channels may
+/// extend any of the validated channels or the ones found in `cable_dir`.
+pub fn validate_files(
+    paths: &[PathBuf],
+    cable: &Cable,
+    cable_dir: &Path,
+) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    let mut files = Vec::new();
+    for path in paths {
+        match std::fs::read_to_string(path) {
+            Ok(content) => files.push((path, content)),
+            Err(e) => {
+                diagnostics.push(Diagnostic {
+                    path: path.clone(),
+                    line: None,
+                    severity: Severity::Error,
+                    message: format!("Failed to read file: {}", e),
+                });
+            }
+        }
+    }
+    // validated channels take precedence over the ones of the cable
+    let cable_files = read_cable_files(cable_dir);
+    let channels = channel_tables(
+        cable_files
+            .values()
+            .chain(files.iter().map(|(_, content)| content)),
+    );
+
+    let mut shortcuts: Vec<Shortcut> = Vec::new();
+    for (path, content) in &files {
+        let (file_diagnostics, shortcut) =
+            validate_channel(path, content, &channels);
+        diagnostics.extend(file_diagnostics);
+        shortcuts.extend(shortcut);
+    }
+
+    // channels of the cable that are not being validated
+    let mut taken: FxHashMap<Key, String> = cable
+        .iter()
+        .filter(|(name, _)| !shortcuts.iter().any(|s| &s.channel == *name))
+        .filter_map(|(name, prototype)| {
+            let key = prototype.keybindings.as_ref()?.shortcut?;
+            Some((key, name.clone()))
+        })
+        .collect();
+    for shortcut in shortcuts {
+        let Some(key) = shortcut.key else {
+            continue;
+        };
+        if let Some(other) = taken.get(&key) {
+            diagnostics.push(Diagnostic {
+                path: shortcut.path,
+                line: shortcut.line,
+                severity: Severity::Error,
+                message: format!(
+                    "shortcut `{}` is already used by channel `{}`",
+                    key, other
+                ),
+            });
+        } else {
+            taken.insert(key, shortcut.channel);
+        }
+    }
+    diagnostics
+}
+
+/// The shortcut of a validated channel.
+struct Shortcut {
+    channel: String,
+    key: Option<Key>,
+    path: PathBuf,
+    line: Option<usize>,
+}
+
+/// Validates a single channel file, returning the problems found along with
+/// the channel shortcut if the file could be parsed.
+fn validate_channel(
+    path: &Path,
+    content: &str,
+    channels: &ChannelTables,
+) -> (Vec<Diagnostic>, Option<Shortcut>) {
+    let line_of = |span: Range<usize>| {
+        content
+            .get(..span.start)
+            .map(|before| before.matches('\n').count() + 1)
+    };
+    let diagnostic = |line: Option<usize>, severity, message| Diagnostic {
+        path: path.to_path_buf(),
+        line,
+        severity,
+        message,
+    };
+
+    if path
+        .extension()
+        .is_none_or(|extension| extension != CHANNEL_FILE_FORMAT)
+    {
+        return (
+            vec![diagnostic(
+                None,
+                Severity::Warning,
+                "channel files must have a `.toml` extension to be loaded"
+                    .to_string(),
+            )],
+            None,
+        );
+    }
+    let prototype = match parse_prototype(content, channels) {
+        Ok(prototype) => prototype,
+        Err(e) => {
+            let line = match e.downcast_ref::<toml::de::Error>() {
+                Some(e) => e.span().and_then(line_of),
+                // the channel it extends couldn't be resolved
+                None => DeTable::parse(content).ok().and_then(|document| {
+                    find_span(document.get_ref(), &["metadata", "extends"])
+                        .and_then(line_of)
+                }),
+            };
+            let message = match e.downcast::<toml::de::Error>() {
+                Ok(e) => e.message().to_string(),
+                Err(e) => e.to_string(),
+            };
+            return (
+                vec![diagnostic(
+                    line,
+                    Severity::Error,
+                    // keep each diagnostic on a single line
+                    message
+                        .lines()
+                        .map(str::trim)
+                        .filter(|l| !l.is_empty())
+                        .collect::<Vec<_>>()
+                        .join(" "),
+                )],
+                None,
+            );
+        }
+    };
+    // the prototype parsed, so the document does too
+    let document = DeTable::parse(content)
+        .map(toml::Spanned::into_inner)
+        .unwrap_or_default();
+    let locate = |keys: &[&str]| find_span(&document, keys).and_then(line_of);
+
+    let mut diagnostics = Vec::new();
+    if let Err(e) = prototype.validate() {
+        diagnostics.push(diagnostic(None, Severity::Error, e.to_string()));
+    }
+
+    // templates are checked as they are once parameters are substituted,
+    // leaving only references to undeclared parameters
+    let prototype = prototype.with_params(
+        &prototype
+            .metadata
+            .params
+            .keys()
+            .map(|name| (name.clone(), name.clone()))
+            .collect(),
+    );
+    let source_commands = commands(&["source"], &prototype.source.command);
+    for (keys, template) in source_commands.chain(templates(&prototype)) {
+        for name in param_references(template.raw()) {
+            let keys = keys.iter().map(String::as_str).collect::<Vec<_>>();
+            diagnostics.push(diagnostic(
+                locate(&keys),
+                Severity::Error,
+                format!(
+                    "unknown parameter `{}`, parameters must be declared in [metadata.params]",
+                    name
+                ),
+            ));
+        }
+    }
+
+    for (keys, template) in templates(&prototype) {
+        let keys = keys.iter().map(String::as_str).collect::<Vec<_>>();
+        // `{+}` is substituted before the rest of the template is parsed.
+        // Templates that don't parse still work as plain text where `{}` is
+        // replaced by the entry, which is what shell snippets using braces
+        // (awk, jq...) rely on.
+        if param_references(template.raw()).is_empty()
+            && template
+                .raw()
+                .split(SELECTION_PLACEHOLDER)
+                .any(|part| MultiTemplate::parse(part).is_err())
+        {
+            diagnostics.push(diagnostic(
+                locate(&keys),
+                Severity::Warning,
+                format!(
+                    "{:?} is not a valid template, it will only have `{{}}` replaced by the entry",
+                    template.raw()
+                ),
+            ));
+        }
+    }
+
+    if let Some(preview) = &prototype.preview
+        && preview.split.is_some()
+        && preview.command.inner.len() < 2
+    {
+        diagnostics.push(diagnostic(
+            locate(&["preview", "split"]),
+            Severity::Warning,
+            "`split` has no effect without several preview commands"
+                .to_string(),
+        ));
+    }
+
+    if let Some(keybindings) = &prototype.keybindings {
+        let raw_keys = match document
+            .iter()
+            .find(|(key, _)| key.get_ref() == "keybindings")
+            .map(|(_, value)| value.get_ref())
+        {
+            Some(DeValue::Table(table)) => table
+                .keys()
+                .map(|key| key.get_ref().to_string())
+                .collect::<Vec<_>>(),
+            _ => Vec::new(),
+        };
+        for raw_key in raw_keys {
+            let Some(actions) = Key::from_str(&raw_key)
+                .ok()
+                .and_then(|key| keybindings.bindings.get(&key))
+            else {
+                continue;
+            };
+            for action in actions.as_slice() {
+                let Action::ExternalAction(name) = action else {
+                    continue;
+                };
+                let message = match name.strip_prefix(CUSTOM_ACTION_PREFIX) {
+                    Some(action_name)
+                        if prototype.actions.contains_key(action_name) =>
+                    {
+                        continue;
+                    }
+                    Some(action_name) => format!(
+                        "`{}` is bound to `{}` but there is no [actions.{}]",
+                        raw_key, name, action_name
+                    ),
+                    None => format!(
+                        "`{}` is bound to unknown action `{}`",
+                        raw_key, name
+                    ),
+                };
+                diagnostics.push(diagnostic(
+                    locate(&["keybindings", &raw_key]),
+                    Severity::Error,
+                    message,
+                ));
+            }
+        }
+    }
+
+    for requirement in &prototype.metadata.requirements {
+        let mut requirement = requirement.clone();
+        requirement.init();
+        if !requirement.is_met() {
+            diagnostics.push(diagnostic(
+                locate(&["metadata", "requirements"]),
+                Severity::Warning,
+                format!(
+                    "required binary `{}` was not found in PATH",
+                    requirement.bin_name
+                ),
+            ));
+        }
+    }
+
+    let shortcut = Shortcut {
+        channel: prototype.metadata.name.clone(),
+        key: prototype
+            .keybindings
+            .as_ref()
+            .and_then(|keybindings| keybindings.shortcut),
+        path: path.to_path_buf(),
+        line: locate(&["keybindings", "shortcut"]),
+    };
+    (diagnostics, Some(shortcut))
+}
+
+fn keys(keys: &[&str]) -> Vec<String> {
+    keys.iter().map(ToString::to_string).collect()
+}
+
+/// The templates of a command spec along with their keys.
+fn commands<'a>(
+    section: &[&str],
+    command: &'a CommandSpec,
+) -> impl Iterator<Item = (Vec<String>, &'a Template)> {
+    command.inner.iter().enumerate().map(move |(i, c)| {
+        let mut keys = keys(section);
+        keys.extend(["command".to_string(), i.to_string()]);
+        (keys, c.template())
+    })
+}
+
+/// The names of the parameters referenced by `{param:<name>}` in `raw`.
+fn param_references(raw: &str) -> Vec<&str> {
+    const PREFIX: &str = "{param:";
+    raw.match_indices(PREFIX)
+        .filter_map(|(i, _)| {
+            let rest = &raw[i + PREFIX.len()..];
+            rest.find('}').map(|end| &rest[..end])
+        })
+        .collect()
+}
+
+/// The templates of a prototype that are formatted with entries, along with
+/// the keys they are defined at.
+fn templates(prototype: &ChannelPrototype) -> Vec<(Vec<String>, &Template)> {
+    let mut templates = Vec::new();
+    let source = &prototype.source;
+    templates.extend(
+        source
+            .display
+            .iter()
+            .map(|t| (keys(&["source", "display"]), t)),
+    );
+    templates.extend(
+        source
+            .output
+            .iter()
+            .map(|t| (keys(&["source", "output"]), t)),
+    );
+    if let Some(preview) = &prototype.preview {
+        templates.extend(commands(&["preview"], &preview.command));
+        templates.extend(
+            preview.path.iter().map(|t| (keys(&["preview", "path"]), t)),
+        );
+        templates.extend(
+            preview
+                .offset
+                .iter()
+                .map(|t| (keys(&["preview", "offset"]), t)),
+        );
+    }
+    if let Some(panel) = prototype
+        .ui
+        .as_ref()
+        .and_then(|ui| ui.preview_panel.as_ref())
+    {
+        templates.extend(
+            panel
+                .header
+                .iter()
+                .map(|t| (keys(&["ui", "preview_panel", "header"]), t)),
+        );
+        templates.extend(
+            panel
+                .footer
+                .iter()
+                .map(|t| (keys(&["ui", "preview_panel", "footer"]), t)),
+        );
+    }
+    let mut actions = prototype.actions.iter().collect::<Vec<_>>();
+    actions.sort_by_key(|(name, _)| name.as_str());
+    for (name, action) in actions {
+        templates.extend(commands(&["actions", name], &action.command));
+    }
+    templates
+}
+
+/// Finds the span of the value at `keys` in `table`, falling back to the
+/// closest parent that exists. Array items are addressed by their index.
+fn find_span(table: &DeTable, keys: &[&str]) -> Option<Range<usize>> {
+    let (first, rest) = keys.split_first()?;
+    let (key, value) = table.iter().find(|(key, _)| key.get_ref() == first)?;
+    let mut span = key.span();
+    let mut value = value;
+    for (i, key) in rest.iter().enumerate() {
+        let next = match value.get_ref() {
+            DeValue::Table(table) => {
+                return find_span(table, &rest[i..]).or(Some(span));
+            }
+            DeValue::Array(array) => {
+                key.parse::<usize>().ok().and_then(|i| array.get(i))
+            }
+            _ => None,
+        };
+        let Some(next) = next else {
+            break;
+        };
+        span = next.span();
+        value = next;
+    }
+    Some(span)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validate(content: &str) -> Vec<String> {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.toml");
+        std::fs::write(&path, content).unwrap();
+        validate_files(
+            std::slice::from_ref(&path),
+            &Cable::default(),
+            dir.path(),
+        )
+        .iter()
+        .map(|d| {
+            d.to_string()
+                .trim_start_matches(&path.display().to_string())
+                .to_string()
+        })
+        .collect()
+    }
+
+    #[test]
+    fn test_validate_parse_error() {
+        let diagnostics = validate(
+            r#"
+[metadata]
+name = "test"
+
+[source]
+command = "ls"
+ansi = "yes"
+"#,
+        );
+
+        assert_eq!(
+            diagnostics,
+            vec![
+                ":7: error: invalid type: string \"yes\", expected a boolean"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_templates_and_keybindings() {
+        let diagnostics = validate(
+            r#"
+[metadata]
+name = "test"
+
+[source]
+command = "ls"
+output = "{split:/:-1}"
+
+[preview]
+command = ["cat {}", "git diff {+} -- {unknown_op}"]
+
+[keybindings]
+ctrl-e = "actions:edit"
+ctrl-o = "actions:open"
+ctrl-x = "not_an_action"
+
+[actions.edit]
+command = "vim {}"
+"#,
+        );
+
+        assert_eq!(
+            diagnostics
+                .iter()
+                .map(|d| d.split(": ").next().unwrap())
+                .collect::<Vec<_>>(),
+            vec![":10", ":14", ":15"]
+        );
+        assert!(diagnostics[0].starts_with(":10: warning: \"git diff {+}"));
+        assert!(diagnostics[1].contains("there is no [actions.open]"));
+        assert!(diagnostics[2].contains("unknown action `not_an_action`"));
+    }
+
+    #[test]
+    fn test_validate_params() {
+        let diagnostics = validate(
+            r#"
+[metadata]
+name = "test"
+params = { namespace = { default = "default" } }
+
+[source]
+command = "kubectl -n {param:namespace} get pods"
+
+[preview]
+command = "kubectl -n {param:namespace} describe {param:context} {}"
+"#,
+        );
+
+        assert_eq!(
+            diagnostics,
+            vec![
+                ":10: error: unknown parameter `context`, parameters must be declared in [metadata.params]"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_duplicate_shortcuts() {
+        let dir = tempfile::tempdir().unwrap();
+        let channel = |name: &str| {
+            let path = dir.path().join(format!("{name}.toml"));
+            std::fs::write(
+                &path,
+                format!(
+                    "[metadata]\nname = \"{name}\"\n[source]\ncommand = \"ls\"\n[keybindings]\nshortcut = \"f1\"\n"
+                ),
+            )
+            .unwrap();
+            path
+        };
+        let paths = vec![channel("a"), channel("b")];
+
+        let diagnostics =
+            validate_files(&paths, &Cable::default(), dir.path());
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].path, paths[1]);
+        assert_eq!(diagnostics[0].line, Some(6));
+        assert!(diagnostics[0].message.contains("channel `a`"));
+    }
+}
@@ -142,7 +142,13 @@ async fn test_app_basic_search() {
 
     assert!(output.selected_entries.is_some());
     assert_eq!(
-        &output.selected_entries.unwrap().drain().next().unwrap().raw,
+        &output
+            .selected_entries
+            .unwrap()
+            .into_iter()
+            .next()
+            .unwrap()
+            .raw,
         "./file1.txt"
     );
 }
@@ -207,7 +213,10 @@ async fn test_app_exact_search_multiselect() {
     assert!(selected_entries.is_some());
     // should contain a single entry with the prompt
     assert!(!selected_entries.as_ref().unwrap().is_empty());
-    assert_eq!(selected_entries.unwrap().drain().next().unwrap().raw, "fie");
+    assert_eq!(
+        selected_entries.unwrap().into_iter().next().unwrap().raw,
+        "fie"
+    );
 }
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 3)]
@@ -264,7 +273,13 @@ async fn test_app_exits_when_select_1_and_only_one_result() {
 
     assert!(output.selected_entries.is_some());
     assert_eq!(
-        &output.selected_entries.unwrap().drain().next().unwrap().raw,
+        &output
+            .selected_entries
+            .unwrap()
+            .into_iter()
+            .next()
+            .unwrap()
+            .raw,
         "file1.txt"
     );
 }
@@ -1,4 +0,0 @@
-pub mod cli;
-
-#[path = "common/mod.rs"]
-mod common;
@@ -1,2 +0,0 @@
-mod channel_overrides;
-mod cli_overrides;
@@ -1,4 +0,0 @@
-pub mod config;
-
-#[path = "common/mod.rs"]
-mod common;